@@ -1,3 +1,4 @@
 pub mod interactive;
+pub mod remote;
 pub mod split_hunk;
 pub mod stack_sync;
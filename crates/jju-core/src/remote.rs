@@ -0,0 +1,34 @@
+/// Remote used when neither a flag nor config names one
+pub const DEFAULT_REMOTE: &str = "origin";
+
+/// The remotes a repo syncs with
+///
+/// In a fork workflow trunk comes from `upstream` while bookmarks are tracked on
+/// and pushed to `origin`; otherwise both are the same remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remotes {
+    /// Where trunk is fetched from and its bookmark is synced with
+    pub trunk: String,
+    /// Where bookmarks are tracked and pushed
+    pub push: String,
+}
+
+impl Remotes {
+    /// Every remote to fetch, trunk first and without repeats
+    pub fn to_fetch(&self) -> Vec<&str> {
+        if self.trunk == self.push {
+            vec![&self.trunk]
+        } else {
+            vec![&self.trunk, &self.push]
+        }
+    }
+}
+
+impl Default for Remotes {
+    fn default() -> Self {
+        Self {
+            trunk: DEFAULT_REMOTE.to_string(),
+            push: DEFAULT_REMOTE.to_string(),
+        }
+    }
+}
@@ -3,10 +3,14 @@ pub struct LineRange(pub usize, pub usize);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SplitSelectionPlan {
+    /// Only consider files whose path contains this string
+    pub file_filter: Option<String>,
     pub hunk_indices: Option<Vec<usize>>,
     pub line_ranges: Option<Vec<LineRange>>,
     pub pattern: Option<String>,
     pub invert: bool,
+    /// Select individual changed lines instead of whole hunks
+    pub line_precise: bool,
 }
 
 impl SplitSelectionPlan {
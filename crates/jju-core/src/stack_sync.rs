@@ -1,14 +1,79 @@
+use crate::remote::Remotes;
+use std::str::FromStr;
+
+/// Which stacks a sync rebases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StackSyncScope {
+    /// The stack containing the working copy
+    #[default]
+    Current,
+    /// Every stack of mutable commits on top of trunk
+    All,
+}
+
+/// What stack sync does with a stack its rebase left conflicted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictRollback {
+    /// Keep the conflicts, offering to roll back when interactive
+    #[default]
+    Keep,
+    /// Restore each conflicted stack to its state before it was rebased
+    Stack,
+    /// Restore the whole sync to the operation recorded before it started
+    All,
+}
+
+impl FromStr for ConflictRollback {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "keep" => Ok(Self::Keep),
+            "stack" => Ok(Self::Stack),
+            "all" => Ok(Self::All),
+            _ => Err(format!(
+                "unknown rollback `{value}`, expected keep, stack or all"
+            )),
+        }
+    }
+}
+
+/// A commit that rebasing a stack moves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackCommitPlan {
+    pub change_id: String,
+    pub description: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StackRootPlan {
     pub change_id: String,
     pub description: String,
+    /// Every commit the rebase moves, the root first
+    pub commits: Vec<StackCommitPlan>,
+    /// Change id of the trunk commit that already holds the stack's changes,
+    /// when it was squash or rebase merged
+    pub landed: Option<String>,
+}
+
+/// The revision stacks are rebased onto
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackSyncTarget {
+    /// What `jj rebase --onto` is given
+    pub revision: String,
+    /// How the target is shown to the user
+    pub label: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StackSyncPlan {
     pub trunk: String,
+    pub onto: StackSyncTarget,
+    pub remotes: Remotes,
+    pub scope: StackSyncScope,
     pub roots: Vec<StackRootPlan>,
     pub push_bookmark_after_sync: bool,
+    pub rollback: ConflictRollback,
 }
 
 impl StackSyncPlan {
@@ -16,3 +81,34 @@ impl StackSyncPlan {
         self.roots.is_empty()
     }
 }
+
+/// A commit left with conflicts, and the files they are in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictedCommit {
+    pub change_id: String,
+    pub files: Vec<String>,
+}
+
+/// How rebasing one stack root went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackRootResult {
+    pub root: StackRootPlan,
+    pub error: Option<String>,
+    /// Commits of the stack that the rebase newly left conflicted
+    pub conflicts: Vec<ConflictedCommit>,
+    /// Whether the stack was restored to its state before the rebase
+    pub rolled_back: bool,
+    /// Whether the root became empty on the new base and was abandoned
+    pub emptied: bool,
+}
+
+impl StackRootResult {
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Conflicts the sync left in place
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty() && !self.rolled_back
+    }
+}
@@ -0,0 +1,47 @@
+mod assign;
+mod command;
+mod plan;
+
+pub use assign::Unabsorbable;
+pub use command::AbsorbCommand;
+use eyre::Result;
+pub use plan::{AbsorbDestination, AbsorbPlan};
+
+use crate::repo::JjRepo;
+use crate::split_hunk::load_diff;
+
+#[derive(Debug, Clone)]
+pub struct AbsorbOptions {
+    pub revision: String,
+    pub file_filter: Option<String>,
+    pub preview: bool,
+    pub dry_run: bool,
+}
+
+/// What an absorb did, for callers that report it themselves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbsorbSummary {
+    pub absorbed: usize,
+    pub destinations: usize,
+    pub skipped: usize,
+    pub conflicted: usize,
+}
+
+/// Absorb every hunk of `revision` that has a single owner in its mutable ancestors
+pub fn absorb(revision: &str) -> Result<AbsorbSummary> {
+    let diff = load_diff(revision)?;
+    let mut repo = JjRepo::load(None)?;
+    let source = repo.eval_revset_single(revision)?;
+    let plan = AbsorbPlan::build(&repo, &source, diff)?;
+    if plan.destinations().is_empty() {
+        return Ok(AbsorbSummary {
+            absorbed: 0,
+            destinations: 0,
+            skipped: plan.skipped_count(),
+            conflicted: 0,
+        });
+    }
+
+    let outcome = command::absorb_plan(&mut repo, &source, &plan)?;
+    Ok(command::summarize(&plan, &outcome))
+}
@@ -0,0 +1,162 @@
+use crate::split_hunk::{DiffHunk, DiffLineKind};
+
+/// Why a hunk stays in the absorbed revision
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unabsorbable {
+    /// There are no parent lines around the hunk, as in a new file
+    NoContext,
+    /// The hunk touches lines that come from immutable history
+    Immutable,
+    /// The hunk touches lines from more than one mutable ancestor
+    Ambiguous,
+    /// The file is added, deleted, renamed or binary, which only moves as a whole
+    WholeFile,
+}
+
+impl Unabsorbable {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::NoContext => "no surrounding lines",
+            Self::Immutable => "touches immutable lines",
+            Self::Ambiguous => "touches lines from several commits",
+            Self::WholeFile => "whole-file change",
+        }
+    }
+}
+
+/// The single owner of every parent line that `hunk` touches
+///
+/// `owners` holds the owner of each parent line, `None` for lines outside the
+/// destination domain. Removed lines count as touched, and a run of additions
+/// with nothing removed touches the parent lines right above and below it
+pub(crate) fn hunk_owner<T: Clone + PartialEq>(
+    hunk: &DiffHunk,
+    owners: &[Option<T>],
+) -> Result<T, Unabsorbable> {
+    let lines = hunk.lines();
+    let positions = hunk.old_positions();
+    let mut touched = Vec::new();
+
+    let mut index = 0;
+    while index < lines.len() {
+        if lines[index].kind == DiffLineKind::Context {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < lines.len() && lines[index].kind != DiffLineKind::Context {
+            index += 1;
+        }
+        let removed = (start..index)
+            .filter(|&line| lines[line].kind.is_removed())
+            .map(|line| positions[line])
+            .collect::<Vec<_>>();
+        if removed.is_empty() {
+            let below = positions[start];
+            touched.extend(
+                [below.saturating_sub(1), below]
+                    .into_iter()
+                    .filter(|&position| position >= 1 && position <= owners.len()),
+            );
+        } else {
+            touched.extend(removed);
+        }
+    }
+
+    if touched.is_empty() {
+        return Err(Unabsorbable::NoContext);
+    }
+
+    let mut owner: Option<T> = None;
+    for position in touched {
+        let Some(Some(line_owner)) = owners.get(position - 1) else {
+            return Err(Unabsorbable::Immutable);
+        };
+        match &owner {
+            Some(existing) if existing != line_owner => return Err(Unabsorbable::Ambiguous),
+            Some(_) => {}
+            None => owner = Some(line_owner.clone()),
+        }
+    }
+
+    owner.ok_or(Unabsorbable::NoContext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Unabsorbable, hunk_owner};
+    use crate::split_hunk::ParsedDiff;
+
+    fn single_hunk(diff: &str) -> crate::split_hunk::DiffHunk {
+        ParsedDiff::parse(diff).files()[0].hunks()[0].clone()
+    }
+
+    #[test]
+    fn test_hunk_owner_uses_owner_of_removed_lines() {
+        let hunk = single_hunk(
+            r#"diff --git a/a.txt b/a.txt
+@@ -1,3 +1,3 @@
+ one
+-two
++TWO
+ three
+"#,
+        );
+
+        let owner = hunk_owner(&hunk, &[Some("base"), Some("edit"), Some("base")]);
+
+        assert_eq!(owner, Ok("edit"));
+    }
+
+    #[test]
+    fn test_hunk_owner_uses_neighbours_of_pure_insertion() {
+        let hunk = single_hunk(
+            r#"diff --git a/a.txt b/a.txt
+@@ -1,4 +1,5 @@
+ one
+ two
++inserted
+ three
+ four
+"#,
+        );
+
+        assert_eq!(
+            hunk_owner(&hunk, &[Some("a"), Some("b"), Some("b"), Some("a")]),
+            Ok("b")
+        );
+        assert_eq!(
+            hunk_owner(&hunk, &[Some("b"), Some("a"), Some("b"), Some("b")]),
+            Err(Unabsorbable::Ambiguous)
+        );
+    }
+
+    #[test]
+    fn test_hunk_owner_leaves_immutable_and_new_file_hunks() {
+        let modified = single_hunk(
+            r#"diff --git a/a.txt b/a.txt
+@@ -1,2 +1,2 @@
+-one
++ONE
+ two
+"#,
+        );
+        let new_file = single_hunk(
+            r#"diff --git a/b.txt b/b.txt
+@@ -0,0 +1,2 @@
++one
++two
+"#,
+        );
+
+        assert_eq!(
+            hunk_owner(&modified, &[None, Some("a")]),
+            Err(Unabsorbable::Immutable)
+        );
+        assert_eq!(
+            hunk_owner::<&str>(&new_file, &[]),
+            Err(Unabsorbable::NoContext)
+        );
+    }
+}
@@ -0,0 +1,193 @@
+use super::plan::AbsorbPlan;
+use super::{AbsorbOptions, AbsorbSummary};
+use crate::repo::{AbsorbOutcome, JjRepo};
+use crate::split_hunk::{
+    DiffHunk, ParsedDiff, SelectedHunk, hunk_header, load_diff, print_hunk_lines,
+};
+use colored::Colorize;
+use eyre::Result;
+use jj_lib::commit::Commit;
+
+#[derive(Debug, Clone)]
+pub struct AbsorbCommand {
+    options: AbsorbOptions,
+}
+
+impl AbsorbCommand {
+    pub fn new(options: AbsorbOptions) -> Self {
+        Self { options }
+    }
+
+    pub fn run(self) -> Result<()> {
+        let diff =
+            load_diff(&self.options.revision)?.filter_by_path(self.options.file_filter.as_deref());
+        if diff.is_empty() {
+            println!("{}", "No changes to absorb".yellow());
+            return Ok(());
+        }
+
+        // loaded after the diff so it sees the snapshot `jj diff` took of the working copy
+        let mut repo = JjRepo::load(None)?;
+        let source = repo.eval_revset_single(&self.options.revision)?;
+        let plan = AbsorbPlan::build(&repo, &source, diff)?;
+        if self.options.preview {
+            self.preview(&plan);
+            return Ok(());
+        }
+
+        self.print_assignments(&plan);
+        if plan.destinations().is_empty() {
+            println!("\n{}", "No hunks could be absorbed".yellow());
+            return Ok(());
+        }
+
+        if self.options.dry_run {
+            println!(
+                "\n{}",
+                format!(
+                    "Dry run - would absorb {} hunks into {} revisions",
+                    plan.absorbed_count(),
+                    plan.destinations().len()
+                )
+                .yellow()
+            );
+            return Ok(());
+        }
+
+        let outcome = absorb_plan(&mut repo, &source, &plan)?;
+        println!(
+            "\n{} {} hunks into {} revisions",
+            "Absorbed".green(),
+            plan.absorbed_count().to_string().cyan(),
+            outcome.rewritten.len().to_string().cyan()
+        );
+        for commit in outcome
+            .rewritten
+            .iter()
+            .filter(|commit| commit.has_conflict())
+        {
+            println!("{} {}", "New conflicts in".red(), destination_label(commit));
+        }
+        if outcome.source_abandoned {
+            println!(
+                "{}",
+                format!("{} is now empty and was abandoned", self.options.revision).yellow()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn preview(&self, plan: &AbsorbPlan) {
+        for_each_hunk(plan.diff(), |path, index, (global_index, hunk)| {
+            if index.hunk_index == 0 {
+                println!("\n{}", path.cyan().bold());
+            }
+            println!(
+                "\n  {} {} {}",
+                hunk_header(global_index, hunk),
+                "->".bright_black(),
+                self.assignment_label(plan, &index)
+            );
+            print_hunk_lines(hunk);
+        });
+    }
+
+    fn print_assignments(&self, plan: &AbsorbPlan) {
+        for destination in plan.destinations() {
+            println!(
+                "{} {}",
+                "Absorbing into".green(),
+                destination_label(&destination.commit)
+            );
+            for_each_hunk(plan.diff(), |path, index, (global_index, hunk)| {
+                if destination.hunks.contains(&index) {
+                    println!("  {} {}", path, hunk_header(global_index, hunk));
+                }
+            });
+        }
+
+        if plan.skipped_count() > 0 {
+            println!(
+                "{}",
+                format!("Staying in {}", self.options.revision).yellow()
+            );
+            for_each_hunk(plan.diff(), |path, index, (global_index, hunk)| {
+                if let Some(Err(reason)) = plan.assignment(&index) {
+                    println!(
+                        "  {} {} {}",
+                        path,
+                        hunk_header(global_index, hunk),
+                        format!("({})", reason.describe()).bright_black()
+                    );
+                }
+            });
+        }
+    }
+
+    fn assignment_label(&self, plan: &AbsorbPlan, hunk: &SelectedHunk) -> String {
+        match plan.assignment(hunk) {
+            Some(Ok(destination)) => destination_label(&destination.commit),
+            Some(Err(reason)) => format!(
+                "{} {}",
+                format!("stays in {}", self.options.revision).yellow(),
+                format!("({})", reason.describe()).bright_black()
+            ),
+            None => format!("stays in {}", self.options.revision)
+                .yellow()
+                .to_string(),
+        }
+    }
+}
+
+/// Absorb the hunks routed by `plan` out of `source` in one operation
+pub(super) fn absorb_plan(
+    repo: &mut JjRepo,
+    source: &Commit,
+    plan: &AbsorbPlan,
+) -> Result<AbsorbOutcome> {
+    let layers = plan.layers(repo, source)?;
+    repo.absorb_commit(source, &layers)
+}
+
+pub(super) fn summarize(plan: &AbsorbPlan, outcome: &AbsorbOutcome) -> AbsorbSummary {
+    AbsorbSummary {
+        absorbed: plan.absorbed_count(),
+        destinations: outcome.rewritten.len(),
+        skipped: plan.skipped_count(),
+        conflicted: outcome
+            .rewritten
+            .iter()
+            .filter(|commit| commit.has_conflict())
+            .count(),
+    }
+}
+
+/// Visit every hunk with its file path, position and global preview index
+fn for_each_hunk(diff: &ParsedDiff, mut visit: impl FnMut(&str, SelectedHunk, (usize, &DiffHunk))) {
+    let mut global_index = 0;
+    for (file_index, file) in diff.files().iter().enumerate() {
+        for (hunk_index, hunk) in file.hunks().iter().enumerate() {
+            visit(
+                file.path(),
+                SelectedHunk {
+                    file_index,
+                    hunk_index,
+                },
+                (global_index, hunk),
+            );
+            global_index += 1;
+        }
+    }
+}
+
+fn destination_label(commit: &Commit) -> String {
+    let change_id = commit.change_id().reverse_hex();
+    let description = JjRepo::description_first_line(commit);
+    let description = if description.is_empty() {
+        "(no description)".bright_black()
+    } else {
+        description.white()
+    };
+    format!("{} {}", change_id[..8].purple(), description)
+}
@@ -0,0 +1,187 @@
+use super::assign::{Unabsorbable, hunk_owner};
+use crate::repo::{AbsorbLayer, JjRepo};
+use crate::split_hunk::{ParsedDiff, SelectedHunk, SplitHunkApplication, SplitHunkPlan};
+use eyre::{Result, bail};
+use jj_lib::commit::Commit;
+use jj_lib::object_id::ObjectId;
+
+/// Ancestors that hunks may be absorbed into
+const DESTINATION_DOMAIN: &str = "mutable()";
+
+/// Hunks routed to one ancestor
+#[derive(Debug, Clone)]
+pub struct AbsorbDestination {
+    pub commit: Commit,
+    pub hunks: Vec<SelectedHunk>,
+}
+
+/// Where each hunk of a revision goes
+#[derive(Debug, Clone)]
+pub struct AbsorbPlan {
+    diff: ParsedDiff,
+    destinations: Vec<AbsorbDestination>,
+    skipped: Vec<(SelectedHunk, Unabsorbable)>,
+}
+
+impl AbsorbPlan {
+    /// Route every hunk of `diff` to the mutable ancestor of `source` that last
+    /// changed the lines it touches
+    pub(crate) fn build(repo: &JjRepo, source: &Commit, diff: ParsedDiff) -> Result<Self> {
+        let source_hex = source.id().hex();
+        if !repo
+            .eval_revset(&format!("{source_hex} & immutable()"))?
+            .is_empty()
+        {
+            bail!("revision {} is immutable", &source_hex[..12]);
+        }
+
+        let mut destinations: Vec<AbsorbDestination> = Vec::new();
+        let mut skipped = Vec::new();
+        for (file_index, file) in diff.files().iter().enumerate() {
+            if file.is_whole_file() {
+                skipped.extend((0..file.hunks().len()).map(|hunk_index| {
+                    let selected = SelectedHunk {
+                        file_index,
+                        hunk_index,
+                    };
+                    (selected, Unabsorbable::WholeFile)
+                }));
+                continue;
+            }
+
+            let owners = repo.line_owners(source, file.path(), DESTINATION_DOMAIN)?;
+            for (hunk_index, hunk) in file.hunks().iter().enumerate() {
+                let selected = SelectedHunk {
+                    file_index,
+                    hunk_index,
+                };
+                let owner = match hunk_owner(hunk, &owners) {
+                    Ok(owner) => owner,
+                    Err(reason) => {
+                        skipped.push((selected, reason));
+                        continue;
+                    }
+                };
+
+                match destinations
+                    .iter_mut()
+                    .find(|destination| destination.commit.id() == &owner)
+                {
+                    Some(destination) => destination.hunks.push(selected),
+                    None => destinations.push(AbsorbDestination {
+                        commit: repo.commit_by_id_hex(&owner.hex())?,
+                        hunks: vec![selected],
+                    }),
+                }
+            }
+        }
+
+        Ok(Self {
+            diff,
+            destinations,
+            skipped,
+        })
+    }
+
+    pub fn diff(&self) -> &ParsedDiff {
+        &self.diff
+    }
+
+    pub fn destinations(&self) -> &[AbsorbDestination] {
+        &self.destinations
+    }
+
+    pub fn absorbed_count(&self) -> usize {
+        self.destinations
+            .iter()
+            .map(|destination| destination.hunks.len())
+            .sum()
+    }
+
+    pub fn skipped_count(&self) -> usize {
+        self.skipped.len()
+    }
+
+    /// The destination of `hunk`, or the reason it stays where it is, and `None`
+    /// for a hunk that isn't in the plan's diff
+    pub fn assignment(
+        &self,
+        hunk: &SelectedHunk,
+    ) -> Option<Result<&AbsorbDestination, Unabsorbable>> {
+        if let Some(destination) = self
+            .destinations
+            .iter()
+            .find(|destination| destination.hunks.contains(hunk))
+        {
+            return Some(Ok(destination));
+        }
+
+        self.skipped
+            .iter()
+            .find(|(skipped, _)| skipped == hunk)
+            .map(|(_, reason)| Err(*reason))
+    }
+
+    /// The changes for each destination, applied to `source`'s parent files
+    pub(crate) fn layers(&self, repo: &JjRepo, source: &Commit) -> Result<Vec<AbsorbLayer>> {
+        self.destinations
+            .iter()
+            .map(|destination| {
+                let plan = SplitHunkPlan::new(self.diff.clone(), destination.hunks.clone());
+                let layer = SplitHunkApplication::build(&plan, repo, source)?.into_layer("");
+                Ok(AbsorbLayer {
+                    destination: destination.commit.id().clone(),
+                    files: layer.files,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AbsorbPlan;
+    use crate::absorb::Unabsorbable;
+    use crate::repo::test_support::{TempRepo, write_commit};
+    use crate::split_hunk::{ParsedDiff, SelectedHunk};
+    use jj_lib::repo::Repo;
+
+    #[test]
+    fn test_assignment_reports_the_recorded_reason() {
+        let temp = TempRepo::init("absorb-plan");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, first) = write_commit(
+            &temp.repo,
+            &root_commit,
+            "add a",
+            &[("a.txt", "one\ntwo\nthree\n")],
+        );
+        let (repo, second) =
+            write_commit(&repo, &first, "edit two", &[("a.txt", "one\n2\nthree\n")]);
+        let (_, source) = write_commit(
+            &repo,
+            &second,
+            "edit one and two",
+            &[("a.txt", "ONE\nTWO\nthree\n")],
+        );
+        let diff = ParsedDiff::parse(
+            "diff --git a/a.txt b/a.txt\n@@ -1,3 +1,3 @@\n-one\n-2\n+ONE\n+TWO\n three\n",
+        );
+
+        let plan = AbsorbPlan::build(&temp.load(), &source, diff).expect("plan");
+
+        let hunk = SelectedHunk {
+            file_index: 0,
+            hunk_index: 0,
+        };
+        assert!(matches!(
+            plan.assignment(&hunk),
+            Some(Err(Unabsorbable::Ambiguous))
+        ));
+        let missing = SelectedHunk {
+            file_index: 0,
+            hunk_index: 1,
+        };
+        assert!(plan.assignment(&missing).is_none());
+    }
+}
@@ -1,3 +1,5 @@
+pub mod absorb;
+pub mod move_hunks;
 pub mod ops;
 pub mod repo;
 pub mod split_hunk;
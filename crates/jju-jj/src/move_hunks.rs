@@ -0,0 +1,130 @@
+use crate::repo::JjRepo;
+use crate::split_hunk::{
+    SplitHunkApplication, SplitHunkPlanner, load_diff, parse_hunk_indices, parse_line_ranges,
+    preview_plan,
+};
+use colored::Colorize;
+use eyre::{Result, bail};
+use jj_lib::commit::Commit;
+use jju_core::split_hunk::SplitSelectionPlan;
+
+#[derive(Debug, Clone)]
+pub struct MoveHunksOptions {
+    pub from: String,
+    pub to: String,
+    pub file_filter: Option<String>,
+    pub lines: Option<String>,
+    pub hunks: Option<String>,
+    pub pattern: Option<String>,
+    pub preview: bool,
+    pub invert: bool,
+    pub line_precise: bool,
+    pub dry_run: bool,
+}
+
+impl MoveHunksOptions {
+    fn planner(&self) -> Result<SplitHunkPlanner> {
+        SplitHunkPlanner::new(SplitSelectionPlan {
+            file_filter: self.file_filter.clone(),
+            hunk_indices: self.hunks.as_deref().map(parse_hunk_indices).transpose()?,
+            line_ranges: self.lines.as_deref().map(parse_line_ranges).transpose()?,
+            pattern: self.pattern.clone(),
+            invert: self.invert,
+            line_precise: self.line_precise,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MoveHunksCommand {
+    options: MoveHunksOptions,
+}
+
+impl MoveHunksCommand {
+    pub fn new(options: MoveHunksOptions) -> Self {
+        Self { options }
+    }
+
+    pub fn run(self) -> Result<()> {
+        let diff =
+            load_diff(&self.options.from)?.filter_by_path(self.options.file_filter.as_deref());
+        if diff.is_empty() {
+            println!("{}", "No matching changes in revision".yellow());
+            return Ok(());
+        }
+
+        let plan = self.options.planner()?.build(diff);
+        if self.options.preview {
+            preview_plan(&plan);
+            return Ok(());
+        }
+
+        if !plan.has_selection() {
+            println!("{}", "No hunks matched selection criteria".yellow());
+            return Ok(());
+        }
+
+        // loaded after the diff so it sees the snapshot `jj diff` took of the working copy
+        let mut repo = JjRepo::load(None)?;
+        let source = resolve_mutable(&repo, &self.options.from)?;
+        let destination = resolve_mutable(&repo, &self.options.to)?;
+        let application = SplitHunkApplication::build(&plan, &repo, &source)?;
+        if self.options.dry_run {
+            println!(
+                "\n{}",
+                format!(
+                    "Dry run - would move {} hunks from {} into {}:",
+                    plan.selected_count(),
+                    self.options.from,
+                    self.options.to
+                )
+                .yellow()
+            );
+            let mut paths = application.new_contents.keys().collect::<Vec<_>>();
+            paths.sort();
+            for path in paths {
+                println!("  {}", path.cyan());
+            }
+            return Ok(());
+        }
+
+        let layer = application.into_layer("");
+        let outcome = repo.move_changes(&source, &destination, &layer.files)?;
+        println!(
+            "{} {} hunks from {} into {}",
+            "Moved".green(),
+            plan.selected_count().to_string().cyan(),
+            self.options.from.cyan(),
+            self.options.to.cyan()
+        );
+        if outcome.destination.has_conflict() && !destination.has_conflict() {
+            println!(
+                "{}",
+                format!(
+                    "The moved hunks conflict with {}, resolve them there",
+                    self.options.to
+                )
+                .red()
+            );
+        }
+        if outcome.source.has_conflict() && !source.has_conflict() {
+            println!(
+                "{}",
+                format!("{} now has conflicts", self.options.from).red()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn resolve_mutable(repo: &JjRepo, revision: &str) -> Result<Commit> {
+    let commit = repo.eval_revset_single(revision)?;
+    if !repo
+        .eval_revset(&format!("({revision}) & immutable()"))?
+        .is_empty()
+    {
+        bail!("revision {revision} is immutable");
+    }
+    Ok(commit)
+}
@@ -1,6 +1,7 @@
 mod ancestry;
 mod bookmark;
 mod command;
+mod config;
 mod conflict;
 mod diff;
 mod git;
@@ -10,6 +11,9 @@ mod revision;
 
 pub use ancestry::is_ancestor;
 pub use bookmark::BookmarkOps;
+pub use command::with_cancellation;
+pub(crate) use command::{read_with_stderr, run_with_stderr};
+pub use config::{ConfigOps, PUSH_REMOTE_KEY, REMOTE_KEY};
 pub use conflict::ConflictOps;
 pub use diff::DiffOps;
 pub use git::GitOps;
@@ -26,8 +26,8 @@ impl BookmarkOps {
         run_with_stderr(cmd!("jj", "bookmark", "delete", name))
     }
 
-    pub fn track(self, name: &str) -> Result<()> {
-        let remote_ref = format!("{name}@origin");
+    pub fn track(self, name: &str, remote: &str) -> Result<()> {
+        let remote_ref = format!("{name}@{remote}");
         run_with_stderr(cmd!("jj", "bookmark", "track", &remote_ref))
     }
 }
@@ -1,8 +1,69 @@
 use duct::cmd;
 use eyre::Result;
+use std::cell::RefCell;
+use std::time::Duration;
+
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+thread_local! {
+    static CANCEL_CHECK: RefCell<Option<Box<dyn Fn() -> bool>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with `is_cancelled` checked while its commands run, killing the
+/// running command and failing with "cancelled" once it returns true
+pub fn with_cancellation<T>(is_cancelled: impl Fn() -> bool + 'static, f: impl FnOnce() -> T) -> T {
+    let previous = CANCEL_CHECK.with(|check| check.replace(Some(Box::new(is_cancelled))));
+    let _restore = RestoreCancelCheck(previous);
+    f()
+}
+
+/// Puts the outer cancel check back, even when `f` panics
+struct RestoreCancelCheck(Option<Box<dyn Fn() -> bool>>);
+
+impl Drop for RestoreCancelCheck {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CANCEL_CHECK.with(|check| *check.borrow_mut() = previous);
+    }
+}
+
+fn is_cancelled() -> bool {
+    CANCEL_CHECK.with(|check| {
+        check
+            .borrow()
+            .as_ref()
+            .is_some_and(|is_cancelled| is_cancelled())
+    })
+}
+
+/// Wait for `handle` to exit, killing it and failing with "cancelled" once the
+/// cancel check returns true
+fn wait(handle: duct::Handle) -> Result<std::process::Output> {
+    loop {
+        if is_cancelled() {
+            handle.kill()?;
+            eyre::bail!("cancelled");
+        }
+        if let Some(output) = handle.try_wait()? {
+            return Ok(output.clone());
+        }
+        std::thread::sleep(CANCEL_POLL_INTERVAL);
+    }
+}
+
+pub(crate) fn run_with_stderr(expr: duct::Expression) -> Result<()> {
+    output_with_stderr(expr.stdout_null()).map(|_| ())
+}
+
+/// The stdout of `expr` like `read_stdout`, failing with its stderr
+pub(crate) fn read_with_stderr(expr: duct::Expression) -> Result<String> {
+    let output = output_with_stderr(expr.stdout_capture())?;
+    trimmed_stdout(output.stdout)
+}
+
+fn output_with_stderr(expr: duct::Expression) -> Result<std::process::Output> {
+    let output = wait(expr.stderr_capture().unchecked().start()?)?;
 
-pub(super) fn run_with_stderr(expr: duct::Expression) -> Result<()> {
-    let output = expr.stdout_null().stderr_capture().unchecked().run()?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         let stderr = stderr.trim();
@@ -12,9 +73,73 @@ pub(super) fn run_with_stderr(expr: duct::Expression) -> Result<()> {
             eyre::bail!("{stderr}");
         }
     }
-    Ok(())
+    Ok(output)
 }
 
 pub(super) fn capture_stdout(args: &[&str]) -> Result<String> {
-    Ok(cmd("jj", args).stdout_capture().stderr_null().read()?)
+    read_stdout(cmd("jj", args))
+}
+
+/// The stdout of `expr` without its trailing newlines, like `duct::Expression::read`
+pub(super) fn read_stdout(expr: duct::Expression) -> Result<String> {
+    let output = wait(expr.stdout_capture().stderr_null().start()?)?;
+    trimmed_stdout(output.stdout)
+}
+
+fn trimmed_stdout(stdout: Vec<u8>) -> Result<String> {
+    let stdout = String::from_utf8(stdout)?;
+    Ok(stdout.trim_end_matches(['\n', '\r']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_stdout, run_with_stderr, with_cancellation};
+    use duct::cmd;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_cancellation_kills_the_running_command() {
+        let started_at = Instant::now();
+        let result = with_cancellation(
+            move || started_at.elapsed() > Duration::from_millis(50),
+            || run_with_stderr(cmd!("sleep", "5")),
+        );
+
+        assert_eq!(result.unwrap_err().to_string(), "cancelled");
+        assert!(started_at.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_commands_outside_cancellation_run_to_completion() {
+        with_cancellation(|| true, || {});
+
+        assert!(run_with_stderr(cmd!("true")).is_ok());
+        assert_eq!(
+            run_with_stderr(cmd!("sh", "-c", "echo nope >&2; exit 1"))
+                .unwrap_err()
+                .to_string(),
+            "nope"
+        );
+    }
+
+    #[test]
+    fn test_cancellation_kills_a_command_whose_output_is_read() {
+        let started_at = Instant::now();
+        let result = with_cancellation(
+            move || started_at.elapsed() > Duration::from_millis(50),
+            || read_stdout(cmd!("sleep", "5")),
+        );
+
+        assert_eq!(result.unwrap_err().to_string(), "cancelled");
+        assert!(started_at.elapsed() < Duration::from_secs(2));
+        assert_eq!(read_stdout(cmd!("echo", "done")).expect("read"), "done");
+    }
+
+    #[test]
+    fn test_cancellation_is_lifted_after_a_panic() {
+        let result = std::panic::catch_unwind(|| with_cancellation(|| true, || panic!("boom")));
+
+        assert!(result.is_err());
+        assert!(run_with_stderr(cmd!("true")).is_ok());
+    }
 }
@@ -0,0 +1,22 @@
+use crate::repo::configured_remotes;
+use eyre::Result;
+use jju_core::remote::Remotes;
+
+/// Config key naming the remote trunk comes from
+pub const REMOTE_KEY: &str = "jju.remote";
+/// Config key naming the remote bookmarks are pushed to
+pub const PUSH_REMOTE_KEY: &str = "jju.push-remote";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfigOps;
+
+impl ConfigOps {
+    /// The remotes to sync with, taking explicit choices over config
+    pub fn remotes(self, trunk: Option<&str>, push: Option<&str>) -> Result<Remotes> {
+        let configured = configured_remotes(None)?;
+        Ok(Remotes {
+            trunk: trunk.map_or(configured.trunk, ToOwned::to_owned),
+            push: push.map_or(configured.push, ToOwned::to_owned),
+        })
+    }
+}
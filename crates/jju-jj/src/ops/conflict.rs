@@ -1,5 +1,7 @@
+use super::command::read_stdout;
 use duct::cmd;
 use eyre::Result;
+use jju_core::stack_sync::ConflictedCommit;
 use std::process::Command;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -27,6 +29,32 @@ impl ConflictOps {
             .collect())
     }
 
+    /// Conflicted commits in `revset`, with their conflicted files
+    pub fn conflicted_commits(self, revset: &str) -> Result<Vec<ConflictedCommit>> {
+        let revset = format!("({revset}) & conflicts()");
+        let template = r#"change_id.short() ++ conflict_files.map(|x| "\t" ++ x).join("") ++ "\n""#;
+        let output = read_stdout(cmd!(
+            "jj",
+            "log",
+            "-r",
+            &revset,
+            "-T",
+            template,
+            "--no-graph"
+        ))?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                let change_id = fields.next().filter(|id| !id.is_empty())?;
+                Some(ConflictedCommit {
+                    change_id: change_id.to_string(),
+                    files: fields.map(ToOwned::to_owned).collect(),
+                })
+            })
+            .collect())
+    }
+
     pub fn resolve_file(self, file: &str) -> Result<()> {
         Command::new("jj").args(["resolve", file]).status()?;
         Ok(())
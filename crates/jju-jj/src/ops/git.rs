@@ -2,13 +2,18 @@ use super::bookmark::BookmarkOps;
 use super::command::run_with_stderr;
 use duct::cmd;
 use eyre::Result;
+use jju_core::remote::Remotes;
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct GitOps;
 
 impl GitOps {
-    pub fn fetch(self) -> Result<()> {
-        run_with_stderr(cmd!("jj", "git", "fetch"))
+    pub fn fetch(self, remotes: &Remotes) -> Result<()> {
+        let mut args = vec!["git", "fetch"];
+        for remote in remotes.to_fetch() {
+            args.extend(["--remote", remote]);
+        }
+        run_with_stderr(cmd("jj", args))
     }
 
     pub fn import(self) -> Result<()> {
@@ -19,13 +24,21 @@ impl GitOps {
         run_with_stderr(cmd!("jj", "git", "export"))
     }
 
-    pub fn push_all(self) -> Result<()> {
-        run_with_stderr(cmd!("jj", "git", "push", "--all"))
+    pub fn push_all(self, remote: &str) -> Result<()> {
+        run_with_stderr(cmd!("jj", "git", "push", "--all", "--remote", remote))
     }
 
-    pub fn push_bookmark(self, bookmark: &str) -> Result<()> {
-        let _ = BookmarkOps.track(bookmark);
-        run_with_stderr(cmd!("jj", "git", "push", "--bookmark", bookmark))
+    pub fn push_bookmark(self, bookmark: &str, remote: &str) -> Result<()> {
+        let _ = BookmarkOps.track(bookmark, remote);
+        run_with_stderr(cmd!(
+            "jj",
+            "git",
+            "push",
+            "--remote",
+            remote,
+            "--bookmark",
+            bookmark
+        ))
     }
 
     pub fn has_open_pr(self, bookmark: &str) -> bool {
@@ -38,8 +51,8 @@ impl GitOps {
             .unwrap_or(false)
     }
 
-    pub fn push_and_pr(self, bookmark: &str) -> Result<bool> {
-        self.push_bookmark(bookmark)?;
+    pub fn push_and_pr(self, bookmark: &str, remote: &str) -> Result<bool> {
+        self.push_bookmark(bookmark, remote)?;
         if self.has_open_pr(bookmark) {
             run_with_stderr(cmd!("gh", "pr", "view", bookmark, "--web"))?;
             Ok(true)
@@ -1,4 +1,5 @@
 use super::command::run_with_stderr;
+use crate::repo::OpHeadsWatch;
 use duct::cmd;
 use eyre::Result;
 
@@ -7,15 +8,13 @@ pub struct OperationOps;
 
 impl OperationOps {
     pub fn current_op_id(self) -> Result<String> {
-        let output = cmd!("jj", "op", "log", "--limit", "1", "-T", "id", "--no-graph")
-            .stdout_capture()
-            .stderr_null()
-            .read()?;
-        let op_id = output.trim().to_string();
-        if op_id.is_empty() {
-            eyre::bail!("jj op log returned empty operation ID");
-        }
-        Ok(op_id)
+        self.op_heads()?.current_op_id()
+    }
+
+    /// The current directory's operation heads, read through jj-lib so
+    /// checking for new operations doesn't run `jj`
+    pub fn op_heads(self) -> Result<OpHeadsWatch> {
+        OpHeadsWatch::load(None)
     }
 
     pub fn restore(self, op_id: &str) -> Result<()> {
@@ -1,14 +1,29 @@
+mod absorb;
 mod display;
+mod landed;
+mod move_changes;
+mod operations;
 mod prefixes;
 mod queries;
 mod revset;
+mod rewrite;
+mod settings;
+mod snapshot;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
+
+pub use operations::OpHeadsWatch;
 
 use eyre::{Context, Result};
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
 use jj_lib::config::{ConfigLayer, ConfigSource, StackedConfig};
+use jj_lib::merge::MergedTreeValue;
 use jj_lib::repo::{ReadonlyRepo, StoreFactories};
 use jj_lib::settings::UserSettings;
 use jj_lib::workspace::{Workspace, default_working_copy_factories};
-use std::path::Path;
+use jju_core::remote::{DEFAULT_REMOTE, Remotes};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -20,7 +35,76 @@ pub struct CommitDetails {
     pub timestamp: String,
 }
 
-pub fn create_user_settings() -> Result<UserSettings> {
+/// A single entry of the repo's operation log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperationLogEntry {
+    pub op_id: String,
+    pub description: String,
+    pub timestamp: String,
+    pub is_current: bool,
+}
+
+/// Text and executable bit of a file in a tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileContents {
+    pub text: Vec<u8>,
+    pub executable: bool,
+    /// The tree value of a symlink, submodule or conflict, written back as it is.
+    /// `text` holds a symlink's target and is empty for the others
+    pub verbatim: Option<MergedTreeValue>,
+}
+
+impl FileContents {
+    pub fn new(text: Vec<u8>, executable: bool) -> Self {
+        Self {
+            text,
+            executable,
+            verbatim: None,
+        }
+    }
+
+    /// Whether this isn't a regular file, so its lines can't be edited
+    pub fn is_verbatim(&self) -> bool {
+        self.verbatim.is_some()
+    }
+}
+
+/// One new commit of a split, with the files it changes relative to the split commit's parent
+///
+/// A `None` entry removes the file from the tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitLayer {
+    pub message: String,
+    pub files: Vec<(String, Option<FileContents>)>,
+}
+
+/// Changes to squash into one ancestor of an absorbed commit
+///
+/// Like a split layer, the files replace entries of the absorbed commit's parent tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AbsorbLayer {
+    pub destination: CommitId,
+    pub files: Vec<(String, Option<FileContents>)>,
+}
+
+/// Commits rewritten by an absorb
+#[derive(Clone, Debug)]
+pub struct AbsorbOutcome {
+    pub rewritten: Vec<Commit>,
+    /// Whether the absorbed commit was left empty and abandoned
+    pub source_abandoned: bool,
+    pub num_rebased: usize,
+}
+
+/// The two commits rewritten by moving changes between them
+#[derive(Clone, Debug)]
+pub struct MoveOutcome {
+    pub source: Commit,
+    pub destination: Commit,
+}
+
+/// Fixed config that ignores the user's, for repos made from scratch like in tests
+pub fn scratch_config() -> Result<StackedConfig> {
     let config_text = r#"
         user.name = "jj-lib user"
         user.email = "jj-lib@localhost"
@@ -29,38 +113,152 @@ pub fn create_user_settings() -> Result<UserSettings> {
     "#;
     let mut config = StackedConfig::with_defaults();
     config.add_layer(ConfigLayer::parse(ConfigSource::User, config_text)?);
-    UserSettings::from_config(config).wrap_err("failed to create user settings")
+    Ok(config)
+}
+
+/// Fixed settings that ignore the user's config, for repos made from scratch like in tests
+pub fn create_user_settings() -> Result<UserSettings> {
+    UserSettings::from_config(scratch_config()?).wrap_err("failed to create user settings")
+}
+
+/// The `jju.remote` and `jju.push-remote` settings of the workspace containing
+/// `path`, or the current directory
+pub fn configured_remotes(path: Option<&Path>) -> Result<Remotes> {
+    let (_, settings) = load_workspace(path, None)?;
+    Ok(remotes_from_settings(&settings))
+}
+
+/// The `jju.remote` and `jju.push-remote` settings
+///
+/// The push remote falls back to jj's own `git.push` setting before `origin`,
+/// so a fork only needs `jju.remote = "upstream"`
+fn remotes_from_settings(settings: &UserSettings) -> Remotes {
+    let trunk = settings
+        .get_string(crate::ops::REMOTE_KEY)
+        .unwrap_or_else(|_| DEFAULT_REMOTE.to_string());
+    let push = settings
+        .get_string(crate::ops::PUSH_REMOTE_KEY)
+        .or_else(|_| settings.get_string("git.push"))
+        .unwrap_or_else(|_| DEFAULT_REMOTE.to_string());
+    Remotes { trunk, push }
 }
 
 pub struct JjRepo {
     workspace: Workspace,
     repo: Arc<ReadonlyRepo>,
+    settings: UserSettings,
+    remotes: Remotes,
+}
+
+/// The nearest directory at or above `start` that holds a `.jj` workspace
+pub fn find_workspace_root(start: &Path) -> Result<PathBuf> {
+    let start = start
+        .canonicalize()
+        .wrap_err_with(|| format!("failed to resolve {}", start.display()))?;
+    start
+        .ancestors()
+        .find(|dir| dir.join(".jj").is_dir())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| {
+            eyre::eyre!(
+                "no jj repo found in {} or any parent directory",
+                start.display()
+            )
+        })
+}
+
+/// The workspace containing `path`, or the current directory, and its settings
+///
+/// `config` replaces jj's defaults, the user config and the environment when given
+fn load_workspace(
+    path: Option<&Path>,
+    config: Option<StackedConfig>,
+) -> Result<(Workspace, UserSettings)> {
+    let start = match path {
+        Some(path) => path.to_path_buf(),
+        None => std::env::current_dir().wrap_err("failed to get current directory")?,
+    };
+    let workspace_path = find_workspace_root(&start)?;
+
+    let settings = match config {
+        Some(config) => settings::load_pinned_settings(&workspace_path, config)?,
+        None => settings::load_user_settings(&workspace_path)?,
+    };
+    let store_factories = StoreFactories::default();
+    let working_copy_factories = default_working_copy_factories();
+
+    let workspace = Workspace::load(
+        &settings,
+        &workspace_path,
+        &store_factories,
+        &working_copy_factories,
+    )
+    .wrap_err("failed to load workspace")?;
+    Ok((workspace, settings))
 }
 
 impl JjRepo {
+    /// Load the workspace containing `path`, or the current directory
     pub fn load(path: Option<&Path>) -> Result<Self> {
-        let workspace_path = match path {
-            Some(path) => path.to_path_buf(),
-            None => std::env::current_dir().wrap_err("failed to get current directory")?,
-        };
-
-        let settings = create_user_settings()?;
-        let store_factories = StoreFactories::default();
-        let working_copy_factories = default_working_copy_factories();
-
-        let workspace = Workspace::load(
-            &settings,
-            &workspace_path,
-            &store_factories,
-            &working_copy_factories,
-        )
-        .wrap_err("failed to load workspace")?;
+        Self::load_with(path, None)
+    }
+
+    /// Load like `load`, with `config` in place of the user's config and
+    /// environment, so only the repo and workspace configs are read
+    pub fn load_with_config(path: Option<&Path>, config: StackedConfig) -> Result<Self> {
+        Self::load_with(path, Some(config))
+    }
 
+    fn load_with(path: Option<&Path>, config: Option<StackedConfig>) -> Result<Self> {
+        let (workspace, settings) = load_workspace(path, config)?;
         let repo = workspace
             .repo_loader()
             .load_at_head()
             .wrap_err("failed to load repo at head")?;
 
-        Ok(Self { workspace, repo })
+        Ok(Self {
+            workspace,
+            repo,
+            remotes: remotes_from_settings(&settings),
+            settings,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JjRepo, find_workspace_root, remotes_from_settings, scratch_config};
+    use crate::repo::test_support::TempRepo;
+    use jju_core::remote::Remotes;
+
+    #[test]
+    fn test_load_from_subdirectory_finds_workspace_root() {
+        let temp = TempRepo::init("repo-root");
+        let nested = temp.root().join("src/nested");
+        std::fs::create_dir_all(&nested).expect("create subdirectory");
+
+        let root = temp.root().canonicalize().expect("canonical root");
+        assert_eq!(find_workspace_root(&nested).expect("find root"), root);
+        let jj_repo = JjRepo::load_with_config(Some(&nested), scratch_config().expect("config"))
+            .expect("load from subdirectory");
+        assert_eq!(jj_repo.workspace.workspace_root(), root);
+    }
+
+    #[test]
+    fn test_configured_remotes_push_falls_back_to_git_push() {
+        let temp = TempRepo::init("remotes");
+        std::fs::write(
+            temp.root().join(".jj/repo/config.toml"),
+            "jju.remote = \"upstream\"\ngit.push = \"fork\"\n",
+        )
+        .expect("write repo config");
+
+        assert_eq!(
+            remotes_from_settings(&temp.load().settings),
+            Remotes {
+                trunk: "upstream".to_string(),
+                push: "fork".to_string(),
+            }
+        );
     }
 }
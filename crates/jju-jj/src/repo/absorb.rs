@@ -0,0 +1,159 @@
+use super::rewrite::{repo_path, tree_builder};
+use super::{AbsorbLayer, AbsorbOutcome, JjRepo};
+use eyre::{Context, Result, bail};
+use jj_lib::absorb::{AbsorbSource, absorb_hunks};
+use jj_lib::annotate::FileAnnotator;
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::repo::Repo;
+use std::collections::HashMap;
+
+impl JjRepo {
+    /// The commit in `domain` that last changed each line of `path` in `commit`'s parent
+    ///
+    /// Lines that come from outside the domain are `None`, and a file missing from
+    /// the parent or that isn't a regular file there has no lines at all
+    pub fn line_owners(
+        &self,
+        commit: &Commit,
+        path: &str,
+        domain: &str,
+    ) -> Result<Vec<Option<CommitId>>> {
+        let Some(parent) = self
+            .parent_file_contents(commit, path)?
+            .filter(|parent| !parent.is_verbatim())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let domain = self.resolve_revset(domain)?;
+        let mut annotator =
+            FileAnnotator::with_file_content(commit.id(), &repo_path(path)?, parent.text);
+        annotator
+            .compute(self.repo.as_ref(), &domain)
+            .wrap_err_with(|| format!("failed to annotate {path}"))?;
+
+        Ok(annotator
+            .to_annotation()
+            .lines()
+            .map(|(owner, _)| owner.ok().filter(|id| *id != commit.id()).cloned())
+            .collect())
+    }
+
+    /// Squash parts of `source` into its ancestors in a single operation
+    ///
+    /// Each layer's changes are merged into its destination, and `source` is rebased
+    /// onto the rewritten ancestors so those changes drop out of it. `source` is
+    /// abandoned when nothing is left in it
+    pub fn absorb_commit(
+        &mut self,
+        source: &Commit,
+        layers: &[AbsorbLayer],
+    ) -> Result<AbsorbOutcome> {
+        if layers.is_empty() {
+            bail!("nothing to absorb");
+        }
+
+        // trees merged by the absorb must all come from this repo's store
+        let source = self.repo.store().get_commit(source.id())?;
+        let absorb_source = AbsorbSource::from_commit(self.repo.as_ref(), source.clone())
+            .wrap_err("failed to load absorb source")?;
+        let parent_tree = source
+            .parent_tree(self.repo.as_ref())
+            .wrap_err("failed to load parent tree")?;
+        let workspace_name = self.workspace.workspace_name().to_owned();
+        let old_wc_commit_id = self.repo.view().get_wc_commit_id(&workspace_name).cloned();
+        let old_wc_tree = match &old_wc_commit_id {
+            Some(id) => Some(self.repo.store().get_commit(id)?.tree()),
+            None => None,
+        };
+
+        let mut selected_trees = HashMap::new();
+        for layer in layers {
+            selected_trees.insert(
+                layer.destination.clone(),
+                tree_builder(&parent_tree, &layer.files)?,
+            );
+        }
+
+        let mut tx = self.repo.start_transaction();
+        let stats = absorb_hunks(tx.repo_mut(), &absorb_source, selected_trees)
+            .wrap_err("failed to absorb changes")?;
+        tx.repo_mut()
+            .rebase_descendants()
+            .wrap_err("failed to rebase descendants")?;
+
+        let change_id = source.change_id().reverse_hex();
+        let short_id = &change_id[..change_id.len().min(12)];
+        self.repo = tx
+            .commit(format!(
+                "absorb changes from {short_id} into {} commits",
+                stats.rewritten_destinations.len()
+            ))
+            .wrap_err("failed to commit absorb transaction")?;
+
+        self.update_working_copy(&workspace_name, old_wc_commit_id, old_wc_tree.as_ref())?;
+        Ok(AbsorbOutcome {
+            source_abandoned: stats.rewritten_source.is_none(),
+            rewritten: stats.rewritten_destinations,
+            num_rebased: stats.num_rebased,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AbsorbLayer;
+    use crate::repo::test_support::{TempRepo, file, write_commit};
+    use jj_lib::repo::Repo;
+
+    #[test]
+    fn test_absorb_commit_moves_changes_into_line_owner() {
+        let temp = TempRepo::init("absorb");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, first) = write_commit(
+            &temp.repo,
+            &root_commit,
+            "add a",
+            &[("a.txt", "one\ntwo\nthree\n")],
+        );
+        let (repo, second) = write_commit(&repo, &first, "add b", &[("b.txt", "b\n")]);
+        let (_, source) = write_commit(
+            &repo,
+            &second,
+            "edit both",
+            &[("a.txt", "one\nTWO\nthree\n"), ("b.txt", "b\nmore\n")],
+        );
+
+        let mut jj_repo = temp.load();
+        let owners = jj_repo
+            .line_owners(&source, "a.txt", "mutable()")
+            .expect("annotate");
+        assert_eq!(owners, vec![Some(first.id().clone()); 3]);
+
+        let outcome = jj_repo
+            .absorb_commit(
+                &source,
+                &[AbsorbLayer {
+                    destination: first.id().clone(),
+                    files: vec![("a.txt".to_string(), file("one\nTWO\nthree\n"))],
+                }],
+            )
+            .expect("absorb");
+        assert_eq!(outcome.rewritten.len(), 1);
+        assert!(!outcome.source_abandoned);
+
+        let rewritten_first = jj_repo
+            .eval_revset_single(&first.change_id().reverse_hex())
+            .expect("rewritten destination");
+        let rewritten_source = jj_repo
+            .eval_revset_single(&source.change_id().reverse_hex())
+            .expect("rewritten source");
+        assert_ne!(rewritten_first.id(), first.id());
+        assert_eq!(rewritten_source.tree_ids(), source.tree_ids());
+        let remaining = jj_repo
+            .line_owners(&rewritten_source, "a.txt", "mutable()")
+            .expect("annotate rewritten");
+        assert_eq!(remaining, vec![Some(rewritten_first.id().clone()); 3]);
+    }
+}
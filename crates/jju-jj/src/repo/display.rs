@@ -1,5 +1,6 @@
 use super::{CommitDetails, JjRepo};
 use eyre::Result;
+use jj_lib::backend::Timestamp;
 use jj_lib::commit::Commit;
 use jj_lib::id_prefix::IdPrefixIndex;
 
@@ -22,30 +23,7 @@ impl JjRepo {
     }
 
     pub fn author_timestamp_relative(commit: &Commit) -> String {
-        let timestamp = commit.author().timestamp;
-        let millis = timestamp.timestamp.0;
-        let secs = millis / 1000;
-        let Some(datetime) = chrono::DateTime::from_timestamp(secs, 0) else {
-            return "unknown".to_string();
-        };
-
-        let now = chrono::Utc::now();
-        let diff = now.signed_duration_since(datetime);
-        let absolute = datetime.format("%Y-%m-%d %H:%M");
-        let relative = if diff.num_days() > 365 {
-            format!("{} years ago", diff.num_days() / 365)
-        } else if diff.num_days() > 30 {
-            format!("{} months ago", diff.num_days() / 30)
-        } else if diff.num_days() > 0 {
-            format!("{} days ago", diff.num_days())
-        } else if diff.num_hours() > 0 {
-            format!("{} hours ago", diff.num_hours())
-        } else if diff.num_minutes() > 0 {
-            format!("{} minutes ago", diff.num_minutes())
-        } else {
-            "just now".to_string()
-        };
-        format!("{relative} ({absolute})")
+        format_timestamp_relative(&commit.author().timestamp)
     }
 
     pub fn commit_details_with_index(
@@ -64,3 +42,29 @@ impl JjRepo {
         })
     }
 }
+
+pub(super) fn format_timestamp_relative(timestamp: &Timestamp) -> String {
+    let millis = timestamp.timestamp.0;
+    let secs = millis / 1000;
+    let Some(datetime) = chrono::DateTime::from_timestamp(secs, 0) else {
+        return "unknown".to_string();
+    };
+
+    let now = chrono::Utc::now();
+    let diff = now.signed_duration_since(datetime);
+    let absolute = datetime.format("%Y-%m-%d %H:%M");
+    let relative = if diff.num_days() > 365 {
+        format!("{} years ago", diff.num_days() / 365)
+    } else if diff.num_days() > 30 {
+        format!("{} months ago", diff.num_days() / 30)
+    } else if diff.num_days() > 0 {
+        format!("{} days ago", diff.num_days())
+    } else if diff.num_hours() > 0 {
+        format!("{} hours ago", diff.num_hours())
+    } else if diff.num_minutes() > 0 {
+        format!("{} minutes ago", diff.num_minutes())
+    } else {
+        "just now".to_string()
+    };
+    format!("{relative} ({absolute})")
+}
@@ -0,0 +1,121 @@
+use super::JjRepo;
+use eyre::{Context, Result};
+use jj_lib::commit::Commit;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTree;
+use pollster::FutureExt as _;
+
+/// How many trunk commits a stack is compared against when looking for where it landed
+const LANDED_SEARCH_DEPTH: usize = 50;
+
+impl JjRepo {
+    /// The trunk commit that already contains every change of the stack from `root`
+    ///
+    /// A squash merge lands a stack as one new commit and a rebase merge as several,
+    /// both with new commit ids, so the latest commits of `trunk` that aren't
+    /// ancestors of the stack are compared by tree: the stack landed in the first
+    /// one that its combined change merges into without changing anything
+    pub fn landed_commit(&self, root: &str, trunk: &str) -> Result<Option<Commit>> {
+        let root_commit = self.eval_revset_single(root)?;
+        let base_tree = root_commit
+            .parent_tree(self.repo.as_ref())
+            .wrap_err("failed to load the stack's parent tree")?;
+        let head_trees = self
+            .eval_revset(&format!("heads(({root})::)"))?
+            .iter()
+            .map(Commit::tree)
+            .filter(|tree| tree.tree_ids() != base_tree.tree_ids())
+            .collect::<Vec<_>>();
+        // an empty stack trivially merges into anything
+        if head_trees.is_empty() {
+            return Ok(None);
+        }
+
+        let candidates = self.eval_revset(&format!(
+            "latest(::({trunk}) ~ ::({root}), {LANDED_SEARCH_DEPTH})"
+        ))?;
+        for candidate in candidates {
+            let mut landed = true;
+            for head_tree in &head_trees {
+                if !contains_change(&candidate, &base_tree, head_tree)? {
+                    landed = false;
+                    break;
+                }
+            }
+            if landed {
+                return Ok(Some(candidate));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Whether merging the change from `base` to `head` into `candidate` leaves it as is
+fn contains_change(candidate: &Commit, base: &MergedTree, head: &MergedTree) -> Result<bool> {
+    let merged = MergedTree::merge(Merge::from_vec(vec![
+        (candidate.tree(), "trunk".to_string()),
+        (base.clone(), "stack base".to_string()),
+        (head.clone(), "stack".to_string()),
+    ]))
+    .block_on()
+    .wrap_err("failed to merge the stack into trunk")?;
+    Ok(merged.tree_ids() == candidate.tree().tree_ids())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repo::test_support::{TempRepo, write_commit};
+    use jj_lib::object_id::ObjectId;
+    use jj_lib::repo::Repo;
+
+    #[test]
+    fn test_landed_commit_finds_a_squash_merge_below_later_trunk_commits() {
+        let temp = TempRepo::init("landed-squash");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, base) = write_commit(&temp.repo, &root_commit, "", &[("a.txt", "a\n")]);
+        let (repo, first) = write_commit(&repo, &base, "", &[("b.txt", "b\n")]);
+        let (repo, _) = write_commit(&repo, &first, "", &[("a.txt", "a\nx\n")]);
+        let (repo, other) = write_commit(&repo, &base, "", &[("c.txt", "c\n")]);
+        let (repo, squashed) =
+            write_commit(&repo, &other, "", &[("a.txt", "a\nx\n"), ("b.txt", "b\n")]);
+        let (repo, trunk) = write_commit(&repo, &squashed, "", &[("a.txt", "a\nx\ny\n")]);
+        let (_, unlanded) = write_commit(&repo, &base, "", &[("d.txt", "d\n")]);
+
+        let jj_repo = temp.load();
+        let trunk_hex = trunk.id().hex();
+        let landed = jj_repo
+            .landed_commit(&first.id().hex(), &trunk_hex)
+            .expect("landed");
+        assert_eq!(
+            landed.map(|commit| commit.id().clone()),
+            Some(squashed.id().clone())
+        );
+        assert!(
+            jj_repo
+                .landed_commit(&unlanded.id().hex(), &trunk_hex)
+                .expect("landed")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_landed_commit_finds_a_rebase_merge() {
+        let temp = TempRepo::init("landed-rebase");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, base) = write_commit(&temp.repo, &root_commit, "", &[("a.txt", "a\n")]);
+        let (repo, first) = write_commit(&repo, &base, "", &[("b.txt", "b\n")]);
+        let (repo, _) = write_commit(&repo, &first, "", &[("a.txt", "a\nx\n")]);
+        let (repo, other) = write_commit(&repo, &base, "", &[("c.txt", "c\n")]);
+        let (repo, rebased_first) = write_commit(&repo, &other, "", &[("b.txt", "b\n")]);
+        let (_, rebased_second) = write_commit(&repo, &rebased_first, "", &[("a.txt", "a\nx\n")]);
+
+        let jj_repo = temp.load();
+        let landed = jj_repo
+            .landed_commit(&first.id().hex(), &rebased_second.id().hex())
+            .expect("landed");
+        assert_eq!(
+            landed.map(|commit| commit.id().clone()),
+            Some(rebased_second.id().clone())
+        );
+    }
+}
@@ -0,0 +1,182 @@
+use super::rewrite::tree_builder;
+use super::{FileContents, JjRepo, MoveOutcome};
+use eyre::{Context, Result, bail};
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::{MutableRepo, Repo};
+use jj_lib::rewrite::{RebaseOptions, RebasedCommit};
+use pollster::FutureExt as _;
+use std::collections::HashMap;
+
+impl JjRepo {
+    /// Move part of `source`'s changes into `destination` in a single operation
+    ///
+    /// `files` describe the moved changes as in a split layer, replacing entries of
+    /// `source`'s parent tree. The destination may be an ancestor, a descendant or
+    /// an unrelated revision; the changes are merged into it, so they can leave
+    /// conflicts behind when they don't apply cleanly there
+    pub fn move_changes(
+        &mut self,
+        source: &Commit,
+        destination: &Commit,
+        files: &[(String, Option<FileContents>)],
+    ) -> Result<MoveOutcome> {
+        if source.id() == destination.id() {
+            bail!("source and destination are the same revision");
+        }
+
+        let source = self.repo.store().get_commit(source.id())?;
+        let destination = self.repo.store().get_commit(destination.id())?;
+        let parent_tree = source
+            .parent_tree(self.repo.as_ref())
+            .wrap_err("failed to load parent tree")?;
+        let moved_tree = tree_builder(&parent_tree, files)?
+            .write_tree()
+            .wrap_err("failed to write moved changes")?;
+        let workspace_name = self.workspace.workspace_name().to_owned();
+        let old_wc_commit_id = self.repo.view().get_wc_commit_id(&workspace_name).cloned();
+        let old_wc_tree = match &old_wc_commit_id {
+            Some(id) => Some(self.repo.store().get_commit(id)?.tree()),
+            None => None,
+        };
+
+        let mut tx = self.repo.start_transaction();
+        let source_tree = MergedTree::merge(Merge::from_vec(vec![
+            (source.tree(), source.conflict_label()),
+            (moved_tree.clone(), "moved changes".to_string()),
+            (parent_tree.clone(), "parents of source".to_string()),
+        ]))
+        .block_on()
+        .wrap_err("failed to remove changes from source")?;
+        let new_source = tx
+            .repo_mut()
+            .rewrite_commit(&source)
+            .set_tree(source_tree)
+            .write()
+            .wrap_err("failed to rewrite source")?;
+        let mut rebased = rebase_tracking(tx.repo_mut())?;
+        let destination = rebased.remove(destination.id()).unwrap_or(destination);
+
+        let destination_tree = MergedTree::merge(Merge::from_vec(vec![
+            (destination.tree(), destination.conflict_label()),
+            (parent_tree, "parents of source".to_string()),
+            (
+                moved_tree,
+                format!("moved changes (from {})", source.conflict_label()),
+            ),
+        ]))
+        .block_on()
+        .wrap_err("failed to apply changes to destination")?;
+        let builder = tx
+            .repo_mut()
+            .rewrite_commit(&destination)
+            .set_tree(destination_tree);
+        let mut predecessors = builder.predecessors().to_vec();
+        predecessors.push(source.id().clone());
+        let new_destination = builder
+            .set_predecessors(predecessors)
+            .write()
+            .wrap_err("failed to rewrite destination")?;
+        // the source moves again when it descends from the destination
+        let new_source = rebase_tracking(tx.repo_mut())?
+            .remove(new_source.id())
+            .unwrap_or(new_source);
+
+        let change_id = source.change_id().reverse_hex();
+        let destination_change_id = destination.change_id().reverse_hex();
+        self.repo = tx
+            .commit(format!(
+                "move changes from {} into {}",
+                &change_id[..change_id.len().min(12)],
+                &destination_change_id[..destination_change_id.len().min(12)]
+            ))
+            .wrap_err("failed to commit move transaction")?;
+
+        self.update_working_copy(&workspace_name, old_wc_commit_id, old_wc_tree.as_ref())?;
+        Ok(MoveOutcome {
+            source: new_source,
+            destination: new_destination,
+        })
+    }
+}
+
+/// Rebase descendants of rewritten commits, returning the new version of each one
+fn rebase_tracking(repo: &mut MutableRepo) -> Result<HashMap<CommitId, Commit>> {
+    let mut rebased = HashMap::new();
+    repo.rebase_descendants_with_options(&RebaseOptions::default(), |old, new| {
+        if let RebasedCommit::Rewritten(commit) = new {
+            rebased.insert(old.id().clone(), commit);
+        }
+    })
+    .wrap_err("failed to rebase descendants")?;
+    Ok(rebased)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::rewrite::{read_file, repo_path};
+    use crate::repo::test_support::{TempRepo, file, write_commit};
+    use jj_lib::commit::Commit;
+    use jj_lib::repo::Repo;
+
+    fn file_text(commit: &Commit, path: &str) -> Option<String> {
+        read_file(&commit.tree(), &repo_path(path).expect("path"))
+            .expect("read file")
+            .map(|contents| String::from_utf8(contents.text).expect("utf8"))
+    }
+
+    #[test]
+    fn test_move_changes_into_descendant_keeps_them_out_of_source() {
+        let temp = TempRepo::init("move-descendant");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, base) = write_commit(&temp.repo, &root_commit, "", &[("a.txt", "a\n")]);
+        let (repo, source) =
+            write_commit(&repo, &base, "", &[("a.txt", "a\nb\n"), ("c.txt", "c\n")]);
+        let (_, destination) = write_commit(&repo, &source, "", &[("d.txt", "d\n")]);
+
+        let mut jj_repo = temp.load();
+        let outcome = jj_repo
+            .move_changes(&source, &destination, &[("c.txt".to_string(), file("c\n"))])
+            .expect("move");
+
+        assert_eq!(file_text(&outcome.source, "c.txt"), None);
+        assert_eq!(
+            file_text(&outcome.source, "a.txt").as_deref(),
+            Some("a\nb\n")
+        );
+        assert_eq!(
+            file_text(&outcome.destination, "c.txt").as_deref(),
+            Some("c\n")
+        );
+        assert!(!outcome.destination.has_conflict());
+    }
+
+    #[test]
+    fn test_move_changes_into_ancestor_rebases_source_onto_it() {
+        let temp = TempRepo::init("move-ancestor");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, destination) = write_commit(&temp.repo, &root_commit, "", &[("a.txt", "a\n")]);
+        let (_, source) = write_commit(&repo, &destination, "", &[("a.txt", "a\nb\n")]);
+
+        let mut jj_repo = temp.load();
+        let outcome = jj_repo
+            .move_changes(
+                &source,
+                &destination,
+                &[("a.txt".to_string(), file("a\nb\n"))],
+            )
+            .expect("move");
+
+        assert_eq!(
+            file_text(&outcome.destination, "a.txt").as_deref(),
+            Some("a\nb\n")
+        );
+        assert_eq!(
+            outcome.source.parent_ids(),
+            [outcome.destination.id().clone()]
+        );
+        assert_eq!(outcome.source.tree_ids(), outcome.destination.tree_ids());
+    }
+}
@@ -0,0 +1,109 @@
+use super::display::format_timestamp_relative;
+use super::{JjRepo, OperationLogEntry};
+use eyre::{Context, Result};
+use jj_lib::object_id::ObjectId;
+use jj_lib::op_heads_store::OpHeadsStore;
+use jj_lib::op_walk;
+use pollster::FutureExt as _;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Reads the repo's current operation heads without reloading the repo, so a
+/// background thread can notice operations made by other processes
+#[derive(Clone, Debug)]
+pub struct OpHeadsWatch {
+    store: Arc<dyn OpHeadsStore>,
+}
+
+impl OpHeadsWatch {
+    /// Open the operation heads of the workspace containing `path`, or the
+    /// current directory, without loading the repo
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let (workspace, _) = super::load_workspace(path, None)?;
+        Ok(Self {
+            store: workspace.repo_loader().op_heads_store().clone(),
+        })
+    }
+
+    /// The id of the single current operation, failing while concurrent
+    /// operations are still waiting for jj to merge them
+    pub fn current_op_id(&self) -> Result<String> {
+        match self.op_heads()?.as_slice() {
+            [op_id] => Ok(op_id.clone()),
+            [] => eyre::bail!("the repo has no operation heads"),
+            heads => eyre::bail!("the repo has {} concurrent operations", heads.len()),
+        }
+    }
+
+    /// The current operation heads as sorted hex ids
+    pub fn op_heads(&self) -> Result<Vec<String>> {
+        let mut heads: Vec<String> = self
+            .store
+            .get_op_heads()
+            .block_on()
+            .wrap_err("failed to read operation heads")?
+            .iter()
+            .map(ObjectId::hex)
+            .collect();
+        heads.sort();
+        Ok(heads)
+    }
+}
+
+impl JjRepo {
+    pub fn op_heads_watch(&self) -> OpHeadsWatch {
+        OpHeadsWatch {
+            store: self.repo.op_heads_store().clone(),
+        }
+    }
+
+    /// Walk the operation log from the loaded head, newest first
+    pub fn operation_log(&self, limit: usize) -> Result<Vec<OperationLogEntry>> {
+        let head = self.repo.operation();
+        op_walk::walk_ancestors(std::slice::from_ref(head))
+            .take(limit)
+            .map(|op| {
+                let op = op.wrap_err("failed to read operation")?;
+                let metadata = op.metadata();
+                Ok(OperationLogEntry {
+                    op_id: op.id().hex(),
+                    description: metadata.description.clone(),
+                    timestamp: format_timestamp_relative(&metadata.time.end),
+                    is_current: op.id() == head.id(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repo::OpHeadsWatch;
+    use crate::repo::test_support::TempRepo;
+    use jj_lib::object_id::ObjectId;
+
+    #[test]
+    fn test_op_heads_watch_sees_new_operations() {
+        let temp = TempRepo::init("op-heads");
+
+        let watch = temp.load().op_heads_watch();
+        assert_eq!(
+            watch.op_heads().expect("heads"),
+            vec![temp.repo.op_id().hex()]
+        );
+
+        let repo = temp
+            .repo
+            .start_transaction()
+            .commit("outside jju")
+            .expect("commit operation");
+        assert_eq!(watch.op_heads().expect("heads"), vec![repo.op_id().hex()]);
+        assert_eq!(
+            OpHeadsWatch::load(Some(temp.root()))
+                .expect("open op heads")
+                .current_op_id()
+                .expect("current op"),
+            repo.op_id().hex()
+        );
+    }
+}
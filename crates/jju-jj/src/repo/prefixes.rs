@@ -1,8 +1,8 @@
 use super::JjRepo;
-use eyre::{Context, Result};
+use eyre::{Context, Result, bail};
 use jj_lib::commit::Commit;
 use jj_lib::id_prefix::{IdPrefixContext, IdPrefixIndex};
-use jj_lib::object_id::ObjectId;
+use jj_lib::object_id::{HexPrefix, ObjectId, PrefixResolution};
 use jj_lib::revset::{self, RevsetDiagnostics};
 
 impl JjRepo {
@@ -38,11 +38,40 @@ impl JjRepo {
         ))
     }
 
+    /// Commit ids (hex) of the visible commits for the change `prefix` resolves to
+    pub fn resolve_change_prefix(
+        &self,
+        index: &IdPrefixIndex,
+        prefix: &str,
+    ) -> Result<Vec<String>> {
+        let Some(hex_prefix) = HexPrefix::try_from_reverse_hex(prefix) else {
+            bail!("`{prefix}` is not a change id");
+        };
+
+        let resolution = index
+            .resolve_change_prefix(self.repo.as_ref(), &hex_prefix)
+            .wrap_err("failed to resolve change id prefix")?;
+        match resolution {
+            PrefixResolution::SingleMatch(targets) => {
+                let commit_ids = targets
+                    .visible_with_offsets()
+                    .map(|(_, commit_id)| commit_id.hex())
+                    .collect::<Vec<_>>();
+                if commit_ids.is_empty() {
+                    bail!("change `{prefix}` has no visible commits");
+                }
+                Ok(commit_ids)
+            }
+            PrefixResolution::AmbiguousMatch => bail!("change id prefix `{prefix}` is ambiguous"),
+            PrefixResolution::NoMatch => bail!("no change matches `{prefix}`"),
+        }
+    }
+
     pub fn with_short_prefix_index<T>(
         &self,
         f: impl FnOnce(&IdPrefixIndex) -> Result<T>,
     ) -> Result<T> {
-        self.with_revset_context("", |extensions, context| {
+        self.with_revset_context(|extensions, context| {
             let mut diagnostics = RevsetDiagnostics::new();
             let short_prefixes_revset =
                 "present(@) | ancestors(immutable_heads()..) | present(trunk())";
@@ -64,3 +93,28 @@ impl JjRepo {
         full_id[..display_len].to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::repo::test_support::{TempRepo, working_copy_commit};
+    use jj_lib::object_id::ObjectId;
+
+    #[test]
+    fn test_resolve_change_prefix_finds_the_working_copy() {
+        let temp = TempRepo::init("resolve-prefix");
+        let working_copy = working_copy_commit(&temp.repo);
+        let working_copy_id = working_copy.id().clone();
+        let change_id = working_copy.change_id().reverse_hex();
+
+        let jj_repo = temp.load();
+        let resolved = jj_repo
+            .with_short_prefix_index(|index| jj_repo.resolve_change_prefix(index, &change_id[..8]))
+            .expect("resolve");
+        assert_eq!(resolved, vec![working_copy_id.hex()]);
+
+        let error = jj_repo
+            .with_short_prefix_index(|index| jj_repo.resolve_change_prefix(index, "xyz!"))
+            .unwrap_err();
+        assert!(error.to_string().contains("not a change id"));
+    }
+}
@@ -4,13 +4,14 @@ use eyre::{Context, Result, bail};
 use itertools::Itertools;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
+use jj_lib::git::REMOTE_NAME_FOR_LOCAL_GIT_REPO;
 use jj_lib::object_id::ObjectId;
 use jj_lib::ref_name::{RemoteName, RemoteRefSymbol};
 use jj_lib::repo::Repo;
 
 impl JjRepo {
     pub fn bookmarks_by_commit_id(&self) -> HashMap<String, Vec<(String, bool)>> {
-        let origin = RemoteName::new("origin");
+        let push_remote = RemoteName::new(&self.remotes.push);
         let mut bookmarks = HashMap::default();
 
         for (name, target) in self.repo.view().local_bookmarks() {
@@ -20,7 +21,7 @@ impl JjRepo {
 
             let symbol = RemoteRefSymbol {
                 name,
-                remote: origin,
+                remote: push_remote,
             };
             let is_diverged = self
                 .repo
@@ -49,6 +50,34 @@ impl JjRepo {
             .collect()
     }
 
+    /// Bookmarks deleted locally while a tracked remote bookmark remains, the
+    /// ones `jj bookmark list` marks as deleted
+    pub fn deleted_bookmarks(&self) -> Vec<String> {
+        self.repo
+            .view()
+            .bookmarks()
+            .filter(|(_, target)| {
+                target.local_target.is_absent()
+                    && target.remote_refs.iter().any(|(remote, remote_ref)| {
+                        *remote != REMOTE_NAME_FOR_LOCAL_GIT_REPO && remote_ref.is_tracked()
+                    })
+            })
+            .map(|(name, _)| name.as_str().to_string())
+            .collect()
+    }
+
+    /// Local bookmarks pointing at any of `commits`, sorted by name
+    pub fn bookmarks_on(&self, commits: &[Commit]) -> Vec<String> {
+        let mut names = commits
+            .iter()
+            .flat_map(|commit| self.repo.view().local_bookmarks_for_commit(commit.id()))
+            .map(|(name, _)| name.as_str().to_string())
+            .collect::<Vec<_>>();
+        names.sort();
+        names.dedup();
+        names
+    }
+
     pub fn parent_commits(&self, commit: &Commit) -> Result<Vec<Commit>> {
         commit
             .parents()
@@ -22,12 +22,18 @@ impl JjRepo {
         self.eval_revset_single("@")
     }
 
+    pub(super) fn resolve_revset(
+        &self,
+        revset_str: &str,
+    ) -> Result<Arc<revset::ResolvedRevsetExpression>> {
+        query::resolve_revset(self, revset_str)
+    }
+
     pub(super) fn with_revset_context<T>(
         &self,
-        user_email: &str,
         f: impl FnOnce(&Arc<revset::RevsetExtensions>, &RevsetParseContext<'_>) -> Result<T>,
     ) -> Result<T> {
-        let aliases_map = aliases::aliases_map();
+        let aliases_map = aliases::aliases_map(&self.remotes.trunk, &self.settings);
         let extensions = Arc::new(revset::RevsetExtensions::default());
         let path_converter = jj_lib::repo_path::RepoPathUiConverter::Fs {
             cwd: self.workspace.workspace_root().to_path_buf(),
@@ -40,7 +46,7 @@ impl JjRepo {
         let context = RevsetParseContext {
             aliases_map: &aliases_map,
             local_variables: HashMap::new(),
-            user_email,
+            user_email: self.settings.user_email(),
             date_pattern_context: chrono::Utc::now().fixed_offset().into(),
             default_ignored_remote: Some(RemoteName::new("git")),
             workspace: Some(workspace_ctx),
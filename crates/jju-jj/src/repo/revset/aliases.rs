@@ -1,21 +1,35 @@
 use jj_lib::revset;
+use jj_lib::settings::UserSettings;
 
-pub(super) fn aliases_map() -> revset::RevsetAliasesMap {
+/// Remotes whose main, master or trunk bookmark can be `trunk()`
+const TRUNK_REMOTES: [&str; 2] = ["origin", "upstream"];
+
+/// `trunk()` over the configured trunk remote and the usual ones
+fn trunk_definition(trunk_remote: &str) -> String {
+    let mut remotes = vec![trunk_remote];
+    remotes.extend(
+        TRUNK_REMOTES
+            .into_iter()
+            .filter(|remote| *remote != trunk_remote),
+    );
+
+    let candidates = remotes
+        .iter()
+        .flat_map(|remote| {
+            ["main", "master", "trunk"]
+                .map(|name| format!(r#"remote_bookmarks(exact:"{name}", exact:"{remote}")"#))
+        })
+        .collect::<Vec<_>>();
+    format!("latest({} | root())", candidates.join(" | "))
+}
+
+/// jju's defaults, overridden by the user's own `revset-aliases`
+pub(super) fn aliases_map(trunk_remote: &str, settings: &UserSettings) -> revset::RevsetAliasesMap {
     let mut aliases_map = revset::RevsetAliasesMap::new();
 
+    let trunk = trunk_definition(trunk_remote);
     let default_aliases = [
-        (
-            "trunk()",
-            r#"latest(
-              remote_bookmarks(exact:"main", exact:"origin") |
-              remote_bookmarks(exact:"master", exact:"origin") |
-              remote_bookmarks(exact:"trunk", exact:"origin") |
-              remote_bookmarks(exact:"main", exact:"upstream") |
-              remote_bookmarks(exact:"master", exact:"upstream") |
-              remote_bookmarks(exact:"trunk", exact:"upstream") |
-              root()
-            )"#,
-        ),
+        ("trunk()", trunk.as_str()),
         (
             "builtin_immutable_heads()",
             "trunk() | tags() | untracked_remote_bookmarks()",
@@ -28,5 +42,10 @@ pub(super) fn aliases_map() -> revset::RevsetAliasesMap {
     for (name, definition) in default_aliases {
         let _ = aliases_map.insert(name, definition);
     }
+    for name in settings.table_keys("revset-aliases") {
+        if let Ok(definition) = settings.get_string(["revset-aliases", name]) {
+            let _ = aliases_map.insert(name, definition);
+        }
+    }
     aliases_map
 }
@@ -4,10 +4,29 @@ use itertools::Itertools;
 use jj_lib::commit::Commit;
 use jj_lib::id_prefix::IdPrefixContext;
 use jj_lib::repo::Repo;
-use jj_lib::revset::{self, RevsetDiagnostics, RevsetIteratorExt, SymbolResolver};
+use jj_lib::revset::{
+    self, ResolvedRevsetExpression, RevsetDiagnostics, RevsetIteratorExt, SymbolResolver,
+};
+use std::sync::Arc;
 
 pub(super) fn eval_revset(repo: &JjRepo, revset_str: &str) -> Result<Vec<Commit>> {
-    repo.with_revset_context("jj-lib@localhost", |extensions, context| {
+    let resolved = resolve_revset(repo, revset_str)?;
+    let evaluated = resolved
+        .evaluate(repo.repo.as_ref())
+        .wrap_err("failed to evaluate revset")?;
+
+    evaluated
+        .iter()
+        .commits(repo.repo.store())
+        .try_collect()
+        .wrap_err("failed to collect commits")
+}
+
+pub(super) fn resolve_revset(
+    repo: &JjRepo,
+    revset_str: &str,
+) -> Result<Arc<ResolvedRevsetExpression>> {
+    repo.with_revset_context(|extensions, context| {
         let mut diagnostics = RevsetDiagnostics::new();
         let expression = revset::parse(&mut diagnostics, revset_str, context)
             .wrap_err_with(|| format!("failed to parse revset: {revset_str}"))?;
@@ -17,19 +36,9 @@ pub(super) fn eval_revset(repo: &JjRepo, revset_str: &str) -> Result<Vec<Commit>
             SymbolResolver::new(repo.repo.as_ref(), extensions.symbol_resolvers())
                 .with_id_prefix_context(&id_prefix_context);
 
-        let resolved = expression
+        expression
             .resolve_user_expression(repo.repo.as_ref(), &symbol_resolver)
-            .wrap_err("failed to resolve revset expression")?;
-
-        let evaluated = resolved
-            .evaluate(repo.repo.as_ref())
-            .wrap_err("failed to evaluate revset")?;
-
-        evaluated
-            .iter()
-            .commits(repo.repo.store())
-            .try_collect()
-            .wrap_err("failed to collect commits")
+            .wrap_err("failed to resolve revset expression")
     })
 }
 
@@ -0,0 +1,339 @@
+use super::{FileContents, JjRepo, SplitLayer};
+use eyre::{Context, Result, bail, eyre};
+use jj_lib::backend::{CopyId, TreeValue};
+use jj_lib::commit::Commit;
+use jj_lib::conflicts::{MaterializedTreeValue, materialize_tree_value};
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::{MergedTree, MergedTreeBuilder};
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::{RepoPath, RepoPathBuf};
+use pollster::FutureExt as _;
+
+impl JjRepo {
+    /// Read a file from a commit's parent tree, `None` when it doesn't exist there
+    pub fn parent_file_contents(
+        &self,
+        commit: &Commit,
+        path: &str,
+    ) -> Result<Option<FileContents>> {
+        let parent_tree = commit
+            .parent_tree(self.repo.as_ref())
+            .wrap_err("failed to load parent tree")?;
+        read_file(&parent_tree, &repo_path(path)?)
+    }
+
+    /// Read a file from a commit's own tree, `None` when it doesn't exist there
+    pub fn file_contents(&self, commit: &Commit, path: &str) -> Result<Option<FileContents>> {
+        read_file(&commit.tree(), &repo_path(path)?)
+    }
+
+    /// Split `commit` into a stack of new commits followed by the original
+    ///
+    /// Each layer's files replace entries of the commit's parent tree, so a layer
+    /// must include everything from the layers below it. The original commit keeps
+    /// its change id and tree and is rebased, with its descendants, on top of the
+    /// last layer. All of it happens in a single operation, and the working copy
+    /// is only touched when the split commit was checked out
+    pub fn split_commit(&mut self, commit: &Commit, layers: &[SplitLayer]) -> Result<()> {
+        if layers.is_empty() {
+            bail!("nothing to split");
+        }
+
+        let parent_tree = commit
+            .parent_tree(self.repo.as_ref())
+            .wrap_err("failed to load parent tree")?;
+        let workspace_name = self.workspace.workspace_name().to_owned();
+        let old_wc_commit_id = self.repo.view().get_wc_commit_id(&workspace_name).cloned();
+        let old_wc_tree = match &old_wc_commit_id {
+            Some(id) => Some(self.repo.store().get_commit(id)?.tree()),
+            None => None,
+        };
+
+        let mut tx = self.repo.start_transaction();
+        let mut parents = commit.parent_ids().to_vec();
+        for layer in layers {
+            let tree = layer_tree(&parent_tree, layer)?;
+            let builder = tx.repo_mut().new_commit(parents, tree);
+            // like `jj split`, keep the author and commit as the configured user
+            let new_commit = builder
+                .set_description(&layer.message)
+                .set_author(commit.author().clone())
+                .write()
+                .wrap_err("failed to write split commit")?;
+            parents = vec![new_commit.id().clone()];
+        }
+
+        tx.repo_mut()
+            .rewrite_commit(commit)
+            .set_parents(parents)
+            .write()
+            .wrap_err("failed to rewrite split revision")?;
+        tx.repo_mut()
+            .rebase_descendants()
+            .wrap_err("failed to rebase descendants")?;
+
+        let change_id = commit.change_id().reverse_hex();
+        let short_id = &change_id[..change_id.len().min(12)];
+        self.repo = tx
+            .commit(format!(
+                "split-hunk {short_id} into {} commits",
+                layers.len() + 1
+            ))
+            .wrap_err("failed to commit split transaction")?;
+
+        self.update_working_copy(&workspace_name, old_wc_commit_id, old_wc_tree.as_ref())
+    }
+
+    /// Record the rewritten working-copy commit in the workspace if a rewrite moved it
+    pub(super) fn update_working_copy(
+        &mut self,
+        workspace_name: &jj_lib::ref_name::WorkspaceName,
+        old_wc_commit_id: Option<jj_lib::backend::CommitId>,
+        old_wc_tree: Option<&MergedTree>,
+    ) -> Result<()> {
+        let new_wc_commit_id = self.repo.view().get_wc_commit_id(workspace_name).cloned();
+        let Some(new_wc_commit_id) = new_wc_commit_id else {
+            return Ok(());
+        };
+        if old_wc_commit_id.as_ref() == Some(&new_wc_commit_id) {
+            return Ok(());
+        }
+
+        let new_wc_commit = self.repo.store().get_commit(&new_wc_commit_id)?;
+        self.workspace
+            .check_out(self.repo.op_id().clone(), old_wc_tree, &new_wc_commit)
+            .map_err(|error| eyre!("failed to update working copy: {error}"))?;
+        Ok(())
+    }
+}
+
+pub(super) fn repo_path(path: &str) -> Result<RepoPathBuf> {
+    RepoPathBuf::from_internal_string(path).wrap_err_with(|| format!("invalid repo path: {path}"))
+}
+
+pub(super) fn read_file(tree: &MergedTree, path: &RepoPath) -> Result<Option<FileContents>> {
+    let value = tree
+        .path_value(path)
+        .wrap_err_with(|| format!("failed to read {}", path.as_internal_file_string()))?;
+    let materialized = materialize_tree_value(tree.store(), path, value.clone(), tree.labels())
+        .block_on()
+        .wrap_err_with(|| format!("failed to read {}", path.as_internal_file_string()))?;
+
+    let verbatim = |text: Vec<u8>| FileContents {
+        text,
+        executable: false,
+        verbatim: Some(value.clone()),
+    };
+    match materialized {
+        MaterializedTreeValue::Absent => Ok(None),
+        MaterializedTreeValue::File(mut file) => Ok(Some(FileContents::new(
+            file.read_all(path).block_on()?,
+            file.executable,
+        ))),
+        MaterializedTreeValue::Symlink { target, .. } => Ok(Some(verbatim(target.into_bytes()))),
+        MaterializedTreeValue::AccessDenied(error) => Err(eyre!(
+            "failed to read {}: {error}",
+            path.as_internal_file_string()
+        )),
+        MaterializedTreeValue::FileConflict(_)
+        | MaterializedTreeValue::OtherConflict { .. }
+        | MaterializedTreeValue::GitSubmodule(_)
+        | MaterializedTreeValue::Tree(_) => Ok(Some(verbatim(Vec::new()))),
+    }
+}
+
+fn layer_tree(parent_tree: &MergedTree, layer: &SplitLayer) -> Result<MergedTree> {
+    tree_builder(parent_tree, &layer.files)?
+        .write_tree()
+        .wrap_err("failed to write split tree")
+}
+
+/// Builder for `parent_tree` with `files` written over it, `None` removing a file
+pub(super) fn tree_builder(
+    parent_tree: &MergedTree,
+    files: &[(String, Option<FileContents>)],
+) -> Result<MergedTreeBuilder> {
+    let store = parent_tree.store();
+    let mut builder = MergedTreeBuilder::new(parent_tree.clone());
+    for (path, contents) in files {
+        let path = repo_path(path)?;
+        let value = match contents {
+            Some(FileContents {
+                verbatim: Some(value),
+                ..
+            }) => value.clone(),
+            Some(contents) => {
+                let id = store
+                    .write_file(&path, &mut contents.text.as_slice())
+                    .block_on()
+                    .wrap_err_with(|| {
+                        format!("failed to write {}", path.as_internal_file_string())
+                    })?;
+                Merge::normal(TreeValue::File {
+                    id,
+                    executable: contents.executable,
+                    copy_id: CopyId::placeholder(),
+                })
+            }
+            None => Merge::absent(),
+        };
+        builder.set_or_remove(path, value);
+    }
+    Ok(builder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SplitLayer, layer_tree, read_file, repo_path};
+    use crate::repo::test_support::{TempRepo, file};
+    use jj_lib::backend::TreeValue;
+    use jj_lib::merge::Merge;
+    use jj_lib::merged_tree::MergedTreeBuilder;
+    use jj_lib::object_id::ObjectId;
+    use jj_lib::repo::Repo;
+    use pollster::FutureExt as _;
+
+    #[test]
+    fn test_split_commit_stacks_layer_below_original() {
+        let temp = TempRepo::init("split");
+        let repo = &temp.repo;
+
+        let mut tx = repo.start_transaction();
+        let tree = layer_tree(
+            &repo.store().empty_merged_tree(),
+            &SplitLayer {
+                message: String::new(),
+                files: vec![
+                    ("a.txt".to_string(), file("a\n")),
+                    ("b.txt".to_string(), file("b\n")),
+                ],
+            },
+        )
+        .expect("tree");
+        let original = tx
+            .repo_mut()
+            .new_commit(vec![repo.store().root_commit_id().clone()], tree)
+            .set_description("both files")
+            .write()
+            .expect("commit");
+        tx.commit("add files").expect("transaction");
+
+        let mut jj_repo = temp.load();
+        let commit = jj_repo
+            .eval_revset_single(&original.id().hex())
+            .expect("resolve commit");
+        jj_repo
+            .split_commit(
+                &commit,
+                &[SplitLayer {
+                    message: "only a".to_string(),
+                    files: vec![("a.txt".to_string(), file("a\n"))],
+                }],
+            )
+            .expect("split");
+
+        let rewritten = jj_repo
+            .eval_revset_single(&original.change_id().reverse_hex())
+            .expect("rewritten commit");
+        let parent = jj_repo
+            .eval_revset_single(&format!("{}-", rewritten.id().hex()))
+            .expect("split parent");
+        assert_eq!(rewritten.tree_ids(), original.tree_ids());
+        assert_eq!(rewritten.description(), "both files");
+        assert_eq!(parent.description(), "only a");
+        assert!(
+            read_file(&parent.tree(), &repo_path("a.txt").expect("path"))
+                .expect("read a")
+                .is_some()
+        );
+        assert!(
+            read_file(&parent.tree(), &repo_path("b.txt").expect("path"))
+                .expect("read b")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_split_commit_of_working_copy_keeps_files_on_disk() {
+        let mut temp = TempRepo::init("split-wc");
+        let repo = temp.repo.clone();
+
+        let mut tx = repo.start_transaction();
+        let tree = layer_tree(
+            &repo.store().empty_merged_tree(),
+            &SplitLayer {
+                message: String::new(),
+                files: vec![
+                    ("a.txt".to_string(), file("a\n")),
+                    ("b.txt".to_string(), file("b\n")),
+                ],
+            },
+        )
+        .expect("tree");
+        let original = tx
+            .repo_mut()
+            .new_commit(vec![repo.store().root_commit_id().clone()], tree)
+            .write()
+            .expect("commit");
+        tx.repo_mut()
+            .edit(temp.workspace.workspace_name().to_owned(), &original)
+            .expect("edit");
+        tx.repo_mut().rebase_descendants().expect("rebase");
+        let repo = tx.commit("check out files").expect("transaction");
+        temp.workspace
+            .check_out(repo.op_id().clone(), None, &original)
+            .expect("check out");
+
+        let mut jj_repo = temp.load();
+        let commit = jj_repo.working_copy_commit().expect("working copy");
+        jj_repo
+            .split_commit(
+                &commit,
+                &[SplitLayer {
+                    message: "only b".to_string(),
+                    files: vec![("b.txt".to_string(), file("b\n"))],
+                }],
+            )
+            .expect("split");
+
+        let reloaded = temp.load();
+        let working_copy = reloaded.working_copy_commit().expect("working copy");
+        assert_eq!(working_copy.change_id(), original.change_id());
+        assert_ne!(working_copy.id(), original.id());
+        assert_eq!(
+            std::fs::read_to_string(temp.root().join("a.txt")).expect("a on disk"),
+            "a\n"
+        );
+    }
+
+    #[test]
+    fn test_read_file_passes_symlinks_through() {
+        let temp = TempRepo::init("symlink");
+        let store = temp.repo.store();
+        let path = repo_path("link").expect("path");
+        let id = store
+            .write_symlink(&path, "target.txt")
+            .block_on()
+            .expect("symlink");
+        let mut builder = MergedTreeBuilder::new(store.empty_merged_tree());
+        builder.set_or_remove(path.clone(), Merge::normal(TreeValue::Symlink(id)));
+        let tree = builder.write_tree().expect("tree");
+
+        let contents = read_file(&tree, &path).expect("read link").expect("link");
+        assert!(contents.is_verbatim());
+        assert_eq!(contents.text, b"target.txt");
+
+        let rewritten = layer_tree(
+            &store.empty_merged_tree(),
+            &SplitLayer {
+                message: String::new(),
+                files: vec![("link".to_string(), Some(contents))],
+            },
+        )
+        .expect("rewritten tree");
+        assert_eq!(
+            rewritten.path_value(&path).expect("value"),
+            tree.path_value(&path).expect("value")
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! The config layers jj itself reads, so revsets and identity match `jj log`
+
+use eyre::{Context, Result};
+use jj_lib::config::{ConfigLayer, ConfigResolutionContext, ConfigSource, StackedConfig, resolve};
+use jj_lib::settings::UserSettings;
+use std::path::{Path, PathBuf};
+
+/// Settings for the workspace at `workspace_root`: jj's defaults, then the
+/// user config (`$JJ_CONFIG` or the usual config files), the repo and
+/// workspace configs, and the `JJ_USER`/`JJ_EMAIL` style overrides
+pub(super) fn load_user_settings(workspace_root: &Path) -> Result<UserSettings> {
+    let mut config = StackedConfig::with_defaults();
+    config.add_layer(env_base_layer()?);
+
+    for path in user_config_paths() {
+        if path.is_dir() {
+            config.load_dir(ConfigSource::User, &path)?;
+        } else if path.is_file() {
+            config.load_file(ConfigSource::User, path)?;
+        }
+    }
+
+    add_workspace_layers(&mut config, workspace_root)?;
+    config.add_layer(env_overrides_layer()?);
+    resolve_settings(&config, workspace_root)
+}
+
+/// Settings for the workspace at `workspace_root` with `config` in place of jj's
+/// defaults, the user config and the environment, so the repo and workspace
+/// configs are all that's read from disk
+pub(super) fn load_pinned_settings(
+    workspace_root: &Path,
+    mut config: StackedConfig,
+) -> Result<UserSettings> {
+    add_workspace_layers(&mut config, workspace_root)?;
+    resolve_settings(&config, workspace_root)
+}
+
+fn add_workspace_layers(config: &mut StackedConfig, workspace_root: &Path) -> Result<()> {
+    let repo_config = repo_path(workspace_root).join("config.toml");
+    if repo_config.is_file() {
+        config.load_file(ConfigSource::Repo, repo_config)?;
+    }
+    let workspace_config = workspace_root.join(".jj").join("workspace-config.toml");
+    if workspace_config.is_file() {
+        config.load_file(ConfigSource::Workspace, workspace_config)?;
+    }
+    Ok(())
+}
+
+/// Resolve the `--when` conditions of `config` for the workspace, like jj does
+fn resolve_settings(config: &StackedConfig, workspace_root: &Path) -> Result<UserSettings> {
+    let home_dir = home_dir();
+    let repo_path = repo_path(workspace_root);
+    let hostname = whoami::fallible::hostname().unwrap_or_default();
+    let context = ConfigResolutionContext {
+        home_dir: home_dir.as_deref(),
+        repo_path: Some(&repo_path),
+        workspace_path: Some(workspace_root),
+        command: None,
+        hostname: &hostname,
+    };
+    let config = resolve(config, &context).wrap_err("failed to resolve jj config")?;
+    UserSettings::from_config(config).wrap_err("failed to create user settings")
+}
+
+/// `$JJ_CONFIG` when set, otherwise `~/.jjconfig.toml` and the `jj` config directories
+fn user_config_paths() -> Vec<PathBuf> {
+    if let Some(paths) = std::env::var_os("JJ_CONFIG") {
+        return std::env::split_paths(&paths)
+            .filter(|path| !path.as_os_str().is_empty())
+            .collect();
+    }
+
+    let mut config_dirs = Vec::new();
+    if let Some(config_home) = std::env::var_os("XDG_CONFIG_HOME") {
+        config_dirs.push(PathBuf::from(config_home));
+    }
+    if let Some(home) = home_dir() {
+        config_dirs.push(home.join(".config"));
+        if cfg!(target_os = "macos") {
+            config_dirs.push(home.join("Library/Application Support"));
+        }
+    }
+    config_dirs.dedup();
+
+    let mut paths = home_dir()
+        .map(|home| vec![home.join(".jjconfig.toml")])
+        .unwrap_or_default();
+    for dir in config_dirs {
+        paths.push(dir.join("jj/config.toml"));
+        paths.push(dir.join("jj/conf.d"));
+    }
+    paths
+}
+
+/// The repo directory, following `.jj/repo` when it points at another workspace's repo
+fn repo_path(workspace_root: &Path) -> PathBuf {
+    let jj_dir = workspace_root.join(".jj");
+    let repo_path = jj_dir.join("repo");
+    if !repo_path.is_file() {
+        return repo_path;
+    }
+
+    match std::fs::read_to_string(&repo_path) {
+        Ok(target) => jj_dir.join(target.trim()),
+        Err(_) => repo_path,
+    }
+}
+
+/// The operation identity, from the environment like jj does
+fn env_base_layer() -> Result<ConfigLayer> {
+    let mut layer = ConfigLayer::empty(ConfigSource::EnvBase);
+    if let Ok(username) = whoami::fallible::username() {
+        layer.set_value("operation.username", username)?;
+    }
+    if let Ok(hostname) = whoami::fallible::hostname() {
+        layer.set_value("operation.hostname", hostname)?;
+    }
+    Ok(layer)
+}
+
+fn env_overrides_layer() -> Result<ConfigLayer> {
+    let mut layer = ConfigLayer::empty(ConfigSource::EnvOverrides);
+    let overrides = [
+        ("JJ_USER", "user.name"),
+        ("JJ_EMAIL", "user.email"),
+        ("JJ_OP_USERNAME", "operation.username"),
+        ("JJ_OP_HOSTNAME", "operation.hostname"),
+    ];
+    for (variable, name) in overrides {
+        if let Ok(value) = std::env::var(variable) {
+            layer.set_value(name, value)?;
+        }
+    }
+    Ok(layer)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repo::test_support::TempRepo;
+    use jj_lib::repo::Repo;
+
+    #[test]
+    fn test_repo_config_overrides_identity_and_builtin_aliases() {
+        let temp = TempRepo::init("repo-config");
+        std::fs::write(
+            temp.root().join(".jj/repo/config.toml"),
+            r#"
+            user.email = "someone@example.com"
+
+            [revset-aliases]
+            "trunk()" = "root()"
+            "tip" = "@"
+            "#,
+        )
+        .expect("write repo config");
+
+        let jj_repo = temp.load();
+        assert_eq!(jj_repo.settings.user_email(), "someone@example.com");
+        let trunk = jj_repo.eval_revset_single("trunk()").expect("trunk");
+        assert_eq!(trunk.id(), temp.repo.store().root_commit_id());
+        assert_eq!(
+            jj_repo.eval_revset_single("tip").expect("alias").id(),
+            jj_repo.working_copy_commit().expect("working copy").id()
+        );
+    }
+}
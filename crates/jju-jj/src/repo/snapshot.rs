@@ -0,0 +1,130 @@
+use super::JjRepo;
+use eyre::{Context, Result, bail};
+use jj_lib::fileset::{self, FilesetDiagnostics};
+use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::matchers::NothingMatcher;
+use jj_lib::object_id::ObjectId;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::settings::HumanByteSize;
+use jj_lib::working_copy::SnapshotOptions;
+use pollster::FutureExt as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+impl JjRepo {
+    /// Record the files on disk into the working-copy commit, like jj does before
+    /// every command, returning whether anything changed
+    ///
+    /// Follows `snapshot.auto-track` and `snapshot.max-new-file-size`, and fails
+    /// without touching anything when the working copy is stale
+    pub fn snapshot_working_copy(&mut self) -> Result<bool> {
+        let workspace_name = self.workspace.workspace_name().to_owned();
+        let Some(wc_commit_id) = self.repo.view().get_wc_commit_id(&workspace_name).cloned() else {
+            return Ok(false);
+        };
+        let wc_commit = self.repo.store().get_commit(&wc_commit_id)?;
+
+        let auto_track = self
+            .settings
+            .get_string("snapshot.auto-track")
+            .unwrap_or_else(|_| "all()".to_string());
+        let path_converter = RepoPathUiConverter::Fs {
+            cwd: self.workspace.workspace_root().to_path_buf(),
+            base: self.workspace.workspace_root().to_path_buf(),
+        };
+        let start_tracking_matcher =
+            fileset::parse(&mut FilesetDiagnostics::new(), &auto_track, &path_converter)
+                .wrap_err("invalid snapshot.auto-track")?
+                .to_matcher();
+        let max_new_file_size = self
+            .settings
+            .get_value_with("snapshot.max-new-file-size", HumanByteSize::try_from)
+            .map(|size| size.0)
+            .unwrap_or(1024 * 1024);
+        let options = SnapshotOptions {
+            base_ignores: self.base_ignores()?,
+            progress: None,
+            start_tracking_matcher: start_tracking_matcher.as_ref(),
+            force_tracking_matcher: &NothingMatcher,
+            max_new_file_size,
+        };
+
+        let mut locked_ws = self
+            .workspace
+            .start_working_copy_mutation()
+            .wrap_err("failed to lock the working copy")?;
+        let old_tree = locked_ws.locked_wc().old_tree().clone();
+        if old_tree.tree_ids_and_labels() != wc_commit.tree().tree_ids_and_labels() {
+            bail!(
+                "the working copy is stale (not updated since operation {}), run `jj workspace update-stale`",
+                &locked_ws.locked_wc().old_operation_id().hex()[..12]
+            );
+        }
+
+        let (new_tree, _stats) = locked_ws
+            .locked_wc()
+            .snapshot(&options)
+            .block_on()
+            .wrap_err("failed to snapshot the working copy")?;
+        let changed = new_tree.tree_ids_and_labels() != old_tree.tree_ids_and_labels();
+        if changed {
+            let mut tx = self.repo.start_transaction();
+            tx.repo_mut()
+                .rewrite_commit(&wc_commit)
+                .set_tree(new_tree)
+                .write()
+                .wrap_err("failed to record the working copy")?;
+            tx.repo_mut()
+                .rebase_descendants()
+                .wrap_err("failed to rebase descendants")?;
+            self.repo = tx
+                .commit("snapshot working copy")
+                .wrap_err("failed to commit snapshot")?;
+        }
+
+        locked_ws
+            .finish(self.repo.op_id().clone())
+            .wrap_err("failed to save the working copy state")?;
+        Ok(changed)
+    }
+
+    /// Ignores that apply outside `.gitignore` files: git's global ignore file
+    /// and the backing git repo's `info/exclude`
+    fn base_ignores(&self) -> Result<Arc<GitIgnoreFile>> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+        let mut ignores = GitIgnoreFile::empty();
+        if let Some(config_home) = config_home {
+            ignores = ignores.chain_with_file("", config_home.join("git").join("ignore"))?;
+        }
+        if let Ok(git_backend) = jj_lib::git::get_git_backend(self.repo.store()) {
+            let exclude = git_backend.git_repo_path().join("info").join("exclude");
+            ignores = ignores.chain_with_file("", exclude)?;
+        }
+        Ok(ignores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repo::test_support::TempRepo;
+
+    #[test]
+    fn test_snapshot_records_new_files_in_the_working_copy() {
+        let temp = TempRepo::init("snapshot");
+        std::fs::write(temp.root().join("a.txt"), "edited in an editor\n").expect("write file");
+
+        let mut jj_repo = temp.load();
+        assert!(jj_repo.snapshot_working_copy().expect("snapshot"));
+        let working_copy = jj_repo.working_copy_commit().expect("working copy");
+        let contents = jj_repo
+            .file_contents(&working_copy, "a.txt")
+            .expect("read")
+            .expect("a.txt recorded");
+        assert_eq!(contents.text, b"edited in an editor\n");
+
+        assert!(!jj_repo.snapshot_working_copy().expect("second snapshot"));
+    }
+}
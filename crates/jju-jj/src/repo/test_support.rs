@@ -0,0 +1,95 @@
+//! Scratch repos for tests
+
+use super::rewrite::tree_builder;
+use super::{FileContents, JjRepo, create_user_settings, scratch_config};
+use jj_lib::commit::Commit;
+use jj_lib::repo::{ReadonlyRepo, Repo};
+use jj_lib::workspace::Workspace;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A new jj repo in its own temp directory, removed again on drop
+pub struct TempRepo {
+    root: PathBuf,
+    pub workspace: Workspace,
+    /// The repo as it was right after init
+    pub repo: Arc<ReadonlyRepo>,
+}
+
+impl TempRepo {
+    pub fn init(name: &str) -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        let root = std::env::temp_dir().join(format!("jju-{name}-{}-{nanos}", std::process::id()));
+        std::fs::create_dir_all(&root).expect("create temp workspace");
+        let settings = create_user_settings().expect("settings");
+        let (workspace, repo) = Workspace::init_simple(&settings, &root).expect("init workspace");
+        Self {
+            root,
+            workspace,
+            repo,
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Load the repo the way jju does, picking up everything written so far
+    ///
+    /// The user's own config is left out, so a custom `trunk()` or identity on
+    /// the machine running the tests can't change their results
+    pub fn load(&self) -> JjRepo {
+        let config = scratch_config().expect("config");
+        JjRepo::load_with_config(Some(&self.root), config).expect("load repo")
+    }
+}
+
+impl Drop for TempRepo {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.root).ok();
+    }
+}
+
+pub fn file(text: &str) -> Option<FileContents> {
+    Some(FileContents::new(text.as_bytes().to_vec(), false))
+}
+
+/// Write a commit on `parent` with `files` changed, returning the new repo and commit
+pub fn write_commit(
+    repo: &Arc<ReadonlyRepo>,
+    parent: &Commit,
+    description: &str,
+    files: &[(&str, &str)],
+) -> (Arc<ReadonlyRepo>, Commit) {
+    let files = files
+        .iter()
+        .map(|(path, text)| (path.to_string(), file(text)))
+        .collect::<Vec<_>>();
+    let tree = tree_builder(&parent.tree(), &files)
+        .expect("tree builder")
+        .write_tree()
+        .expect("tree");
+    let mut tx = repo.start_transaction();
+    let commit = tx
+        .repo_mut()
+        .new_commit(vec![parent.id().clone()], tree)
+        .set_description(description)
+        .write()
+        .expect("commit");
+    (tx.commit("write commit").expect("transaction"), commit)
+}
+
+/// The commit checked out in the repo's default workspace
+pub fn working_copy_commit(repo: &Arc<ReadonlyRepo>) -> Commit {
+    let id = repo
+        .view()
+        .wc_commit_ids()
+        .values()
+        .next()
+        .expect("working copy")
+        .clone();
+    repo.store().get_commit(&id).expect("commit")
+}
@@ -1,14 +1,23 @@
 mod application;
 mod command;
 mod diff;
+mod groups;
 mod plan;
 mod preview;
 mod repo;
+mod report;
 mod selection;
 mod workflow;
 
-pub use command::SplitHunkCommand;
-use eyre::{Result, eyre};
+pub(crate) use application::SplitHunkApplication;
+pub use command::{SplitHunkCommand, load_diff, split_plan};
+pub use diff::{DiffHunk, DiffLine, DiffLineKind, FileDiff, FileStatus, ParsedDiff};
+use eyre::{Result, bail, eyre};
+pub use plan::SplitHunkPlan;
+pub(crate) use preview::{hunk_header, preview_plan, print_hunk_lines};
+pub use selection::SelectedHunk;
+pub(crate) use selection::{SplitHunkPlanner, parse_hunk_indices, parse_line_ranges};
+use std::str::FromStr;
 
 #[derive(Debug, Clone)]
 pub struct SplitHunkOptions {
@@ -18,12 +27,53 @@ pub struct SplitHunkOptions {
     pub lines: Option<String>,
     pub hunks: Option<String>,
     pub pattern: Option<String>,
+    /// Selection specs for a multi-way split, one stacked commit per group
+    pub groups: Vec<String>,
+    /// Split every changed file into its own commit
+    pub by_file: bool,
     pub preview: bool,
     pub invert: bool,
+    pub line_precise: bool,
     pub dry_run: bool,
+    pub format: OutputFormat,
+}
+
+/// How `--preview` and `--dry-run` report their results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format `{value}`, expected text or json")),
+        }
+    }
 }
 
 impl SplitHunkOptions {
+    fn is_multi_way(&self) -> bool {
+        self.by_file || !self.groups.is_empty()
+    }
+
+    fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    fn validate_format(&self) -> Result<()> {
+        if self.is_json() && !self.preview && !self.dry_run {
+            bail!("--format json requires --preview or --dry-run");
+        }
+        Ok(())
+    }
+
     fn commit_message(&self) -> Result<&str> {
         if self.preview {
             return Ok("");
@@ -1,47 +1,118 @@
+use super::diff::{DiffHunk, DiffLine, DiffLineKind, FileStatus};
 use super::plan::SplitHunkPlan;
-use super::repo::SplitHunkRepo;
+use crate::repo::{FileContents, JjRepo, SplitLayer};
 use ahash::HashMap;
-use eyre::Result;
+use eyre::{Context, Result, bail};
+use jj_lib::commit::Commit;
+use std::collections::BTreeSet;
 
 #[derive(Debug, Clone)]
 pub(crate) struct SplitHunkApplication {
-    pub(crate) new_contents: HashMap<String, String>,
-    pub(crate) original_contents: HashMap<String, String>,
+    /// New contents of each touched path, `None` for files the split removes
+    pub(crate) new_contents: HashMap<String, Option<FileContents>>,
 }
 
 impl SplitHunkApplication {
-    pub(crate) fn build(
-        plan: &SplitHunkPlan,
-        repo: &SplitHunkRepo,
-        revision: &str,
-    ) -> Result<Self> {
+    /// Apply the plan's selection to the files of `commit`'s parent
+    pub(crate) fn build(plan: &SplitHunkPlan, repo: &JjRepo, commit: &Commit) -> Result<Self> {
         let mut new_contents = HashMap::default();
-        let parent_revision = format!("{revision}-");
 
         for selection in plan.selected_files() {
-            let parent_lines =
-                repo.read_file_lines_or_empty(&parent_revision, selection.file.path());
+            let file = selection.file;
+            if file.is_whole_file() {
+                // the plan always selects these in full, so take the file as the commit has it
+                if file.status() == FileStatus::Renamed
+                    && let Some(old_path) = file.old_path()
+                {
+                    new_contents.insert(old_path.to_string(), None);
+                }
+                let contents = match file.status() {
+                    FileStatus::Deleted => None,
+                    _ => repo.file_contents(commit, file.path())?,
+                };
+                new_contents.insert(file.path().to_string(), contents);
+                continue;
+            }
+
+            let parent = repo.parent_file_contents(commit, file.path())?;
+            let new = repo.file_contents(commit, file.path())?;
+            if parent.iter().chain(&new).any(FileContents::is_verbatim) {
+                // symlinks and the like have no lines to pick, so they move as they are
+                new_contents.insert(file.path().to_string(), new);
+                continue;
+            }
+
+            let parent_text = parent
+                .as_ref()
+                .map(|parent| parent.text.as_slice())
+                .unwrap_or_default();
+            let new_text = new
+                .as_ref()
+                .map(|new| new.text.as_slice())
+                .unwrap_or_default();
             let result_lines = apply_hunks_to_lines(
-                &parent_lines,
-                selection.file.hunks(),
+                &byte_lines(parent_text),
+                &byte_lines(new_text),
+                file.hunks(),
                 &selection.selected_hunks,
+                &selection.selected_lines,
+            )
+            .wrap_err_with(|| format!("failed to apply the selected hunks to {}", file.path()))?;
+
+            new_contents.insert(
+                file.path().to_string(),
+                Some(FileContents::new(
+                    join_lines(&result_lines),
+                    parent.is_some_and(|parent| parent.executable),
+                )),
             );
-            new_contents.insert(selection.file.path().to_string(), result_lines.join("\n"));
         }
 
-        let original_contents = repo.collect_original_contents(plan.files(), revision);
-        Ok(Self {
-            new_contents,
-            original_contents,
-        })
+        Ok(Self { new_contents })
+    }
+
+    pub(crate) fn into_layer(self, message: &str) -> SplitLayer {
+        let mut files = self.new_contents.into_iter().collect::<Vec<_>>();
+        files.sort_by(|left, right| left.0.cmp(&right.0));
+        SplitLayer {
+            message: message.to_string(),
+            files,
+        }
+    }
+}
+
+/// The lines of `text`, each with its own line terminator
+pub(super) fn byte_lines(text: &[u8]) -> Vec<&[u8]> {
+    text.split_inclusive(|&byte| byte == b'\n').collect()
+}
+
+/// Join lines back into a file, ending any line that lost its last position with a newline
+///
+/// Only the last line of a file can lack a terminator, so a parent's unterminated
+/// last line that now has lines after it gets one
+pub(super) fn join_lines(lines: &[&[u8]]) -> Vec<u8> {
+    let mut text = Vec::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+    for (index, line) in lines.iter().enumerate() {
+        text.extend_from_slice(line);
+        if index + 1 < lines.len() && !line.ends_with(b"\n") {
+            text.push(b'\n');
+        }
     }
+    text
 }
 
-fn apply_hunks_to_lines(
-    parent_lines: &[String],
-    hunks: &[super::diff::DiffHunk],
+/// Apply the selected hunks to `parent_lines`
+///
+/// Kept context and removed lines are copied from the parent and added lines from
+/// `new_lines`, the file as the commit has it, so line endings and bytes that
+/// aren't UTF-8 come through exactly
+pub(super) fn apply_hunks_to_lines<'a>(
+    parent_lines: &[&'a [u8]],
+    new_lines: &[&'a [u8]],
+    hunks: &[DiffHunk],
     selected_indices: &[usize],
-) -> Vec<String> {
+    selected_lines: &HashMap<usize, BTreeSet<usize>>,
+) -> Result<Vec<&'a [u8]>> {
     let mut result_lines = parent_lines.to_vec();
     let mut sorted_indices = selected_indices.to_vec();
     sorted_indices.sort_by(|left, right| right.cmp(left));
@@ -50,58 +121,160 @@ fn apply_hunks_to_lines(
         let hunk = &hunks[hunk_index];
         let insert_pos = hunk.old_start().saturating_sub(1);
         let remove_end = (insert_pos + hunk.old_count()).min(result_lines.len());
-        result_lines.drain(insert_pos..remove_end);
-
-        let new_lines = hunk
-            .lines()
-            .iter()
-            .filter(|line| !line.kind.is_removed())
-            .map(|line| line.content.clone())
-            .collect::<Vec<_>>();
+        if insert_pos > remove_end {
+            bail!(
+                "hunk {} starts past the end of the parent file",
+                hunk.header()
+            );
+        }
 
-        for (index, line) in new_lines.into_iter().enumerate() {
-            result_lines.insert(insert_pos + index, line);
+        let kept_lines = selected_lines.get(&hunk_index);
+        let old_positions = hunk.old_positions();
+        let new_positions = hunk.line_positions();
+        let mut new_hunk_lines = Vec::new();
+        for (line_index, line) in hunk.lines().iter().enumerate() {
+            if !keeps_line(line, kept_lines.map(|kept| kept.contains(&line_index))) {
+                continue;
+            }
+            let (lines, position) = match line.kind {
+                DiffLineKind::Added => (new_lines, new_positions[line_index]),
+                DiffLineKind::Context | DiffLineKind::Removed => {
+                    (parent_lines, old_positions[line_index])
+                }
+            };
+            let Some(&content) = position.checked_sub(1).and_then(|index| lines.get(index)) else {
+                bail!("hunk {} doesn't match the file contents", hunk.header());
+            };
+            new_hunk_lines.push(content);
         }
+
+        result_lines.splice(insert_pos..remove_end, new_hunk_lines);
     }
 
-    result_lines
+    Ok(result_lines)
+}
+
+/// Whether a hunk line ends up in the split commit
+///
+/// `selected` is `None` when the whole hunk is applied. A removal that isn't
+/// selected stays as it was in the parent, an addition that isn't selected is dropped
+fn keeps_line(line: &DiffLine, selected: Option<bool>) -> bool {
+    match (line.kind, selected) {
+        (DiffLineKind::Context, _) => true,
+        (DiffLineKind::Added, selected) => selected.unwrap_or(true),
+        (DiffLineKind::Removed, selected) => !selected.unwrap_or(true),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::apply_hunks_to_lines;
+    use super::{apply_hunks_to_lines, byte_lines, join_lines};
     use crate::split_hunk::diff::ParsedDiff;
+    use ahash::HashMap;
+    use std::collections::BTreeSet;
+
+    /// Apply the selection of the single file in `diff` to `parent`, with `new` as the commit's file
+    fn apply(
+        diff: &str,
+        parent: &[u8],
+        new: &[u8],
+        selected_indices: &[usize],
+        selected_lines: &HashMap<usize, BTreeSet<usize>>,
+    ) -> Vec<u8> {
+        let diff = ParsedDiff::parse(diff);
+        let lines = apply_hunks_to_lines(
+            &byte_lines(parent),
+            &byte_lines(new),
+            diff.files()[0].hunks(),
+            selected_indices,
+            selected_lines,
+        )
+        .expect("apply hunks");
+        join_lines(&lines)
+    }
 
     #[test]
     fn test_apply_hunks_to_lines_applies_selected_hunks_bottom_up() {
-        let diff = ParsedDiff::parse(
-            r#"diff --git a/src/lib.rs b/src/lib.rs
+        let diff = r#"diff --git a/src/lib.rs b/src/lib.rs
 @@ -1,1 +1,2 @@
  line one
 +inserted one
-@@ -3,1 +3,2 @@
+@@ -3,1 +4,2 @@
  line three
 +inserted three
-"#,
-        );
-        let file = &diff.files()[0];
-        let parent_lines = vec![
-            "line one".to_string(),
-            "line two".to_string(),
-            "line three".to_string(),
-        ];
-
-        let applied = apply_hunks_to_lines(&parent_lines, file.hunks(), &[0, 1]);
-
-        assert_eq!(
-            applied,
-            vec![
-                "line one".to_string(),
-                "inserted one".to_string(),
-                "line two".to_string(),
-                "line three".to_string(),
-                "inserted three".to_string(),
-            ]
+"#;
+        let parent = b"line one\nline two\nline three\n";
+        let new = b"line one\ninserted one\nline two\nline three\ninserted three\n";
+
+        let applied = apply(diff, parent, new, &[0, 1], &HashMap::default());
+
+        assert_eq!(applied, new);
+    }
+
+    #[test]
+    fn test_apply_hunks_to_lines_applies_only_selected_lines() {
+        let diff = r#"diff --git a/src/lib.rs b/src/lib.rs
+@@ -1,3 +1,3 @@
+ keep
+-old one
+-old two
++new one
++new two
+"#;
+        let selected_lines = HashMap::from_iter([(0, BTreeSet::from([1, 3]))]);
+
+        let applied = apply(
+            diff,
+            b"keep\nold one\nold two\n",
+            b"keep\nnew one\nnew two\n",
+            &[0],
+            &selected_lines,
         );
+
+        assert_eq!(applied, b"keep\nold two\nnew one\n");
+    }
+
+    #[test]
+    fn test_apply_hunks_to_lines_keeps_crlf_and_non_utf8_bytes() {
+        let diff = "diff --git a/notes.txt b/notes.txt\n@@ -1,3 +1,3 @@\n one\r\n-two\r\n+caf\u{fffd}\r\n three\r\n";
+        let parent = b"one\r\ntwo\r\nthree\r\n";
+        let new = b"one\r\ncaf\xe9\r\nthree\r\n";
+
+        assert_eq!(apply(diff, parent, new, &[0], &HashMap::default()), new);
+        assert_eq!(apply(diff, parent, new, &[], &HashMap::default()), parent);
+    }
+
+    #[test]
+    fn test_apply_hunks_to_lines_follows_selected_end_of_file_hunk() {
+        let diff = r#"diff --git a/notes.txt b/notes.txt
+@@ -1,2 +1,2 @@
+ first
+-second
++changed
+\ No newline at end of file
+"#;
+        let parent = b"first\nsecond\n";
+        let new = b"first\nchanged";
+
+        assert_eq!(apply(diff, parent, new, &[0], &HashMap::default()), new);
+        // keeping the removed line but not its replacement keeps the parent's newline
+        let selected_lines = HashMap::from_iter([(0, BTreeSet::from([]))]);
+        assert_eq!(apply(diff, parent, new, &[0], &selected_lines), parent);
+    }
+
+    #[test]
+    fn test_join_lines_ends_a_parent_last_line_that_moves_up() {
+        let diff = r#"diff --git a/notes.txt b/notes.txt
+@@ -1,1 +1,2 @@
+-last
+\ No newline at end of file
++last
++after
+"#;
+        let selected_lines = HashMap::from_iter([(0, BTreeSet::from([2]))]);
+
+        let applied = apply(diff, b"last", b"last\nafter\n", &[0], &selected_lines);
+
+        assert_eq!(applied, b"last\nafter\n");
     }
 }
@@ -1,3 +1,7 @@
+use super::application::SplitHunkApplication;
+use super::diff::ParsedDiff;
+use super::plan::SplitHunkPlan;
+use super::repo::SplitHunkRepo;
 use super::{SplitHunkOptions, workflow::SplitHunkWorkflow};
 use eyre::Result;
 
@@ -12,6 +16,20 @@ impl SplitHunkCommand {
     }
 
     pub fn run(self) -> Result<()> {
-        SplitHunkWorkflow::new(self.options, super::repo::SplitHunkRepo).run()
+        SplitHunkWorkflow::new(self.options).run()
     }
 }
+
+/// Load and parse the git diff of a revision
+pub fn load_diff(revision: &str) -> Result<ParsedDiff> {
+    let diff_output = SplitHunkRepo::load_diff(revision)?;
+    Ok(ParsedDiff::parse(&diff_output))
+}
+
+/// Split the hunks selected in `plan` out of `revision` into a new parent commit
+pub fn split_plan(plan: &SplitHunkPlan, revision: &str, message: &str) -> Result<()> {
+    let mut repo = SplitHunkRepo::load()?;
+    let commit = repo.resolve(revision)?;
+    let layer = SplitHunkApplication::build(plan, repo.jj_repo(), &commit)?.into_layer(message);
+    repo.execute_split(&commit, &[layer])
+}
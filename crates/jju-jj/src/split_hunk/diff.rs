@@ -4,4 +4,4 @@ mod parse;
 #[cfg(test)]
 mod tests;
 
-pub(crate) use model::{DiffHunk, DiffLineKind, FileDiff, ParsedDiff};
+pub use model::{DiffHunk, DiffLine, DiffLineKind, FileDiff, FileStatus, ParsedDiff};
@@ -32,3 +32,92 @@ pub(super) fn parse_hunk_header(line: &str) -> (usize, usize, usize, usize) {
         .unwrap_or(1);
     (old_start, old_count, new_start, new_count)
 }
+
+/// One extended header line of a git diff, between `diff --git` and the first hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum FileHeader {
+    NewFile {
+        mode: String,
+    },
+    DeletedFile {
+        mode: String,
+    },
+    OldMode(String),
+    NewMode(String),
+    /// The mode on an `index` line, present when it didn't change
+    IndexMode(String),
+    RenameFrom(String),
+    RenameTo(String),
+    CopyFrom(String),
+    CopyTo(String),
+    Binary,
+}
+
+pub(super) fn parse_file_header(line: &str) -> Option<FileHeader> {
+    let field = |prefix: &str| {
+        line.strip_prefix(prefix)
+            .map(|value| value.trim().to_string())
+    };
+
+    if let Some(mode) = field("new file mode ") {
+        return Some(FileHeader::NewFile { mode });
+    }
+    if let Some(mode) = field("deleted file mode ") {
+        return Some(FileHeader::DeletedFile { mode });
+    }
+    if let Some(mode) = field("old mode ") {
+        return Some(FileHeader::OldMode(mode));
+    }
+    if let Some(mode) = field("new mode ") {
+        return Some(FileHeader::NewMode(mode));
+    }
+    if let Some(path) = field("rename from ") {
+        return Some(FileHeader::RenameFrom(unquote_path(&path)));
+    }
+    if let Some(path) = field("rename to ") {
+        return Some(FileHeader::RenameTo(unquote_path(&path)));
+    }
+    if let Some(path) = field("copy from ") {
+        return Some(FileHeader::CopyFrom(unquote_path(&path)));
+    }
+    if let Some(path) = field("copy to ") {
+        return Some(FileHeader::CopyTo(unquote_path(&path)));
+    }
+    if line.starts_with("Binary files ") || line == "GIT binary patch" {
+        return Some(FileHeader::Binary);
+    }
+    if let Some(index) = field("index ") {
+        return index
+            .split_whitespace()
+            .nth(1)
+            .map(|mode| FileHeader::IndexMode(mode.to_string()));
+    }
+    None
+}
+
+/// The `b/` path of a `diff --git a/... b/...` line
+///
+/// Both sides name the same path unless the file was renamed, and then the
+/// `rename to` header has the final say
+pub(super) fn parse_file_path(line: &str) -> String {
+    let paths = line.strip_prefix("diff --git ").unwrap_or(line);
+    let path = if let Some(index) = paths.find(" \"b/") {
+        paths[index + 4..].trim_end_matches('"')
+    } else if let Some(index) = paths.find(" b/") {
+        &paths[index + 3..]
+    } else {
+        paths
+            .split_whitespace()
+            .nth(1)
+            .unwrap_or("")
+            .trim_start_matches("b/")
+    };
+    path.to_string()
+}
+
+fn unquote_path(path: &str) -> String {
+    path.strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+        .unwrap_or(path)
+        .to_string()
+}
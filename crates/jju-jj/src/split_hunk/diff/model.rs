@@ -1,26 +1,26 @@
-use super::header::parse_hunk_header;
+use super::header::{FileHeader, parse_hunk_header};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) enum DiffLineKind {
+pub enum DiffLineKind {
     Context,
     Added,
     Removed,
 }
 
 impl DiffLineKind {
-    pub(crate) fn is_added(self) -> bool {
+    pub fn is_added(self) -> bool {
         matches!(self, Self::Added)
     }
 
-    pub(crate) fn is_removed(self) -> bool {
+    pub fn is_removed(self) -> bool {
         matches!(self, Self::Removed)
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) struct DiffLine {
-    pub(crate) kind: DiffLineKind,
-    pub(crate) content: String,
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub content: String,
 }
 
 impl DiffLine {
@@ -40,12 +40,14 @@ impl DiffLine {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) struct DiffHunk {
+pub struct DiffHunk {
     old_start: usize,
     old_count: usize,
     new_start: usize,
     new_count: usize,
     lines: Vec<DiffLine>,
+    /// Indices of lines followed by `\ No newline at end of file`
+    missing_newline: Vec<usize>,
 }
 
 impl DiffHunk {
@@ -61,9 +63,16 @@ impl DiffHunk {
             new_start,
             new_count,
             lines: Vec::new(),
+            missing_newline: Vec::new(),
         }
     }
 
+    /// Stand-in hunk for a file change without line changes, such as a binary
+    /// file or a pure rename, so the change can still be selected
+    pub(crate) fn whole_file() -> Self {
+        Self::new(0, 0, 0, 0)
+    }
+
     pub(super) fn parse_header(line: &str) -> Self {
         let (old_start, old_count, new_start, new_count) = parse_hunk_header(line);
         Self::new(old_start, old_count, new_start, new_count)
@@ -73,30 +82,114 @@ impl DiffHunk {
         self.lines.push(line);
     }
 
-    pub(crate) fn first_line(&self) -> usize {
+    /// Record that the last pushed line has no trailing newline
+    pub(crate) fn mark_missing_newline(&mut self) {
+        if let Some(index) = self.lines.len().checked_sub(1) {
+            self.missing_newline.push(index);
+        }
+    }
+
+    /// Whether the line at `index` in `lines()` ends its file without a newline
+    pub fn lacks_newline(&self, index: usize) -> bool {
+        self.missing_newline.contains(&index)
+    }
+
+    pub fn first_line(&self) -> usize {
         self.new_start
     }
 
-    pub(crate) fn last_line(&self) -> usize {
+    pub fn last_line(&self) -> usize {
         self.new_start + self.new_count.saturating_sub(1)
     }
 
-    pub(crate) fn old_start(&self) -> usize {
+    pub fn old_start(&self) -> usize {
         self.old_start
     }
 
-    pub(crate) fn old_count(&self) -> usize {
+    pub fn old_count(&self) -> usize {
         self.old_count
     }
 
-    pub(crate) fn lines(&self) -> &[DiffLine] {
+    pub fn new_count(&self) -> usize {
+        self.new_count
+    }
+
+    pub fn lines(&self) -> &[DiffLine] {
         &self.lines
     }
+
+    /// Indices into `lines()` of the added and removed lines
+    pub fn changed_line_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.kind != DiffLineKind::Context)
+            .map(|(index, _)| index)
+    }
+
+    /// New-side line number of each line in `lines()`
+    ///
+    /// A removed line reports the position of the line that follows it in the
+    /// new file, so a replaced line and its replacement share one number
+    pub fn line_positions(&self) -> Vec<usize> {
+        let mut position = self.new_start;
+        self.lines
+            .iter()
+            .map(|line| {
+                let current = position;
+                if !line.kind.is_removed() {
+                    position += 1;
+                }
+                current
+            })
+            .collect()
+    }
+
+    /// Old-side line number of each line in `lines()`
+    ///
+    /// The mirror of `line_positions`: an added line reports the position of the
+    /// parent line that follows it
+    pub fn old_positions(&self) -> Vec<usize> {
+        let mut position = self.old_start;
+        self.lines
+            .iter()
+            .map(|line| {
+                let current = position;
+                if !line.kind.is_added() {
+                    position += 1;
+                }
+                current
+            })
+            .collect()
+    }
+
+    /// The unified-diff `@@ -a,b +c,d @@` header for this hunk
+    pub fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_count, self.new_start, self.new_count
+        )
+    }
+}
+
+/// What happened to a file as a whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Modified,
+    Added,
+    Deleted,
+    Renamed,
+    Copied,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) struct FileDiff {
+pub struct FileDiff {
     path: String,
+    old_path: Option<String>,
+    status: FileStatus,
+    old_mode: Option<String>,
+    new_mode: Option<String>,
+    binary: bool,
     hunks: Vec<DiffHunk>,
 }
 
@@ -104,30 +197,127 @@ impl FileDiff {
     pub(super) fn new(path: String) -> Self {
         Self {
             path,
+            old_path: None,
+            status: FileStatus::Modified,
+            old_mode: None,
+            new_mode: None,
+            binary: false,
             hunks: Vec::new(),
         }
     }
 
+    pub(super) fn apply_header(&mut self, header: FileHeader) {
+        match header {
+            FileHeader::NewFile { mode } => {
+                self.status = FileStatus::Added;
+                self.new_mode = Some(mode);
+            }
+            FileHeader::DeletedFile { mode } => {
+                self.status = FileStatus::Deleted;
+                self.old_mode = Some(mode);
+            }
+            FileHeader::OldMode(mode) => self.old_mode = Some(mode),
+            FileHeader::NewMode(mode) => self.new_mode = Some(mode),
+            FileHeader::IndexMode(mode) => {
+                self.old_mode.get_or_insert_with(|| mode.clone());
+                self.new_mode.get_or_insert(mode);
+            }
+            FileHeader::RenameFrom(path) => {
+                self.status = FileStatus::Renamed;
+                self.old_path = Some(path);
+            }
+            FileHeader::CopyFrom(path) => {
+                self.status = FileStatus::Copied;
+                self.old_path = Some(path);
+            }
+            FileHeader::RenameTo(path) | FileHeader::CopyTo(path) => self.path = path,
+            FileHeader::Binary => self.binary = true,
+        }
+    }
+
     pub(super) fn push_hunk(&mut self, hunk: DiffHunk) {
         self.hunks.push(hunk);
     }
 
-    pub(crate) fn path(&self) -> &str {
+    /// Give a whole-file change without any hunks a placeholder one to select it by
+    pub(super) fn finish(&mut self) {
+        if self.hunks.is_empty() && self.is_whole_file() {
+            self.hunks.push(DiffHunk::whole_file());
+        }
+    }
+
+    pub fn path(&self) -> &str {
         &self.path
     }
 
-    pub(crate) fn hunks(&self) -> &[DiffHunk] {
+    /// The source path of a rename or copy
+    pub fn old_path(&self) -> Option<&str> {
+        self.old_path.as_deref()
+    }
+
+    pub fn status(&self) -> FileStatus {
+        self.status
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.binary
+    }
+
+    /// The old and new modes, when they differ
+    pub fn mode_change(&self) -> Option<(&str, &str)> {
+        match (&self.old_mode, &self.new_mode) {
+            (Some(old), Some(new)) if old != new && self.status != FileStatus::Added => {
+                Some((old, new))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the file can only move between commits in one piece
+    ///
+    /// Adds, deletes, renames, copies, binary files and mode changes can't be
+    /// split by line, so all of their hunks are selected together
+    pub fn is_whole_file(&self) -> bool {
+        self.status != FileStatus::Modified || self.binary || self.mode_change().is_some()
+    }
+
+    /// Short description of a whole-file change, e.g. `renamed from src/old.rs`
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        match self.status {
+            FileStatus::Modified => {}
+            FileStatus::Added => parts.push("new file".to_string()),
+            FileStatus::Deleted => parts.push("deleted".to_string()),
+            FileStatus::Renamed => parts.push(format!(
+                "renamed from {}",
+                self.old_path.as_deref().unwrap_or("?")
+            )),
+            FileStatus::Copied => parts.push(format!(
+                "copied from {}",
+                self.old_path.as_deref().unwrap_or("?")
+            )),
+        }
+        if self.binary {
+            parts.push("binary".to_string());
+        }
+        if let Some((old, new)) = self.mode_change() {
+            parts.push(format!("mode {old} -> {new}"));
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    pub fn hunks(&self) -> &[DiffHunk] {
         &self.hunks
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) struct ParsedDiff {
+pub struct ParsedDiff {
     files: Vec<FileDiff>,
 }
 
 impl ParsedDiff {
-    pub(crate) fn empty() -> Self {
+    pub fn empty() -> Self {
         Self { files: Vec::new() }
     }
 
@@ -142,11 +332,11 @@ impl ParsedDiff {
         self
     }
 
-    pub(crate) fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.files.is_empty()
     }
 
-    pub(crate) fn files(&self) -> &[FileDiff] {
+    pub fn files(&self) -> &[FileDiff] {
         &self.files
     }
 }
@@ -1,7 +1,8 @@
+use super::header::{parse_file_header, parse_file_path};
 use super::model::{DiffHunk, DiffLine, FileDiff, ParsedDiff};
 
 impl ParsedDiff {
-    pub(crate) fn parse(diff_output: &str) -> Self {
+    pub fn parse(diff_output: &str) -> Self {
         let mut files = Vec::new();
         let mut current_file: Option<FileDiff> = None;
         let mut current_hunk: Option<DiffHunk> = None;
@@ -20,9 +21,18 @@ impl ParsedDiff {
                 continue;
             }
 
-            if let Some(hunk) = &mut current_hunk
-                && let Some(diff_line) = DiffLine::parse(line)
-            {
+            let Some(hunk) = &mut current_hunk else {
+                if let Some(file) = &mut current_file
+                    && let Some(header) = parse_file_header(line)
+                {
+                    file.apply_header(header);
+                }
+                continue;
+            };
+
+            if line.starts_with('\\') {
+                hunk.mark_missing_newline();
+            } else if let Some(diff_line) = DiffLine::parse(line) {
                 hunk.push_line(diff_line);
             }
         }
@@ -34,14 +44,6 @@ impl ParsedDiff {
     }
 }
 
-fn parse_file_path(line: &str) -> String {
-    line.split_whitespace()
-        .nth(3)
-        .map(|part| part.trim_start_matches("b/"))
-        .unwrap_or("")
-        .to_string()
-}
-
 fn flush_current_hunk(current_file: &mut Option<FileDiff>, current_hunk: &mut Option<DiffHunk>) {
     if let Some(hunk) = current_hunk.take()
         && let Some(file) = current_file
@@ -51,7 +53,8 @@ fn flush_current_hunk(current_file: &mut Option<FileDiff>, current_hunk: &mut Op
 }
 
 fn flush_current_file(files: &mut Vec<FileDiff>, current_file: &mut Option<FileDiff>) {
-    if let Some(file) = current_file.take() {
+    if let Some(mut file) = current_file.take() {
+        file.finish();
         files.push(file);
     }
 }
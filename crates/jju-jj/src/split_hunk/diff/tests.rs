@@ -1,4 +1,4 @@
-use super::{DiffLineKind, ParsedDiff};
+use super::{DiffLineKind, FileStatus, ParsedDiff};
 
 #[test]
 fn test_parse_diff_output_groups_files_and_hunks() {
@@ -25,3 +25,62 @@ diff --git a/src/main.rs b/src/main.rs
     );
     assert_eq!(parsed.files()[1].path(), "src/main.rs");
 }
+
+#[test]
+fn test_parse_diff_output_reads_whole_file_headers() {
+    let parsed = ParsedDiff::parse(
+        r#"diff --git a/src/new.rs b/src/new.rs
+new file mode 100644
+index 0000000000..e69de29bb2
+@@ -0,0 +1,1 @@
++fn new() {}
+diff --git a/src/gone.rs b/src/gone.rs
+deleted file mode 100755
+index e69de29bb2..0000000000
+@@ -1,1 +0,0 @@
+-fn gone() {}
+diff --git a/src/old.rs b/src/moved.rs
+rename from src/old.rs
+rename to src/moved.rs
+diff --git a/logo.png b/logo.png
+index 1111111111..2222222222 100644
+Binary files a/logo.png and b/logo.png differ
+diff --git a/run.sh b/run.sh
+old mode 100644
+new mode 100755
+"#,
+    );
+
+    let files = parsed.files();
+    assert_eq!(files.len(), 5);
+    assert_eq!(files[0].status(), FileStatus::Added);
+    assert_eq!(files[1].status(), FileStatus::Deleted);
+    assert_eq!(files[2].status(), FileStatus::Renamed);
+    assert_eq!(files[2].path(), "src/moved.rs");
+    assert_eq!(files[2].old_path(), Some("src/old.rs"));
+    assert!(files[3].is_binary());
+    assert_eq!(files[4].mode_change(), Some(("100644", "100755")));
+    assert!(files.iter().all(|file| file.is_whole_file()));
+    // changes without line hunks still get one to select them by
+    assert_eq!(files[2].hunks().len(), 1);
+    assert!(files[3].hunks()[0].lines().is_empty());
+}
+
+#[test]
+fn test_parse_diff_output_records_missing_newlines() {
+    let parsed = ParsedDiff::parse(
+        r#"diff --git a/notes.txt b/notes.txt
+index 1111111111..2222222222 100644
+@@ -1,1 +1,1 @@
+-old
+\ No newline at end of file
++new
+"#,
+    );
+
+    let file = &parsed.files()[0];
+    let hunk = &file.hunks()[0];
+    assert!(!file.is_whole_file());
+    assert!(hunk.lacks_newline(0));
+    assert!(!hunk.lacks_newline(1));
+}
@@ -0,0 +1,244 @@
+use super::SplitHunkOptions;
+use super::diff::ParsedDiff;
+use super::plan::SplitHunkPlan;
+use super::selection::{SplitHunkPlanner, parse_hunk_indices, parse_line_ranges};
+use eyre::{Result, bail, eyre};
+use jju_core::split_hunk::SplitSelectionPlan;
+
+const GROUP_KEYS: [&str; 5] = ["file", "hunks", "lines", "pattern", "message"];
+
+/// One commit of a multi-way split
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SplitGroup {
+    pub(crate) message: String,
+    pub(crate) selection: SplitSelectionPlan,
+}
+
+impl SplitGroup {
+    /// Parse a `--group` spec such as `file=src/a.rs,hunks=0,2,message=Add a`
+    ///
+    /// Keys are `file`, `hunks`, `lines`, `pattern` and `message`. A comma-separated
+    /// part that doesn't start with one of them continues the previous value, so
+    /// lists and messages may contain commas and `=`
+    pub(crate) fn parse(spec: &str, options: &SplitHunkOptions) -> Result<Self> {
+        let mut entries: Vec<(String, String)> = Vec::new();
+        for part in spec.split(',') {
+            let entry = part
+                .split_once('=')
+                .filter(|(key, _)| GROUP_KEYS.contains(&key.trim()));
+            match (entry, entries.last_mut()) {
+                (Some((key, value)), _) => {
+                    entries.push((key.trim().to_string(), value.to_string()))
+                }
+                (None, Some((_, value))) => {
+                    value.push(',');
+                    value.push_str(part);
+                }
+                (None, None) => match part.split_once('=') {
+                    Some((key, _)) => bail!("unknown group key {:?} in {spec:?}", key.trim()),
+                    None => bail!("invalid group spec {spec:?}: expected key=value"),
+                },
+            }
+        }
+
+        let mut message = None;
+        let mut selection = SplitSelectionPlan {
+            file_filter: None,
+            hunk_indices: None,
+            line_ranges: None,
+            pattern: None,
+            invert: options.invert,
+            line_precise: options.line_precise,
+        };
+        for (key, value) in entries {
+            match key.as_str() {
+                "message" => message = Some(value),
+                "file" => selection.file_filter = Some(value),
+                "hunks" => selection.hunk_indices = Some(parse_hunk_indices(&value)?),
+                "lines" => selection.line_ranges = Some(parse_line_ranges(&value)?),
+                "pattern" => selection.pattern = Some(value),
+                _ => unreachable!("only known keys start an entry: {key}"),
+            }
+        }
+
+        let message = message
+            .filter(|message| !message.trim().is_empty())
+            .ok_or_else(|| eyre!("group {spec:?} is missing message="))?;
+        Ok(Self { message, selection })
+    }
+}
+
+/// One group per changed file, except the last file which stays in the original revision
+///
+/// `template` is the commit message, with `{file}` replaced by the file's path
+pub(crate) fn groups_by_file(
+    diff: &ParsedDiff,
+    template: Option<&str>,
+    options: &SplitHunkOptions,
+) -> Vec<SplitGroup> {
+    let template = template.unwrap_or("{file}");
+    let mut global_index = 0;
+    let mut groups = Vec::new();
+
+    for file in diff.files() {
+        let hunk_count = file.hunks().len();
+        groups.push(SplitGroup {
+            message: template.replace("{file}", file.path()),
+            selection: SplitSelectionPlan {
+                file_filter: None,
+                hunk_indices: Some((global_index..global_index + hunk_count).collect()),
+                line_ranges: None,
+                pattern: None,
+                invert: false,
+                line_precise: options.line_precise,
+            },
+        });
+        global_index += hunk_count;
+    }
+
+    groups.pop();
+    groups
+}
+
+/// A group's own selection alongside everything to be committed up to and including it
+#[derive(Debug, Clone)]
+pub(crate) struct StackedPlan {
+    pub(crate) message: String,
+    pub(crate) own: SplitHunkPlan,
+    pub(crate) stacked: SplitHunkPlan,
+}
+
+/// Build one plan per group, each including the selections of every group below it
+///
+/// Splitting these in order stacks the groups as consecutive commits under the revision
+pub(crate) fn stacked_plans(diff: &ParsedDiff, groups: &[SplitGroup]) -> Result<Vec<StackedPlan>> {
+    let mut cumulative: Option<SplitHunkPlan> = None;
+    let mut plans = Vec::with_capacity(groups.len());
+
+    for (index, group) in groups.iter().enumerate() {
+        let plan = SplitHunkPlanner::new(group.selection.clone())?.build(diff.clone());
+        if !plan.has_selection() {
+            bail!("group {} ({:?}) matched no hunks", index + 1, group.message);
+        }
+
+        let stacked = match &cumulative {
+            Some(below) => below.union(&plan),
+            None => plan.clone(),
+        };
+        cumulative = Some(stacked.clone());
+        plans.push(StackedPlan {
+            message: group.message.clone(),
+            own: plan,
+            stacked,
+        });
+    }
+
+    Ok(plans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SplitGroup, groups_by_file, stacked_plans};
+    use crate::split_hunk::diff::ParsedDiff;
+    use crate::split_hunk::{OutputFormat, SplitHunkOptions};
+
+    fn options() -> SplitHunkOptions {
+        SplitHunkOptions {
+            message: None,
+            revision: "@".to_string(),
+            file_filter: None,
+            lines: None,
+            hunks: None,
+            pattern: None,
+            groups: Vec::new(),
+            by_file: false,
+            preview: false,
+            invert: false,
+            line_precise: false,
+            dry_run: false,
+            format: OutputFormat::Text,
+        }
+    }
+
+    fn parsed_diff() -> ParsedDiff {
+        ParsedDiff::parse(
+            r#"diff --git a/src/a.rs b/src/a.rs
+@@ -1,1 +1,2 @@
+ a
++a2
+diff --git a/src/b.rs b/src/b.rs
+@@ -1,1 +1,2 @@
+ b
++b2
+@@ -5,1 +6,2 @@
+ b5
++b6
+"#,
+        )
+    }
+
+    #[test]
+    fn test_parse_group_spec_allows_commas_in_lists_and_messages() {
+        let group = SplitGroup::parse("file=src/b.rs,hunks=0,2,message=Fix b, again", &options())
+            .expect("parse group");
+
+        assert_eq!(group.message, "Fix b, again");
+        assert_eq!(group.selection.file_filter.as_deref(), Some("src/b.rs"));
+        assert_eq!(group.selection.hunk_indices, Some(vec![0, 2]));
+    }
+
+    #[test]
+    fn test_parse_group_spec_keeps_equals_signs_in_values() {
+        let group =
+            SplitGroup::parse("message=Fix a, b=c,pattern=x=1", &options()).expect("parse group");
+
+        assert_eq!(group.message, "Fix a, b=c");
+        assert_eq!(group.selection.pattern.as_deref(), Some("x=1"));
+    }
+
+    #[test]
+    fn test_parse_group_spec_rejects_unknown_leading_key() {
+        let error =
+            SplitGroup::parse("files=src/a.rs,message=a", &options()).expect_err("unknown key");
+
+        assert!(error.to_string().contains("unknown group key \"files\""));
+    }
+
+    #[test]
+    fn test_parse_group_spec_requires_message() {
+        assert!(SplitGroup::parse("file=src/a.rs", &options()).is_err());
+    }
+
+    #[test]
+    fn test_stacked_plans_accumulate_lower_groups() {
+        let diff = parsed_diff();
+        let groups = [
+            SplitGroup::parse("file=src/a.rs,message=a", &options()).expect("group a"),
+            SplitGroup::parse("file=src/b.rs,lines=6,message=b", &options()).expect("group b"),
+        ];
+
+        let plans = stacked_plans(&diff, &groups).expect("plans");
+
+        assert_eq!(plans.len(), 2);
+        assert_eq!(plans[0].stacked.selected_count(), 1);
+        assert_eq!(plans[1].message, "b");
+        assert_eq!(plans[1].own.selected_count(), 1);
+        assert_eq!(plans[1].stacked.selected_count(), 2);
+    }
+
+    #[test]
+    fn test_stacked_plans_reject_empty_group() {
+        let groups = [SplitGroup::parse("file=missing.rs,message=x", &options()).expect("group")];
+
+        assert!(stacked_plans(&parsed_diff(), &groups).is_err());
+    }
+
+    #[test]
+    fn test_groups_by_file_leaves_last_file_in_revision() {
+        let groups = groups_by_file(&parsed_diff(), Some("update {file}"), &options());
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].message, "update src/a.rs");
+        assert_eq!(groups[0].selection.hunk_indices, Some(vec![0]));
+    }
+}
@@ -1,36 +1,125 @@
 use super::diff::{FileDiff, ParsedDiff};
 use super::selection::SelectedHunk;
 use ahash::{HashMap, HashMapExt};
+use std::collections::BTreeSet;
 
 #[derive(Debug, Clone)]
 pub(crate) struct FileSelection<'a> {
     pub(crate) file: &'a FileDiff,
     pub(crate) selected_hunks: Vec<usize>,
+    /// Changed-line indices to keep for partially selected hunks, keyed by hunk index
+    pub(crate) selected_lines: HashMap<usize, BTreeSet<usize>>,
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct SplitHunkPlan {
+pub struct SplitHunkPlan {
     diff: ParsedDiff,
     selected: Vec<SelectedHunk>,
+    selected_lines: HashMap<SelectedHunk, BTreeSet<usize>>,
 }
 
 impl SplitHunkPlan {
-    pub(crate) fn new(diff: ParsedDiff, selected: Vec<SelectedHunk>) -> Self {
-        Self { diff, selected }
+    pub fn new(diff: ParsedDiff, selected: Vec<SelectedHunk>) -> Self {
+        Self {
+            diff,
+            selected,
+            selected_lines: HashMap::new(),
+        }
+        .with_whole_files()
+    }
+
+    /// Narrow `hunk` to some of its changed lines, given as indices into `DiffHunk::lines()`
+    ///
+    /// The hunk is added to the selection if it isn't already part of it. Unselected
+    /// removals stay in place and unselected additions are left out of the split
+    pub fn with_selected_lines(
+        mut self,
+        hunk: SelectedHunk,
+        lines: impl IntoIterator<Item = usize>,
+    ) -> Self {
+        if !self.selected.contains(&hunk) {
+            self.selected.push(hunk);
+        }
+        self.selected_lines.entry(hunk).or_default().extend(lines);
+        self.with_whole_files()
+    }
+
+    /// Widen any selection that touches a whole-file change to the entire file
+    fn with_whole_files(mut self) -> Self {
+        let files = self.diff.files();
+        let whole_files = self
+            .selected
+            .iter()
+            .map(|hunk| hunk.file_index)
+            .filter(|&file_index| files[file_index].is_whole_file())
+            .collect::<BTreeSet<_>>();
+
+        for file_index in whole_files {
+            for hunk_index in 0..files[file_index].hunks().len() {
+                let hunk = SelectedHunk {
+                    file_index,
+                    hunk_index,
+                };
+                if !self.selected.contains(&hunk) {
+                    self.selected.push(hunk);
+                }
+            }
+        }
+        self.selected_lines
+            .retain(|hunk, _| !files[hunk.file_index].is_whole_file());
+        self
     }
 
-    pub(crate) fn files(&self) -> &[FileDiff] {
+    pub fn files(&self) -> &[FileDiff] {
         self.diff.files()
     }
 
-    pub(crate) fn selected_count(&self) -> usize {
+    pub fn selected_count(&self) -> usize {
         self.selected.len()
     }
 
-    pub(crate) fn has_selection(&self) -> bool {
+    pub fn has_selection(&self) -> bool {
         !self.selected.is_empty()
     }
 
+    pub fn is_selected(&self, hunk: &SelectedHunk) -> bool {
+        self.selected.contains(hunk)
+    }
+
+    /// Changed lines kept for `hunk`, `None` when the whole hunk is selected
+    pub fn selected_lines(&self, hunk: &SelectedHunk) -> Option<&BTreeSet<usize>> {
+        self.selected_lines.get(hunk)
+    }
+
+    /// Combine the selections of two plans over the same diff
+    ///
+    /// A hunk picked whole by either side stays whole, partial picks merge their lines
+    pub(crate) fn union(&self, other: &SplitHunkPlan) -> SplitHunkPlan {
+        let mut combined = self.clone();
+        for hunk in &other.selected {
+            let other_lines = other.selected_lines.get(hunk);
+            if !combined.selected.contains(hunk) {
+                combined.selected.push(*hunk);
+                if let Some(lines) = other_lines {
+                    combined.selected_lines.insert(*hunk, lines.clone());
+                }
+                continue;
+            }
+
+            match other_lines {
+                None => {
+                    combined.selected_lines.remove(hunk);
+                }
+                Some(lines) => {
+                    if let Some(existing) = combined.selected_lines.get_mut(hunk) {
+                        existing.extend(lines.iter().copied());
+                    }
+                }
+            }
+        }
+        combined
+    }
+
     pub(crate) fn selected_files(&self) -> Vec<FileSelection<'_>> {
         let mut grouped = HashMap::<usize, Vec<usize>>::new();
         for selected in &self.selected {
@@ -47,6 +136,12 @@ impl SplitHunkPlan {
             .into_iter()
             .map(|(file_index, selected_hunks)| FileSelection {
                 file: &self.diff.files()[file_index],
+                selected_lines: self
+                    .selected_lines
+                    .iter()
+                    .filter(|(hunk, _)| hunk.file_index == file_index)
+                    .map(|(hunk, lines)| (hunk.hunk_index, lines.clone()))
+                    .collect(),
                 selected_hunks,
             })
             .collect()
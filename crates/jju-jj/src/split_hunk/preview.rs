@@ -5,34 +5,55 @@ use colored::{Color, Colorize};
 pub(crate) fn preview_plan(plan: &SplitHunkPlan) {
     let mut global_index = 0;
     for file in plan.files() {
-        println!("\n{}", file.path().cyan().bold());
+        match file.summary() {
+            Some(summary) => println!(
+                "\n{} {}",
+                file.path().cyan().bold(),
+                format!("({summary})").magenta()
+            ),
+            None => println!("\n{}", file.path().cyan().bold()),
+        }
         for hunk in file.hunks() {
-            let (label, color) = categorize_hunk(hunk);
-            println!(
-                "\n  {} {} (lines {}-{})",
-                format!("[{global_index}]").white().bold(),
-                label.color(color),
-                hunk.first_line(),
-                hunk.last_line()
-            );
-            for line in hunk.lines() {
-                let prefix = match line.kind {
-                    DiffLineKind::Context => " ".white(),
-                    DiffLineKind::Added => "+".green(),
-                    DiffLineKind::Removed => "-".red(),
-                };
-                let content = match line.kind {
-                    DiffLineKind::Context => line.content.white(),
-                    DiffLineKind::Added => line.content.green(),
-                    DiffLineKind::Removed => line.content.red(),
-                };
-                println!("    {}{}", prefix, content);
-            }
+            println!("\n  {}", hunk_header(global_index, hunk));
+            print_hunk_lines(hunk);
             global_index += 1;
         }
     }
 }
 
+/// `[index] kind (lines a-b)` summary line for a hunk
+pub(crate) fn hunk_header(index: usize, hunk: &DiffHunk) -> String {
+    let (label, color) = categorize_hunk(hunk);
+    format!(
+        "{} {} (lines {}-{})",
+        format!("[{index}]").white().bold(),
+        label.color(color),
+        hunk.first_line(),
+        hunk.last_line()
+    )
+}
+
+pub(crate) fn print_hunk_lines(hunk: &DiffHunk) {
+    for (line, position) in hunk.lines().iter().zip(hunk.line_positions()) {
+        let prefix = match line.kind {
+            DiffLineKind::Context => " ".white(),
+            DiffLineKind::Added => "+".green(),
+            DiffLineKind::Removed => "-".red(),
+        };
+        let content = match line.kind {
+            DiffLineKind::Context => line.content.white(),
+            DiffLineKind::Added => line.content.green(),
+            DiffLineKind::Removed => line.content.red(),
+        };
+        println!(
+            "    {} {}{}",
+            format!("{position:>4}").bright_black(),
+            prefix,
+            content
+        );
+    }
+}
+
 fn categorize_hunk(hunk: &DiffHunk) -> (&'static str, Color) {
     let has_added = hunk.lines().iter().any(|line| line.kind.is_added());
     let has_removed = hunk.lines().iter().any(|line| line.kind.is_removed());
@@ -41,6 +62,7 @@ fn categorize_hunk(hunk: &DiffHunk) -> (&'static str, Color) {
         (true, true) => ("modified", Color::Yellow),
         (true, false) => ("added", Color::Green),
         (false, true) => ("removed", Color::Red),
+        (false, false) if hunk.lines().is_empty() => ("whole file", Color::Magenta),
         (false, false) => ("context", Color::White),
     }
 }
@@ -1,13 +1,20 @@
-use super::diff::FileDiff;
-use ahash::HashMap;
+use crate::repo::{JjRepo, SplitLayer};
 use duct::cmd;
 use eyre::{Context as _, Result};
+use jj_lib::commit::Commit;
 
-#[derive(Debug, Clone, Copy)]
-pub(crate) struct SplitHunkRepo;
+pub(crate) struct SplitHunkRepo {
+    repo: JjRepo,
+}
 
 impl SplitHunkRepo {
-    pub(crate) fn load_diff(&self, revision: &str) -> Result<String> {
+    pub(crate) fn load() -> Result<Self> {
+        Ok(Self {
+            repo: JjRepo::load(None)?,
+        })
+    }
+
+    pub(crate) fn load_diff(revision: &str) -> Result<String> {
         cmd!("jj", "diff", "-r", revision, "--git")
             .stdout_capture()
             .stderr_capture()
@@ -15,93 +22,15 @@ impl SplitHunkRepo {
             .wrap_err("failed to get diff")
     }
 
-    pub(crate) fn read_file_lines_or_empty(&self, revision: &str, path: &str) -> Vec<String> {
-        cmd!("jj", "file", "show", "-r", revision, path)
-            .stdout_capture()
-            .stderr_capture()
-            .read()
-            .unwrap_or_default()
-            .lines()
-            .map(ToOwned::to_owned)
-            .collect()
+    pub(crate) fn resolve(&self, revision: &str) -> Result<Commit> {
+        self.repo.eval_revset_single(revision)
     }
 
-    pub(crate) fn collect_original_contents(
-        &self,
-        files: &[FileDiff],
-        revision: &str,
-    ) -> HashMap<String, String> {
-        files.iter().fold(HashMap::default(), |mut contents, file| {
-            let content = cmd!("jj", "file", "show", "-r", revision, file.path())
-                .stdout_capture()
-                .stderr_capture()
-                .read()
-                .unwrap_or_default();
-            contents.insert(file.path().to_string(), content);
-            contents
-        })
+    pub(crate) fn jj_repo(&self) -> &JjRepo {
+        &self.repo
     }
 
-    pub(crate) fn execute_split(
-        &self,
-        revision: &str,
-        message: &str,
-        new_contents: &HashMap<String, String>,
-        original_contents: &HashMap<String, String>,
-    ) -> Result<()> {
-        let parent_revision = format!("{revision}-");
-        cmd!("jj", "new", &parent_revision)
-            .stdout_null()
-            .stderr_null()
-            .run()
-            .wrap_err("failed to create new commit")?;
-
-        for (path, content) in new_contents {
-            std::fs::write(path, content).wrap_err_with(|| format!("failed to write {path}"))?;
-        }
-
-        cmd!("jj", "describe", "-m", message)
-            .stdout_null()
-            .stderr_null()
-            .run()
-            .wrap_err("failed to set commit message")?;
-
-        let split_change_id = cmd!(
-            "jj",
-            "log",
-            "-r",
-            "@",
-            "--no-graph",
-            "-T",
-            "change_id.short()"
-        )
-        .stdout_capture()
-        .stderr_capture()
-        .read()
-        .wrap_err("failed to get split commit change id")?;
-
-        cmd!("jj", "rebase", "-s", revision, "-d", "@")
-            .stdout_null()
-            .stderr_null()
-            .run()
-            .wrap_err("failed to rebase original revision")?;
-
-        cmd!("jj", "edit", revision)
-            .stdout_null()
-            .stderr_null()
-            .run()
-            .wrap_err("failed to edit original revision")?;
-
-        for (path, content) in original_contents {
-            std::fs::write(path, content).wrap_err_with(|| format!("failed to restore {path}"))?;
-        }
-
-        cmd!("jj", "edit", split_change_id.trim())
-            .stdout_null()
-            .stderr_null()
-            .run()
-            .wrap_err("failed to return to split commit")?;
-
-        Ok(())
+    pub(crate) fn execute_split(&mut self, commit: &Commit, layers: &[SplitLayer]) -> Result<()> {
+        self.repo.split_commit(commit, layers)
     }
 }
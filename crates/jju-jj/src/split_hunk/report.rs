@@ -0,0 +1,294 @@
+use super::application::SplitHunkApplication;
+use super::diff::{DiffHunk, DiffLineKind, FileDiff, FileStatus};
+use super::groups::StackedPlan;
+use super::plan::SplitHunkPlan;
+use super::selection::SelectedHunk;
+use crate::repo::FileContents;
+use eyre::{Result, WrapErr};
+use serde::Serialize;
+
+/// `split-hunk --format json` output for `--preview`
+#[derive(Debug, Serialize)]
+pub(crate) struct PreviewReport {
+    revision: String,
+    selected_count: usize,
+    files: Vec<FileReport>,
+}
+
+/// `split-hunk --format json` output for `--dry-run`
+#[derive(Debug, Serialize)]
+pub(crate) struct DryRunReport {
+    revision: String,
+    commits: Vec<CommitReport>,
+}
+
+/// One commit a dry run would create, with the full contents of the files it changes
+#[derive(Debug, Serialize)]
+struct CommitReport {
+    message: String,
+    selected_count: usize,
+    files: Vec<FileReport>,
+    results: Vec<ResultFile>,
+}
+
+#[derive(Debug, Serialize)]
+struct FileReport {
+    path: String,
+    old_path: Option<String>,
+    status: &'static str,
+    binary: bool,
+    old_mode: Option<String>,
+    new_mode: Option<String>,
+    hunks: Vec<HunkReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct HunkReport {
+    /// Index accepted by `--hunks`, counted across all files
+    index: usize,
+    header: String,
+    old_start: usize,
+    old_count: usize,
+    new_start: usize,
+    new_count: usize,
+    selected: bool,
+    /// Indices into `lines` kept from a partially selected hunk
+    selected_lines: Option<Vec<usize>>,
+    lines: Vec<LineReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct LineReport {
+    kind: &'static str,
+    content: String,
+    old_line: Option<usize>,
+    new_line: Option<usize>,
+    missing_newline: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ResultFile {
+    path: String,
+    /// `false` when the commit removes the file
+    exists: bool,
+    executable: bool,
+    /// File text, `None` when it isn't valid UTF-8
+    contents: Option<String>,
+}
+
+impl PreviewReport {
+    pub(crate) fn new(revision: &str, plan: &SplitHunkPlan) -> Self {
+        Self {
+            revision: revision.to_string(),
+            selected_count: plan.selected_count(),
+            files: file_reports(plan),
+        }
+    }
+}
+
+impl DryRunReport {
+    pub(crate) fn empty(revision: &str) -> Self {
+        Self {
+            revision: revision.to_string(),
+            commits: Vec::new(),
+        }
+    }
+
+    pub(crate) fn single(
+        revision: &str,
+        message: &str,
+        plan: &SplitHunkPlan,
+        application: &SplitHunkApplication,
+    ) -> Self {
+        Self {
+            revision: revision.to_string(),
+            commits: vec![CommitReport::new(message, plan, application)],
+        }
+    }
+
+    pub(crate) fn stacked(
+        revision: &str,
+        plans: &[StackedPlan],
+        applications: &[SplitHunkApplication],
+    ) -> Self {
+        Self {
+            revision: revision.to_string(),
+            commits: plans
+                .iter()
+                .zip(applications)
+                .map(|(plan, application)| CommitReport::new(&plan.message, &plan.own, application))
+                .collect(),
+        }
+    }
+}
+
+impl CommitReport {
+    fn new(message: &str, plan: &SplitHunkPlan, application: &SplitHunkApplication) -> Self {
+        let mut results = application
+            .new_contents
+            .iter()
+            .map(|(path, contents)| ResultFile::new(path, contents.as_ref()))
+            .collect::<Vec<_>>();
+        results.sort_by(|left, right| left.path.cmp(&right.path));
+
+        Self {
+            message: message.to_string(),
+            selected_count: plan.selected_count(),
+            files: file_reports(plan)
+                .into_iter()
+                .filter(|file| file.hunks.iter().any(|hunk| hunk.selected))
+                .collect(),
+            results,
+        }
+    }
+}
+
+impl ResultFile {
+    fn new(path: &str, contents: Option<&FileContents>) -> Self {
+        Self {
+            path: path.to_string(),
+            exists: contents.is_some(),
+            executable: contents.is_some_and(|contents| contents.executable),
+            contents: contents.and_then(|contents| String::from_utf8(contents.text.clone()).ok()),
+        }
+    }
+}
+
+/// Print a report as pretty JSON on stdout
+pub(crate) fn print_json(report: &impl Serialize) -> Result<()> {
+    let json = serde_json::to_string_pretty(report).wrap_err("failed to serialize report")?;
+    println!("{json}");
+    Ok(())
+}
+
+fn file_reports(plan: &SplitHunkPlan) -> Vec<FileReport> {
+    let mut global_index = 0;
+    plan.files()
+        .iter()
+        .enumerate()
+        .map(|(file_index, file)| {
+            let hunks = file
+                .hunks()
+                .iter()
+                .enumerate()
+                .map(|(hunk_index, hunk)| {
+                    let selected = SelectedHunk {
+                        file_index,
+                        hunk_index,
+                    };
+                    let report = HunkReport::new(global_index, hunk, plan, &selected);
+                    global_index += 1;
+                    report
+                })
+                .collect();
+            FileReport::new(file, hunks)
+        })
+        .collect()
+}
+
+impl FileReport {
+    fn new(file: &FileDiff, hunks: Vec<HunkReport>) -> Self {
+        let (old_mode, new_mode) = match file.mode_change() {
+            Some((old, new)) => (Some(old.to_string()), Some(new.to_string())),
+            None => (None, None),
+        };
+        Self {
+            path: file.path().to_string(),
+            old_path: file.old_path().map(ToOwned::to_owned),
+            status: status_name(file.status()),
+            binary: file.is_binary(),
+            old_mode,
+            new_mode,
+            hunks,
+        }
+    }
+}
+
+impl HunkReport {
+    fn new(index: usize, hunk: &DiffHunk, plan: &SplitHunkPlan, selected: &SelectedHunk) -> Self {
+        let lines = hunk
+            .lines()
+            .iter()
+            .zip(hunk.old_positions().into_iter().zip(hunk.line_positions()))
+            .enumerate()
+            .map(|(line_index, (line, (old_line, new_line)))| LineReport {
+                kind: match line.kind {
+                    DiffLineKind::Context => "context",
+                    DiffLineKind::Added => "added",
+                    DiffLineKind::Removed => "removed",
+                },
+                content: line.content.clone(),
+                old_line: (!line.kind.is_added()).then_some(old_line),
+                new_line: (!line.kind.is_removed()).then_some(new_line),
+                missing_newline: hunk.lacks_newline(line_index),
+            })
+            .collect();
+
+        Self {
+            index,
+            header: hunk.header(),
+            old_start: hunk.old_start(),
+            old_count: hunk.old_count(),
+            new_start: hunk.first_line(),
+            new_count: hunk.new_count(),
+            selected: plan.is_selected(selected),
+            selected_lines: plan
+                .selected_lines(selected)
+                .map(|lines| lines.iter().copied().collect()),
+            lines,
+        }
+    }
+}
+
+fn status_name(status: FileStatus) -> &'static str {
+    match status {
+        FileStatus::Modified => "modified",
+        FileStatus::Added => "added",
+        FileStatus::Deleted => "deleted",
+        FileStatus::Renamed => "renamed",
+        FileStatus::Copied => "copied",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PreviewReport;
+    use crate::split_hunk::diff::ParsedDiff;
+    use crate::split_hunk::plan::SplitHunkPlan;
+    use crate::split_hunk::selection::SelectedHunk;
+
+    #[test]
+    fn test_preview_report_marks_selected_hunks_and_line_numbers() {
+        let diff = ParsedDiff::parse(
+            r#"diff --git a/src/lib.rs b/src/lib.rs
+@@ -1,2 +1,2 @@
+ keep
+-old
++new
+@@ -9,1 +9,2 @@
+ tail
++added
+"#,
+        );
+        let plan = SplitHunkPlan::new(
+            diff,
+            vec![SelectedHunk {
+                file_index: 0,
+                hunk_index: 1,
+            }],
+        );
+
+        let report = serde_json::to_value(PreviewReport::new("@", &plan)).expect("json");
+
+        let hunks = &report["files"][0]["hunks"];
+        assert_eq!(report["selected_count"], 1);
+        assert_eq!(hunks[0]["selected"], false);
+        assert_eq!(hunks[1]["index"], 1);
+        assert_eq!(hunks[1]["selected"], true);
+        assert_eq!(hunks[1]["header"], "@@ -9,1 +9,2 @@");
+        assert_eq!(hunks[0]["lines"][1]["kind"], "removed");
+        assert_eq!(hunks[0]["lines"][1]["old_line"], 2);
+        assert!(hunks[0]["lines"][1]["new_line"].is_null());
+        assert_eq!(hunks[0]["lines"][2]["new_line"], 2);
+    }
+}
@@ -12,10 +12,10 @@ use regex::Regex;
 
 pub(crate) use parse::{parse_hunk_indices, parse_line_ranges};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) struct SelectedHunk {
-    pub(crate) file_index: usize,
-    pub(crate) hunk_index: usize,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SelectedHunk {
+    pub file_index: usize,
+    pub hunk_index: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -26,7 +26,8 @@ pub(crate) struct SplitHunkPlanner {
 
 impl SplitHunkPlanner {
     pub(crate) fn from_options(options: &SplitHunkOptions) -> Result<Self> {
-        let selection = SplitSelectionPlan {
+        Self::new(SplitSelectionPlan {
+            file_filter: options.file_filter.clone(),
             hunk_indices: options
                 .hunks
                 .as_deref()
@@ -39,7 +40,11 @@ impl SplitHunkPlanner {
                 .transpose()?,
             pattern: options.pattern.clone(),
             invert: options.invert,
-        };
+            line_precise: options.line_precise,
+        })
+    }
+
+    pub(crate) fn new(selection: SplitSelectionPlan) -> Result<Self> {
         let pattern = selection
             .pattern
             .as_deref()
@@ -51,9 +56,18 @@ impl SplitHunkPlanner {
     }
 
     pub(crate) fn build(self, diff: ParsedDiff) -> SplitHunkPlan {
-        SplitHunkPlan::new(
-            diff.clone(),
-            planner::build_selected_hunks(&diff, &self.selection, self.pattern.as_ref()),
-        )
+        if !self.selection.line_precise {
+            return SplitHunkPlan::new(
+                diff.clone(),
+                planner::build_selected_hunks(&diff, &self.selection, self.pattern.as_ref()),
+            );
+        }
+
+        planner::build_selected_lines(&diff, &self.selection, self.pattern.as_ref())
+            .into_iter()
+            .fold(
+                SplitHunkPlan::new(diff, Vec::new()),
+                |plan, (hunk, lines)| plan.with_selected_lines(hunk, lines),
+            )
     }
 }
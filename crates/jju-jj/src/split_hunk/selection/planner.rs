@@ -2,6 +2,7 @@ use super::super::diff::DiffHunk;
 use super::{ParsedDiff, SelectedHunk};
 use jju_core::split_hunk::{LineRange, SplitSelectionPlan};
 use regex::Regex;
+use std::collections::BTreeSet;
 
 pub(crate) fn build_selected_hunks(
     diff: &ParsedDiff,
@@ -12,6 +13,11 @@ pub(crate) fn build_selected_hunks(
     let mut global_index = 0;
 
     for (file_index, file) in diff.files().iter().enumerate() {
+        if !matches_file(selection, file.path()) {
+            global_index += file.hunks().len();
+            continue;
+        }
+
         for (hunk_index, hunk) in file.hunks().iter().enumerate() {
             if matches_selection(selection, pattern, hunk, global_index) {
                 selected.push(SelectedHunk {
@@ -27,6 +33,83 @@ pub(crate) fn build_selected_hunks(
     selected
 }
 
+/// Pick individual changed lines, keyed by the hunk they belong to
+///
+/// Hunk indices still select every changed line of a hunk, while line ranges and
+/// the pattern are matched against each added or removed line on its own
+pub(crate) fn build_selected_lines(
+    diff: &ParsedDiff,
+    selection: &SplitSelectionPlan,
+    pattern: Option<&Regex>,
+) -> Vec<(SelectedHunk, BTreeSet<usize>)> {
+    let mut selected = Vec::new();
+    let mut global_index = 0;
+
+    for (file_index, file) in diff.files().iter().enumerate() {
+        if !matches_file(selection, file.path()) {
+            global_index += file.hunks().len();
+            continue;
+        }
+
+        for (hunk_index, hunk) in file.hunks().iter().enumerate() {
+            let selected_hunk = SelectedHunk {
+                file_index,
+                hunk_index,
+            };
+            if file.is_whole_file() {
+                // whole-file changes have no lines to pick, only the hunk as a unit
+                if matches_selection(selection, pattern, hunk, global_index) {
+                    selected.push((selected_hunk, BTreeSet::new()));
+                }
+            } else {
+                let lines = matching_lines(selection, pattern, hunk, global_index);
+                if !lines.is_empty() {
+                    selected.push((selected_hunk, lines));
+                }
+            }
+
+            global_index += 1;
+        }
+    }
+
+    selected
+}
+
+fn matches_file(selection: &SplitSelectionPlan, path: &str) -> bool {
+    selection
+        .file_filter
+        .as_deref()
+        .is_none_or(|filter| path.contains(filter))
+}
+
+fn matching_lines(
+    selection: &SplitSelectionPlan,
+    pattern: Option<&Regex>,
+    hunk: &DiffHunk,
+    global_index: usize,
+) -> BTreeSet<usize> {
+    let whole_hunk = selection.matches_all()
+        || selection
+            .hunk_indices
+            .as_deref()
+            .is_some_and(|indices| indices.contains(&global_index));
+    let positions = hunk.line_positions();
+
+    hunk.changed_line_indices()
+        .filter(|&line_index| {
+            let matches = whole_hunk
+                || selection.line_ranges.as_deref().is_some_and(|ranges| {
+                    ranges.iter().any(|LineRange(start, end)| {
+                        (*start..=*end).contains(&positions[line_index])
+                    })
+                })
+                || pattern
+                    .is_some_and(|pattern| pattern.is_match(&hunk.lines()[line_index].content));
+            matches != selection.invert
+        })
+        .collect()
+}
+
 pub(crate) fn matches_selection(
     selection: &SplitSelectionPlan,
     pattern: Option<&Regex>,
@@ -1,6 +1,8 @@
-use super::{SplitHunkPlanner, parse_hunk_indices, parse_line_ranges};
-use crate::split_hunk::SplitHunkOptions;
+use super::{SelectedHunk, SplitHunkPlanner, parse_hunk_indices, parse_line_ranges};
+use crate::split_hunk::application::{apply_hunks_to_lines, byte_lines, join_lines};
 use crate::split_hunk::diff::ParsedDiff;
+use crate::split_hunk::{OutputFormat, SplitHunkOptions};
+use ahash::HashMap;
 
 fn options() -> SplitHunkOptions {
     SplitHunkOptions {
@@ -10,9 +12,13 @@ fn options() -> SplitHunkOptions {
         lines: None,
         hunks: None,
         pattern: None,
+        groups: Vec::new(),
+        by_file: false,
         preview: false,
         invert: false,
+        line_precise: false,
         dry_run: false,
+        format: OutputFormat::Text,
     }
 }
 
@@ -22,7 +28,7 @@ fn parsed_diff() -> ParsedDiff {
 @@ -1,1 +1,2 @@
  line one
 +selected
-@@ -10,1 +10,2 @@
+@@ -10,1 +11,2 @@
  line ten
 +other
 "#,
@@ -86,3 +92,88 @@ fn test_build_inverts_matches() {
     assert_eq!(plan.selected_count(), 1);
     assert_eq!(plan.selected_files()[0].selected_hunks, vec![1]);
 }
+
+#[test]
+fn test_build_line_precise_selects_changed_lines_in_range() {
+    let mut options = options();
+    options.lines = Some("2".to_string());
+    options.line_precise = true;
+
+    let plan = SplitHunkPlanner::from_options(&options)
+        .expect("planner")
+        .build(ParsedDiff::parse(
+            r#"diff --git a/src/lib.rs b/src/lib.rs
+@@ -1,3 +1,3 @@
+ line one
+-old two
+-old three
++new two
++new three
+"#,
+        ));
+
+    let hunk = SelectedHunk {
+        file_index: 0,
+        hunk_index: 0,
+    };
+    assert_eq!(plan.selected_count(), 1);
+    assert_eq!(
+        plan.selected_lines(&hunk)
+            .map(|lines| lines.iter().copied().collect::<Vec<_>>()),
+        Some(vec![1, 2, 3])
+    );
+}
+
+#[test]
+fn test_build_line_precise_inverts_per_line() {
+    let mut options = options();
+    options.pattern = Some("selected".to_string());
+    options.line_precise = true;
+    options.invert = true;
+
+    let plan = SplitHunkPlanner::from_options(&options)
+        .expect("planner")
+        .build(parsed_diff());
+
+    let hunk = SelectedHunk {
+        file_index: 0,
+        hunk_index: 1,
+    };
+    assert_eq!(plan.selected_count(), 1);
+    assert_eq!(
+        plan.selected_lines(&hunk)
+            .map(|lines| lines.iter().copied().collect::<Vec<_>>()),
+        Some(vec![1])
+    );
+
+    let mut parent = "line one\n".to_string();
+    parent.extend((2..10).map(|line| format!("line {line}\n")));
+    parent.push_str("line ten\n");
+    let new = parent.replacen("line one\n", "line one\nselected\n", 1) + "other\n";
+    let parent_lines = byte_lines(parent.as_bytes());
+    let new_lines = byte_lines(new.as_bytes());
+    let selection = &plan.selected_files()[0];
+    let first_commit = apply_hunks_to_lines(
+        &parent_lines,
+        &new_lines,
+        selection.file.hunks(),
+        &selection.selected_hunks,
+        &selection.selected_lines,
+    )
+    .expect("first commit");
+    let second_commit = apply_hunks_to_lines(
+        &parent_lines,
+        &new_lines,
+        selection.file.hunks(),
+        &[0, 1],
+        &HashMap::default(),
+    )
+    .expect("second commit");
+
+    // the selected lines land in the first commit, the rest in the one stacked on it
+    assert_eq!(
+        join_lines(&first_commit),
+        format!("{parent}other\n").as_bytes()
+    );
+    assert_eq!(join_lines(&second_commit), new.as_bytes());
+}
@@ -1,48 +1,77 @@
 use super::SplitHunkOptions;
 use super::application::SplitHunkApplication;
 use super::diff::ParsedDiff;
+use super::groups::{SplitGroup, StackedPlan, groups_by_file, stacked_plans};
 use super::plan::SplitHunkPlan;
 use super::preview::preview_plan;
 use super::repo::SplitHunkRepo;
+use super::report::{DryRunReport, PreviewReport, print_json};
 use super::selection::SplitHunkPlanner;
 use colored::Colorize;
 use eyre::Result;
 
 #[derive(Debug, Clone)]
 pub(super) struct SplitHunkWorkflow {
-    repo: SplitHunkRepo,
     options: SplitHunkOptions,
 }
 
 impl SplitHunkWorkflow {
-    pub(super) fn new(options: SplitHunkOptions, repo: SplitHunkRepo) -> Self {
-        Self { repo, options }
+    pub(super) fn new(options: SplitHunkOptions) -> Self {
+        Self { options }
     }
 
     pub(super) fn run(self) -> Result<()> {
+        self.options.validate_format()?;
         let diff = self.load_diff()?;
         if diff.is_empty() {
-            return Ok(());
+            return self.report_empty();
+        }
+
+        if self.options.is_multi_way() && !self.options.preview {
+            return self.run_groups(&diff);
         }
 
         let plan = self.build_plan(diff)?;
         if self.options.preview {
+            if self.options.is_json() {
+                return print_json(&PreviewReport::new(&self.options.revision, &plan));
+            }
             preview_plan(&plan);
             return Ok(());
         }
 
         if !plan.has_selection() {
+            if self.options.is_json() {
+                return print_json(&DryRunReport::empty(&self.options.revision));
+            }
             println!("{}", "No hunks matched selection criteria".yellow());
             return Ok(());
         }
 
+        if self.options.is_json() {
+            // json output is only allowed with --dry-run past this point
+            let repo = SplitHunkRepo::load()?;
+            let commit = repo.resolve(&self.options.revision)?;
+            let application = SplitHunkApplication::build(&plan, repo.jj_repo(), &commit)?;
+            let message = self.options.message.as_deref().unwrap_or_default();
+            return print_json(&DryRunReport::single(
+                &self.options.revision,
+                message,
+                &plan,
+                &application,
+            ));
+        }
+
         println!(
             "{} {} hunks",
             "Selected".green(),
             plan.selected_count().to_string().cyan()
         );
 
-        let application = SplitHunkApplication::build(&plan, &self.repo, &self.options.revision)?;
+        // loaded after the diff so it sees the snapshot `jj diff` took of the working copy
+        let mut repo = SplitHunkRepo::load()?;
+        let commit = repo.resolve(&self.options.revision)?;
+        let application = SplitHunkApplication::build(&plan, repo.jj_repo(), &commit)?;
         if self.options.dry_run {
             println!("\n{}", "Dry run - would commit:".yellow());
             for path in application.new_contents.keys() {
@@ -52,34 +81,125 @@ impl SplitHunkWorkflow {
         }
 
         let message = self.options.commit_message()?;
-        self.repo.execute_split(
-            &self.options.revision,
-            message,
-            &application.new_contents,
-            &application.original_contents,
-        )?;
+        repo.execute_split(&commit, &[application.into_layer(message)])?;
 
         println!("{} {}", "Created split commit:".green(), message.cyan());
         Ok(())
     }
 
+    fn run_groups(&self, diff: &ParsedDiff) -> Result<()> {
+        let groups = self.split_groups(diff)?;
+        if groups.is_empty() {
+            if self.options.is_json() {
+                return print_json(&DryRunReport::empty(&self.options.revision));
+            }
+            println!("{}", "Only one file changed, nothing to split".yellow());
+            return Ok(());
+        }
+
+        let plans = stacked_plans(diff, &groups)?;
+        let mut repo = SplitHunkRepo::load()?;
+        let commit = repo.resolve(&self.options.revision)?;
+        let applications = plans
+            .iter()
+            .map(|plan| SplitHunkApplication::build(&plan.stacked, repo.jj_repo(), &commit))
+            .collect::<Result<Vec<_>>>()?;
+
+        if self.options.dry_run {
+            if self.options.is_json() {
+                return print_json(&DryRunReport::stacked(
+                    &self.options.revision,
+                    &plans,
+                    &applications,
+                ));
+            }
+            print_group_dry_run(&plans);
+            return Ok(());
+        }
+
+        let layers = applications
+            .into_iter()
+            .zip(&plans)
+            .map(|(application, plan)| application.into_layer(&plan.message))
+            .collect::<Vec<_>>();
+
+        repo.execute_split(&commit, &layers)?;
+        for plan in &plans {
+            println!(
+                "{} {}",
+                "Created split commit:".green(),
+                plan.message.cyan()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn split_groups(&self, diff: &ParsedDiff) -> Result<Vec<SplitGroup>> {
+        if self.options.by_file {
+            return Ok(groups_by_file(
+                diff,
+                self.options.message.as_deref(),
+                &self.options,
+            ));
+        }
+
+        self.options
+            .groups
+            .iter()
+            .map(|spec| SplitGroup::parse(spec, &self.options))
+            .collect()
+    }
+
     fn load_diff(&self) -> Result<ParsedDiff> {
-        let diff_output = self.repo.load_diff(&self.options.revision)?;
+        let diff_output = SplitHunkRepo::load_diff(&self.options.revision)?;
         if diff_output.is_empty() {
-            println!("{}", "No changes in revision".yellow());
+            if !self.options.is_json() {
+                println!("{}", "No changes in revision".yellow());
+            }
             return Ok(ParsedDiff::empty());
         }
 
         let diff =
             ParsedDiff::parse(&diff_output).filter_by_path(self.options.file_filter.as_deref());
-        if diff.is_empty() {
+        if diff.is_empty() && !self.options.is_json() {
             println!("{}", "No matching files found".yellow());
         }
 
         Ok(diff)
     }
 
+    /// Nothing to split: json callers still get a well-formed, empty report
+    fn report_empty(&self) -> Result<()> {
+        if !self.options.is_json() {
+            return Ok(());
+        }
+        if self.options.preview {
+            let plan = SplitHunkPlan::new(ParsedDiff::empty(), Vec::new());
+            return print_json(&PreviewReport::new(&self.options.revision, &plan));
+        }
+        print_json(&DryRunReport::empty(&self.options.revision))
+    }
+
     fn build_plan(&self, diff: ParsedDiff) -> Result<SplitHunkPlan> {
         Ok(SplitHunkPlanner::from_options(&self.options)?.build(diff))
     }
 }
+
+fn print_group_dry_run(plans: &[StackedPlan]) {
+    println!(
+        "\n{}",
+        format!("Dry run - would create {} stacked commits:", plans.len()).yellow()
+    );
+    for (index, plan) in plans.iter().enumerate() {
+        println!(
+            "  {} {} ({} hunks)",
+            format!("{}.", index + 1).white().bold(),
+            plan.message.cyan(),
+            plan.own.selected_count()
+        );
+        for selection in plan.own.selected_files() {
+            println!("     {}", selection.file.path());
+        }
+    }
+}
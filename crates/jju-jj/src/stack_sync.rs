@@ -1,29 +1,49 @@
+mod cleanup;
 mod confirm;
 mod discover;
 mod execute;
 
 use colored::Colorize;
 use eyre::Result;
+use jju_core::stack_sync::{ConflictRollback, StackSyncScope};
 
+pub use cleanup::{abandon_landed_stack, cleanup_deleted_bookmarks};
 pub use discover::{
-    cleanup_deleted_bookmarks, detect_trunk_branch, discover_plan, find_stack_roots,
+    detect_trunk_branch, discover_plan, find_all_stack_roots, find_stack_roots,
     get_commit_description, sync_trunk_bookmark,
 };
-pub use execute::{execute_plan, rebase_root_onto_trunk};
+pub use execute::{execute_plan, first_stack_bookmark, rebase_root_onto_trunk, sync_root};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Default)]
+pub struct StackSyncOptions {
+    /// Push the first bookmark of the current stack afterwards
+    pub push: bool,
+    /// Skip the confirmation prompts
+    pub force: bool,
+    pub scope: StackSyncScope,
+    pub rollback: ConflictRollback,
+    /// Remote to take trunk from, overriding `jju.remote`
+    pub remote: Option<String>,
+    /// Remote to push to, overriding `jju.push-remote`
+    pub push_remote: Option<String>,
+    /// Revision to rebase onto instead of trunk
+    pub onto: Option<String>,
+    /// Roots to sync instead of the ones `scope` finds
+    pub roots: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
 pub struct StackSyncCommand {
-    push: bool,
-    force: bool,
+    options: StackSyncOptions,
 }
 
 impl StackSyncCommand {
-    pub fn new(push: bool, force: bool) -> Self {
-        Self { push, force }
+    pub fn new(options: StackSyncOptions) -> Self {
+        Self { options }
     }
 
     pub fn run(self) -> Result<()> {
-        execute::run_command(self)
+        execute::run_command(self.options)
     }
 }
 
@@ -0,0 +1,28 @@
+use crate::ops::{BookmarkOps, RevisionOps};
+use crate::repo::JjRepo;
+use eyre::{Context as _, Result};
+
+/// Delete bookmarks that are gone locally but still tracked on a remote, returning
+/// their names, so the next push removes the merged branches
+pub fn cleanup_deleted_bookmarks() -> Result<Vec<String>> {
+    let deleted = JjRepo::load(None)?.deleted_bookmarks();
+    for bookmark in &deleted {
+        BookmarkOps
+            .delete(bookmark)
+            .wrap_err_with(|| format!("failed to delete bookmark {bookmark}"))?;
+    }
+    Ok(deleted)
+}
+
+/// Abandon the stack from `root` after it landed on trunk, returning the
+/// bookmarks `jj abandon` deleted with it
+pub fn abandon_landed_stack(root: &str) -> Result<Vec<String>> {
+    let stack = format!("{root}::");
+    let repo = JjRepo::load(None)?;
+    let bookmarks = repo.bookmarks_on(&repo.eval_revset(&stack)?);
+
+    RevisionOps
+        .abandon(&stack)
+        .wrap_err_with(|| format!("failed to abandon stack {root}"))?;
+    Ok(bookmarks)
+}
@@ -1,31 +1,46 @@
 use colored::Colorize;
 use eyre::Result;
-use jju_core::stack_sync::StackSyncPlan;
+use jju_core::stack_sync::{StackSyncPlan, StackSyncScope};
 use std::io::Write;
 
 pub(super) fn confirm_plan(plan: &StackSyncPlan) -> Result<bool> {
-    println!(
-        "Will rebase the following commits on top of {}:",
-        plan.trunk.cyan()
-    );
+    let what = match plan.scope {
+        StackSyncScope::Current => "the following commits".to_string(),
+        StackSyncScope::All => format!("all {} stacks", plan.roots.len()),
+    };
+    println!("Will rebase {what} on top of {}:", plan.onto.label.cyan());
 
     for root in &plan.roots {
-        println!(
-            "  {}  {}",
-            root.change_id.purple(),
-            root.description.dimmed()
-        );
+        for commit in &root.commits {
+            println!(
+                "  {}  {}",
+                commit.change_id.purple(),
+                commit.description.dimmed()
+            );
+        }
         println!(
             "  {}",
             format!(
                 "jj rebase --source (-s) {} --onto (-o) {} --skip-emptied",
-                root.change_id, plan.trunk
+                root.change_id, plan.onto.revision
             )
             .dimmed()
         );
     }
 
-    print!("Continue? [y/N] ");
+    ask("Continue? [y/N] ")
+}
+
+pub(super) fn confirm_abandon() -> Result<bool> {
+    ask("Abandon it and delete its bookmarks? [y/N] ")
+}
+
+pub(super) fn confirm_rollback() -> Result<bool> {
+    ask("Roll back the whole sync? [y/N] ")
+}
+
+fn ask(prompt: &str) -> Result<bool> {
+    print!("{prompt}");
     std::io::stdout().flush()?;
     let mut input = String::new();
     std::io::stdin().read_line(&mut input)?;
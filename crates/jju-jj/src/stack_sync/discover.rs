@@ -1,31 +1,60 @@
+use super::StackSyncOptions;
 use crate::ops::BookmarkOps;
-use colored::Colorize;
+use crate::repo::JjRepo;
 use duct::cmd;
-use eyre::{Context as _, Result};
-use jju_core::stack_sync::{StackRootPlan, StackSyncPlan};
+use eyre::{Context as _, Result, bail, eyre};
+use jj_lib::commit::Commit;
+use jj_lib::object_id::ObjectId;
+use jju_core::remote::Remotes;
+use jju_core::stack_sync::{
+    StackCommitPlan, StackRootPlan, StackSyncPlan, StackSyncScope, StackSyncTarget,
+};
 use log::debug;
 
-pub fn discover_plan(push_bookmark_after_sync: bool) -> Result<StackSyncPlan> {
-    let trunk = detect_trunk_branch()?;
-    let roots = find_stack_roots(&trunk)?
+/// Length of the change ids shown for stack commits, matching `change_id.short()`
+const CHANGE_ID_LEN: usize = 12;
+
+pub fn discover_plan(options: &StackSyncOptions, remotes: Remotes) -> Result<StackSyncPlan> {
+    let trunk = detect_trunk_branch(&remotes.trunk)?;
+    let repo = JjRepo::load(None)?;
+    let onto = match options.onto.as_deref() {
+        Some(onto) => resolve_onto(&repo, onto)?,
+        None => StackSyncTarget {
+            revision: trunk.clone(),
+            label: trunk.clone(),
+        },
+    };
+    let roots = if options.roots.is_empty() {
+        match options.scope {
+            StackSyncScope::Current => find_stack_roots(&onto.revision)?,
+            StackSyncScope::All => find_all_stack_roots(&onto.revision)?,
+        }
+    } else {
+        resolve_roots(&repo, &options.roots)?
+    };
+    // trunk as fetched, since its local bookmark is only synced after discovery
+    let landed_base = match options.onto {
+        Some(_) => onto.revision.clone(),
+        None => format!("present({trunk}@{}) | {trunk}", remotes.trunk),
+    };
+    let roots = roots
         .into_iter()
-        .map(|change_id| {
-            let description = get_commit_description(&change_id).unwrap_or_default();
-            StackRootPlan {
-                change_id,
-                description,
-            }
-        })
-        .collect();
+        .map(|change_id| root_plan(&repo, change_id, &landed_base))
+        .collect::<Result<_>>()?;
 
     Ok(StackSyncPlan {
         trunk,
+        onto,
+        remotes,
+        scope: options.scope,
         roots,
-        push_bookmark_after_sync,
+        push_bookmark_after_sync: options.push,
+        rollback: options.rollback,
     })
 }
 
-pub fn detect_trunk_branch() -> Result<String> {
+/// Name of the bookmark `trunk()` points at, preferring one on `remote`
+pub fn detect_trunk_branch(remote: &str) -> Result<String> {
     let output = cmd!(
         "jj",
         "log",
@@ -33,7 +62,7 @@ pub fn detect_trunk_branch() -> Result<String> {
         "trunk()",
         "--no-graph",
         "-T",
-        "local_bookmarks",
+        r#"local_bookmarks.map(|b| b.name()).join(" ") ++ "\n" ++ remote_bookmarks.map(|b| b.name() ++ "@" ++ b.remote()).join(" ")"#,
         "--limit",
         "1"
     )
@@ -42,20 +71,134 @@ pub fn detect_trunk_branch() -> Result<String> {
     .read()
     .wrap_err("failed to detect trunk branch")?;
 
-    Ok(output
+    let mut lines = output.lines();
+    let local = lines.next().unwrap_or_default();
+    let remote_refs = lines.next().unwrap_or_default();
+    pick_trunk_bookmark(local, remote_refs, remote).ok_or_else(|| {
+        eyre!(
+            "trunk() has no bookmark to sync with, set `trunk()` or `{}` in your jj config",
+            crate::ops::REMOTE_KEY
+        )
+    })
+}
+
+/// Choose trunk's bookmark from its space-separated local bookmarks and `name@remote` refs
+fn pick_trunk_bookmark(local: &str, remote_refs: &str, remote: &str) -> Option<String> {
+    let on_remote = remote_refs
         .split_whitespace()
-        .next()
-        .unwrap_or("master")
-        .to_string())
+        .filter_map(|symbol| symbol.rsplit_once('@'))
+        .filter(|(_, ref_remote)| *ref_remote == remote)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    let local = local.split_whitespace().collect::<Vec<_>>();
+
+    on_remote
+        .iter()
+        .find(|name| local.contains(name))
+        .or_else(|| on_remote.first())
+        .or_else(|| local.first())
+        .map(|name| name.to_string())
+}
+
+/// Resolve `--onto` to one commit up front, so every stack lands on the same base
+fn resolve_onto(repo: &JjRepo, onto: &str) -> Result<StackSyncTarget> {
+    let commit = repo
+        .eval_revset_single(onto)
+        .wrap_err_with(|| format!("invalid --onto revision `{onto}`"))?;
+    Ok(StackSyncTarget {
+        revision: commit.id().hex(),
+        label: format!("{onto} ({})", short_change_id(&commit)),
+    })
+}
+
+/// Resolve each `--root` to the change id of one mutable commit
+///
+/// A root inside another root's stack is rejected, since rebasing the outer
+/// stack already moves it and rebasing it again would split the stack
+fn resolve_roots(repo: &JjRepo, roots: &[String]) -> Result<Vec<String>> {
+    let mut commits: Vec<(&str, Commit)> = Vec::new();
+    for root in roots {
+        let commit = repo
+            .eval_revset_single(root)
+            .wrap_err_with(|| format!("invalid --root revision `{root}`"))?;
+        let hex = commit.id().hex();
+        if !repo
+            .eval_revset(&format!("{hex} & immutable()"))?
+            .is_empty()
+        {
+            bail!("root `{root}` is immutable");
+        }
+        if commits
+            .iter()
+            .any(|(_, existing)| existing.id() == commit.id())
+        {
+            continue;
+        }
+        commits.push((root, commit));
+    }
+
+    for (root, commit) in &commits {
+        for (other, ancestor) in &commits {
+            let nested = format!("{}:: & {}", ancestor.id().hex(), commit.id().hex());
+            if ancestor.id() != commit.id() && !repo.eval_revset(&nested)?.is_empty() {
+                bail!("root `{root}` is inside the stack of root `{other}`");
+            }
+        }
+    }
+
+    Ok(commits
+        .iter()
+        .map(|(_, commit)| short_change_id(commit))
+        .collect())
+}
+
+/// The root with every commit its rebase moves, and where it landed on `trunk`
+fn root_plan(repo: &JjRepo, change_id: String, trunk: &str) -> Result<StackRootPlan> {
+    let mut stack = repo.eval_revset(&format!("{change_id}::"))?;
+    stack.reverse();
+    let commits = stack
+        .iter()
+        .map(|commit| StackCommitPlan {
+            change_id: short_change_id(commit),
+            description: JjRepo::description_first_line(commit),
+        })
+        .collect::<Vec<_>>();
+    let description = commits
+        .first()
+        .map(|commit| commit.description.clone())
+        .unwrap_or_default();
+    let landed = repo
+        .landed_commit(&change_id, trunk)?
+        .map(|commit| short_change_id(&commit));
+
+    Ok(StackRootPlan {
+        change_id,
+        description,
+        commits,
+        landed,
+    })
+}
+
+fn short_change_id(commit: &Commit) -> String {
+    let change_id = commit.change_id().reverse_hex();
+    change_id[..change_id.len().min(CHANGE_ID_LEN)].to_string()
+}
+
+pub fn find_stack_roots(onto: &str) -> Result<Vec<String>> {
+    find_roots(&format!("roots({onto}..@)"))
+}
+
+/// Roots of every stack of mutable commits that isn't already on `onto`
+pub fn find_all_stack_roots(onto: &str) -> Result<Vec<String>> {
+    find_roots(&format!("roots(({onto}..heads(mutable())) & mutable())"))
 }
 
-pub fn find_stack_roots(trunk: &str) -> Result<Vec<String>> {
-    let roots_revset = format!("roots({trunk}..@)");
+fn find_roots(roots_revset: &str) -> Result<Vec<String>> {
     let roots_output = cmd!(
         "jj",
         "log",
         "-r",
-        &roots_revset,
+        roots_revset,
         "--no-graph",
         "-T",
         r#"change_id.short() ++ "\n""#
@@ -91,31 +234,86 @@ pub fn get_commit_description(rev: &str) -> Result<String> {
     .wrap_err_with(|| format!("failed to load description for {rev}"))
 }
 
-pub fn sync_trunk_bookmark(trunk: &str) -> Result<()> {
+pub fn sync_trunk_bookmark(trunk: &str, remote: &str) -> Result<()> {
     BookmarkOps
-        .set(trunk, &format!("{trunk}@origin"))
+        .set(trunk, &format!("{trunk}@{remote}"))
         .wrap_err("failed to sync trunk bookmark")
 }
 
-pub fn cleanup_deleted_bookmarks() -> Result<Vec<String>> {
-    let tracked = cmd!("jj", "bookmark", "list", "--tracked")
-        .stdout_capture()
-        .stderr_capture()
-        .read()
-        .wrap_err("failed to list tracked bookmarks")?;
-
-    let mut deleted = Vec::new();
-    for line in tracked.lines() {
-        if line.contains("[deleted]")
-            && let Some(bookmark) = line.split_whitespace().next()
-        {
-            println!("{}{}", "Deleting merged bookmark: ".dimmed(), bookmark);
-            BookmarkOps
-                .delete(bookmark)
-                .wrap_err_with(|| format!("failed to delete bookmark {bookmark}"))?;
-            deleted.push(bookmark.to_string());
-        }
+#[cfg(test)]
+mod tests {
+    use super::{pick_trunk_bookmark, resolve_roots, root_plan, short_change_id};
+    use crate::repo::test_support::{TempRepo, write_commit};
+    use jj_lib::repo::Repo;
+
+    #[test]
+    fn test_root_plan_lists_the_stack_root_first() {
+        let temp = TempRepo::init("stack-sync-plan");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, first) = write_commit(&temp.repo, &root_commit, "first", &[]);
+        let (_, second) = write_commit(&repo, &first, "second", &[]);
+
+        let jj_repo = temp.load();
+        let plan = root_plan(&jj_repo, short_change_id(&first), "root()").expect("plan");
+        let commits = plan
+            .commits
+            .iter()
+            .map(|commit| (commit.change_id.clone(), commit.description.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commits,
+            vec![
+                (short_change_id(&first), "first"),
+                (short_change_id(&second), "second"),
+            ]
+        );
+        assert_eq!(plan.description, "first");
+        assert_eq!(plan.landed, None);
     }
 
-    Ok(deleted)
+    #[test]
+    fn test_resolve_roots_rejects_a_root_inside_another_stack() {
+        let temp = TempRepo::init("stack-sync-roots");
+        let root_commit = temp.repo.store().root_commit();
+        let (repo, first) = write_commit(&temp.repo, &root_commit, "first", &[]);
+        let (repo, second) = write_commit(&repo, &first, "second", &[]);
+        let (_, other) = write_commit(&repo, &root_commit, "other", &[]);
+
+        let jj_repo = temp.load();
+        let first_id = short_change_id(&first);
+        let other_id = short_change_id(&other);
+        assert_eq!(
+            resolve_roots(
+                &jj_repo,
+                &[first_id.clone(), other_id.clone(), first_id.clone()]
+            )
+            .expect("roots"),
+            vec![first_id.clone(), other_id]
+        );
+
+        let error = resolve_roots(&jj_repo, &[first_id, short_change_id(&second)])
+            .expect_err("nested root");
+        assert!(error.to_string().contains("inside the stack"));
+    }
+
+    #[test]
+    fn test_pick_trunk_bookmark_prefers_the_trunk_remote() {
+        assert_eq!(
+            pick_trunk_bookmark("main", "main@origin develop@upstream", "upstream").as_deref(),
+            Some("develop")
+        );
+        assert_eq!(
+            pick_trunk_bookmark("main trunk", "trunk@origin", "origin").as_deref(),
+            Some("trunk")
+        );
+    }
+
+    #[test]
+    fn test_pick_trunk_bookmark_has_no_hardcoded_fallback() {
+        assert_eq!(
+            pick_trunk_bookmark("main", "", "upstream").as_deref(),
+            Some("main")
+        );
+        assert_eq!(pick_trunk_bookmark("", "", "origin"), None);
+    }
 }
@@ -1,56 +1,215 @@
-use super::{discover_plan, print_aborted, print_complete, should_continue};
-use crate::ops::GitOps;
+use super::{
+    StackSyncOptions, confirm, discover_plan, print_aborted, print_complete, should_continue,
+};
+use crate::ops::{ConfigOps, ConflictOps, GitOps, OperationOps, read_with_stderr, run_with_stderr};
 use colored::Colorize;
 use duct::cmd;
-use eyre::{Context as _, Result};
-use jju_core::stack_sync::StackSyncPlan;
+use eyre::{Context as _, Result, bail};
+use jju_core::stack_sync::{
+    ConflictRollback, StackRootPlan, StackRootResult, StackSyncPlan, StackSyncScope,
+};
 
-pub(super) fn run_command(command: super::StackSyncCommand) -> Result<()> {
-    println!("{}", "Fetching from remote...".dimmed());
-    GitOps.fetch().wrap_err("failed to fetch")?;
+pub(super) fn run_command(options: StackSyncOptions) -> Result<()> {
+    // the point a whole-sync rollback returns to
+    let start_op = OperationOps
+        .current_op_id()
+        .wrap_err("failed to record the operation before syncing")?;
 
-    let plan = discover_plan(command.push)?;
+    let remotes = ConfigOps.remotes(options.remote.as_deref(), options.push_remote.as_deref())?;
+    println!(
+        "{}{}...",
+        "Fetching from ".dimmed(),
+        remotes.to_fetch().join(", ")
+    );
+    GitOps.fetch(&remotes).wrap_err("failed to fetch")?;
+
+    let plan = discover_plan(&options, remotes)?;
     println!("{}{}", "Syncing ".dimmed(), plan.trunk);
-    super::sync_trunk_bookmark(&plan.trunk)?;
+    super::sync_trunk_bookmark(&plan.trunk, &plan.remotes.trunk)?;
 
     if plan.is_empty() {
         println!(
             "{}{}{}",
             "No commits after ".dimmed(),
-            plan.trunk,
+            plan.onto.label,
             ", nothing to rebase".dimmed()
         );
         return Ok(());
     }
 
-    if !should_continue(&plan, command.force)? {
+    let plan = abandon_landed_stacks(plan, options.force)?;
+    if plan.is_empty() {
+        cleanup_bookmarks()?;
+        print_complete();
+        return Ok(());
+    }
+
+    if !should_continue(&plan, options.force)? {
         print_aborted();
         return Ok(());
     }
 
-    execute_plan(&plan)?;
-    let _ = super::cleanup_deleted_bookmarks()?;
+    let results = execute_plan(&plan);
+    let failed = results.iter().filter(|result| !result.is_success()).count();
+    if plan.scope == StackSyncScope::All || failed > 0 {
+        print_results(&results);
+    }
+
+    if results.iter().any(StackRootResult::has_conflicts) {
+        print_conflicts(&results);
+        if should_roll_back_all(plan.rollback, options.force)? {
+            OperationOps
+                .restore(&start_op)
+                .wrap_err("failed to roll back the sync")?;
+            println!("{}", "Rolled back the sync, nothing was changed".yellow());
+            return Ok(());
+        }
+    }
+
+    cleanup_bookmarks()?;
+    if failed > 0 {
+        bail!("failed to sync {failed} of {} stacks", results.len());
+    }
 
     if plan.push_bookmark_after_sync {
-        push_first_bookmark(&plan.trunk)?;
+        push_first_bookmark(&plan.onto.revision, &plan.remotes.push)?;
     }
 
     print_complete();
     Ok(())
 }
 
-pub fn execute_plan(plan: &StackSyncPlan) -> Result<()> {
-    for root in &plan.roots {
-        println!("{}{}...", "Rebasing stack from ".dimmed(), root.change_id);
-        rebase_root_onto_trunk(&root.change_id, &plan.trunk)
-            .wrap_err_with(|| format!("failed to rebase from {}", root.change_id))?;
+/// Offer to abandon each stack that already landed on trunk, leaving the rest to rebase
+fn abandon_landed_stacks(mut plan: StackSyncPlan, force: bool) -> Result<StackSyncPlan> {
+    let mut remaining = Vec::new();
+    for root in std::mem::take(&mut plan.roots) {
+        let Some(landed) = &root.landed else {
+            remaining.push(root);
+            continue;
+        };
+
+        println!(
+            "Stack from {} already landed on {} as {}",
+            root.change_id.purple(),
+            plan.trunk.cyan(),
+            landed.purple()
+        );
+        if !force && !confirm::confirm_abandon()? {
+            remaining.push(root);
+            continue;
+        }
+
+        let bookmarks = super::abandon_landed_stack(&root.change_id)?;
+        println!("{}{}", "Abandoned stack from ".dimmed(), root.change_id);
+        for bookmark in bookmarks {
+            println!("{}{}", "Deleted merged bookmark: ".dimmed(), bookmark);
+        }
     }
 
+    plan.roots = remaining;
+    Ok(plan)
+}
+
+fn cleanup_bookmarks() -> Result<()> {
+    for bookmark in super::cleanup_deleted_bookmarks()? {
+        println!("{}{}", "Deleting merged bookmark: ".dimmed(), bookmark);
+    }
     Ok(())
 }
 
+/// Rebase every root of `plan`, carrying on past stacks that fail
+pub fn execute_plan(plan: &StackSyncPlan) -> Vec<StackRootResult> {
+    plan.roots
+        .iter()
+        .map(|root| {
+            println!("{}{}...", "Rebasing stack from ".dimmed(), root.change_id);
+            sync_root(root, &plan.onto.revision, plan.rollback)
+        })
+        .collect()
+}
+
+/// Rebase one stack onto `onto` and find the commits it newly left conflicted
+///
+/// With `ConflictRollback::Stack` a conflicted stack is restored right away to
+/// the operation before its rebase, which undoes only this stack
+pub fn sync_root(root: &StackRootPlan, onto: &str, rollback: ConflictRollback) -> StackRootResult {
+    rebase_and_check(root, onto, rollback).unwrap_or_else(|error| StackRootResult {
+        root: root.clone(),
+        error: Some(format!("{error:#}")),
+        conflicts: Vec::new(),
+        rolled_back: false,
+        emptied: false,
+    })
+}
+
+fn rebase_and_check(
+    root: &StackRootPlan,
+    onto: &str,
+    rollback: ConflictRollback,
+) -> Result<StackRootResult> {
+    // `--skip-emptied` can abandon the root, so look the stack up by the change
+    // ids it has now rather than through the root
+    let stack = change_ids(&format!("{}::", root.change_id))?
+        .iter()
+        .map(|change_id| format!("present({change_id})"))
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let already_conflicted = ConflictOps.conflicted_commits(&stack)?;
+    let before_op = OperationOps.current_op_id()?;
+
+    rebase_root_onto_trunk(&root.change_id, onto)?;
+
+    let emptied = change_ids(&format!("present({})", root.change_id))?.is_empty();
+    let conflicts = ConflictOps
+        .conflicted_commits(&stack)?
+        .into_iter()
+        .filter(|commit| {
+            !already_conflicted
+                .iter()
+                .any(|existing| existing.change_id == commit.change_id)
+        })
+        .collect::<Vec<_>>();
+
+    let mut result = StackRootResult {
+        root: root.clone(),
+        error: None,
+        conflicts,
+        rolled_back: false,
+        emptied,
+    };
+    if result.conflicts.is_empty() || rollback != ConflictRollback::Stack {
+        return Ok(result);
+    }
+    OperationOps
+        .restore(&before_op)
+        .wrap_err_with(|| format!("failed to roll back stack {}", root.change_id))?;
+    result.rolled_back = true;
+    result.emptied = false;
+    Ok(result)
+}
+
+/// Full change ids of the commits in `revset`
+fn change_ids(revset: &str) -> Result<Vec<String>> {
+    let output = read_with_stderr(cmd!(
+        "jj",
+        "log",
+        "-r",
+        revset,
+        "-T",
+        r#"change_id ++ "\n""#,
+        "--no-graph"
+    ))
+    .wrap_err_with(|| format!("failed to resolve {revset}"))?;
+
+    Ok(output
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
 pub fn rebase_root_onto_trunk(root: &str, trunk: &str) -> Result<()> {
-    cmd!(
+    run_with_stderr(cmd!(
         "jj",
         "rebase",
         "--source",
@@ -58,17 +217,69 @@ pub fn rebase_root_onto_trunk(root: &str, trunk: &str) -> Result<()> {
         "--onto",
         trunk,
         "--skip-emptied"
-    )
-    .stdout_null()
-    .stderr_null()
-    .run()
-    .wrap_err_with(|| format!("failed to rebase from {root}"))?;
+    ))
+    .wrap_err_with(|| format!("failed to rebase from {root}"))
+}
+
+fn print_results(results: &[StackRootResult]) {
+    for result in results {
+        let status = match &result.error {
+            Some(error) => format!("failed: {error}").red(),
+            None if result.rolled_back => "conflicted, rolled back".yellow(),
+            None if result.has_conflicts() => "synced with conflicts".yellow(),
+            None if result.emptied => "synced, root emptied and abandoned".green(),
+            None => "synced".green(),
+        };
+        println!(
+            "  {}  {}  {}",
+            result.root.change_id.purple(),
+            result.root.description.dimmed(),
+            status
+        );
+    }
+}
+
+fn print_conflicts(results: &[StackRootResult]) {
+    println!("{}", "The sync left conflicts in:".red());
+    for commit in results
+        .iter()
+        .filter(|result| result.has_conflicts())
+        .flat_map(|result| &result.conflicts)
+    {
+        println!("  {}", commit.change_id.purple());
+        for file in &commit.files {
+            println!("    {}", file.dimmed());
+        }
+    }
+}
+
+/// Whether to put the whole sync back, asking unless the choice was made up front
+fn should_roll_back_all(rollback: ConflictRollback, force: bool) -> Result<bool> {
+    match rollback {
+        ConflictRollback::All => Ok(true),
+        ConflictRollback::Stack => Ok(false),
+        ConflictRollback::Keep if force => Ok(false),
+        ConflictRollback::Keep => confirm::confirm_rollback(),
+    }
+}
+
+fn push_first_bookmark(onto: &str, remote: &str) -> Result<()> {
+    if let Some(bookmark) = first_stack_bookmark(onto)? {
+        println!("{}{}...", "Pushing ".dimmed(), bookmark);
+        GitOps
+            .push_bookmark(&bookmark, remote)
+            .wrap_err("failed to push")?;
+    } else {
+        println!("{}", "No bookmarks found to push".dimmed());
+    }
+
     Ok(())
 }
 
-fn push_first_bookmark(trunk: &str) -> Result<()> {
-    let revset = format!("({trunk}..@) & bookmarks()");
-    let output = cmd!(
+/// The bookmark closest to `onto` on the stack containing the working copy
+pub fn first_stack_bookmark(onto: &str) -> Result<Option<String>> {
+    let revset = format!("({onto}..@) & bookmarks()");
+    let output = read_with_stderr(cmd!(
         "jj",
         "log",
         "-r",
@@ -79,19 +290,11 @@ fn push_first_bookmark(trunk: &str) -> Result<()> {
         r#"bookmarks ++ "\n""#,
         "--limit",
         "1"
-    )
-    .stdout_capture()
-    .stderr_capture()
-    .read()
+    ))
     .wrap_err("failed to get bookmarks")?;
 
-    if let Some(bookmark) = output.lines().find(|line| !line.is_empty()) {
-        let bookmark = bookmark.trim();
-        println!("{}{}...", "Pushing ".dimmed(), bookmark);
-        GitOps.push_bookmark(bookmark).wrap_err("failed to push")?;
-    } else {
-        println!("{}", "No bookmarks found to push".dimmed());
-    }
-
-    Ok(())
+    Ok(output
+        .lines()
+        .find(|line| !line.is_empty())
+        .map(|bookmark| bookmark.trim().to_string()))
 }
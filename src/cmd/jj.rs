@@ -1,5 +1,7 @@
+mod absorb;
 mod cli;
 mod dispatch;
+mod move_hunks;
 mod split_hunk;
 mod stack_sync;
 mod tree;
@@ -37,6 +39,17 @@ mod tests {
         assert!(flags.subcommand.is_none());
     }
 
+    #[test]
+    fn parses_global_repository_flag_after_subcommand() {
+        let flags = Jj::parse_from(["jju", "tree", "-R", "../other"]);
+
+        assert_eq!(
+            flags.repository.as_deref(),
+            Some(std::path::Path::new("../other"))
+        );
+        assert!(matches!(flags.subcommand, Some(JjCmd::Tree { .. })));
+    }
+
     #[test]
     fn parses_tree_subcommand_without_neighborhood() {
         let flags = Jj::parse_from(["jju", "tree", "--full"]);
@@ -47,4 +60,119 @@ mod tests {
             Some(JjCmd::Tree { full: true, .. })
         ));
     }
+
+    #[test]
+    fn parses_repeated_split_hunk_groups() {
+        let flags = Jj::parse_from([
+            "jju",
+            "split-hunk",
+            "--group",
+            "file=a.rs,message=a",
+            "--group",
+            "file=b.rs,message=b",
+        ]);
+
+        let Some(JjCmd::SplitHunk { groups, .. }) = flags.subcommand else {
+            panic!("expected split-hunk subcommand");
+        };
+        assert_eq!(groups, vec!["file=a.rs,message=a", "file=b.rs,message=b"]);
+    }
+
+    #[test]
+    fn parses_split_hunk_json_format() {
+        let flags = Jj::parse_from(["jju", "split-hunk", "--preview", "--format", "json"]);
+
+        let Some(JjCmd::SplitHunk { format, .. }) = flags.subcommand else {
+            panic!("expected split-hunk subcommand");
+        };
+        assert_eq!(format, jju_jj::split_hunk::OutputFormat::Json);
+    }
+
+    #[test]
+    fn parses_stack_sync_all_with_rollback() {
+        let flags = Jj::parse_from(["jju", "stack-sync", "--all", "--rollback", "stack"]);
+
+        let Some(JjCmd::StackSync { all, rollback, .. }) = flags.subcommand else {
+            panic!("expected stack-sync subcommand");
+        };
+        assert!(all);
+        assert_eq!(rollback, jju_core::stack_sync::ConflictRollback::Stack);
+    }
+
+    #[test]
+    fn parses_stack_sync_onto_with_roots() {
+        let flags = Jj::parse_from([
+            "jju",
+            "stack-sync",
+            "--onto",
+            "release",
+            "--root",
+            "abc",
+            "-r",
+            "def",
+        ]);
+
+        let Some(JjCmd::StackSync { onto, roots, .. }) = flags.subcommand else {
+            panic!("expected stack-sync subcommand");
+        };
+        assert_eq!(onto.as_deref(), Some("release"));
+        assert_eq!(roots, vec!["abc", "def"]);
+    }
+
+    #[test]
+    fn rejects_stack_sync_roots_with_all() {
+        assert!(Jj::try_parse_from(["jju", "stack-sync", "--all", "--root", "abc"]).is_err());
+    }
+
+    #[test]
+    fn parses_stack_sync_remotes() {
+        let flags = Jj::parse_from([
+            "jju",
+            "stack-sync",
+            "--remote",
+            "upstream",
+            "--push-remote",
+            "fork",
+        ]);
+
+        let Some(JjCmd::StackSync {
+            remote,
+            push_remote,
+            ..
+        }) = flags.subcommand
+        else {
+            panic!("expected stack-sync subcommand");
+        };
+        assert_eq!(remote.as_deref(), Some("upstream"));
+        assert_eq!(push_remote.as_deref(), Some("fork"));
+    }
+
+    #[test]
+    fn parses_absorb_dry_run_with_revision() {
+        let flags = Jj::parse_from(["jju", "absorb", "-r", "xyz", "--dry-run"]);
+
+        let Some(JjCmd::Absorb {
+            revision, dry_run, ..
+        }) = flags.subcommand
+        else {
+            panic!("expected absorb subcommand");
+        };
+        assert_eq!(revision, "xyz");
+        assert!(dry_run);
+    }
+
+    #[test]
+    fn parses_move_hunks_with_default_source() {
+        let flags = Jj::parse_from(["jju", "move-hunks", "--to", "xyz", "--hunks", "0,2"]);
+
+        let Some(JjCmd::MoveHunks {
+            from, to, hunks, ..
+        }) = flags.subcommand
+        else {
+            panic!("expected move-hunks subcommand");
+        };
+        assert_eq!(from, "@");
+        assert_eq!(to, "xyz");
+        assert_eq!(hunks.as_deref(), Some("0,2"));
+    }
 }
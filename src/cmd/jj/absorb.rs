@@ -0,0 +1,26 @@
+pub(crate) struct AbsorbOptions {
+    pub(crate) revision: String,
+    pub(crate) file_filter: Option<String>,
+    pub(crate) preview: bool,
+    pub(crate) dry_run: bool,
+}
+
+pub(crate) struct AbsorbCommand {
+    options: AbsorbOptions,
+}
+
+impl AbsorbCommand {
+    pub(crate) fn new(options: AbsorbOptions) -> Self {
+        Self { options }
+    }
+
+    pub(crate) fn run(self) -> eyre::Result<()> {
+        jju_jj::absorb::AbsorbCommand::new(jju_jj::absorb::AbsorbOptions {
+            revision: self.options.revision,
+            file_filter: self.options.file_filter,
+            preview: self.options.preview,
+            dry_run: self.options.dry_run,
+        })
+        .run()
+    }
+}
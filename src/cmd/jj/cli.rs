@@ -1,4 +1,7 @@
 use clap::{Parser, Subcommand};
+use jju_core::stack_sync::ConflictRollback;
+use jju_jj::split_hunk::OutputFormat;
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Parser)]
 #[command(name = "jju", author, version, about, styles = crate::cli::get_styles())]
@@ -7,6 +10,10 @@ pub struct Jj {
     #[arg(short, long)]
     pub neighborhood: bool,
 
+    /// Path to the jj repo to operate on, defaulting to the one containing the current directory
+    #[arg(short = 'R', long, global = true, value_name = "PATH")]
+    pub repository: Option<PathBuf>,
+
     #[command(subcommand)]
     pub subcommand: Option<JjCmd>,
 }
@@ -20,6 +27,33 @@ pub enum JjCmd {
         #[arg(short, long)]
         push: bool,
 
+        /// Sync every mutable stack on top of trunk, not only the one containing @
+        #[arg(short, long)]
+        all: bool,
+
+        /// Rebase onto this revision instead of trunk, e.g. a release branch
+        #[arg(short, long, value_name = "REVSET")]
+        onto: Option<String>,
+
+        /// Only sync the stack starting at this root (repeatable)
+        #[arg(short, long = "root", value_name = "REV", conflicts_with = "all")]
+        roots: Vec<String>,
+
+        /// What to do with stacks the rebase leaves conflicted: keep (asking to roll
+        /// back unless --force), stack (restore each conflicted stack) or all (restore
+        /// the whole sync)
+        #[arg(long, default_value = "keep")]
+        rollback: ConflictRollback,
+
+        /// Remote to take trunk from (default: jju.remote config, then origin)
+        #[arg(long)]
+        remote: Option<String>,
+
+        /// Remote to track and push bookmarks on (default: jju.push-remote config,
+        /// then git.push, then origin)
+        #[arg(long)]
+        push_remote: Option<String>,
+
         /// Skip confirmation prompt
         #[arg(short, long)]
         force: bool,
@@ -40,7 +74,8 @@ pub enum JjCmd {
     /// Split hunks from a commit non-interactively
     #[command(visible_alias = "sh")]
     SplitHunk {
-        /// Commit message for the new commit (required unless --preview)
+        /// Commit message for the new commit (required unless --preview);
+        /// with --by-file, `{file}` is replaced by each file's path
         #[arg(short, long)]
         message: Option<String>,
 
@@ -64,6 +99,15 @@ pub enum JjCmd {
         #[arg(long)]
         pattern: Option<String>,
 
+        /// Selection for one commit of a multi-way split, repeatable and stacked in order
+        /// (e.g., "file=src/a.rs,hunks=0,2,message=Update a"; keys: file, hunks, lines, pattern, message)
+        #[arg(long = "group", value_name = "SPEC", conflicts_with_all = ["lines", "hunks", "pattern", "message", "by_file"])]
+        groups: Vec<String>,
+
+        /// Split each changed file into its own stacked commit, the last file stays in the revision
+        #[arg(long, conflicts_with_all = ["lines", "hunks", "pattern"])]
+        by_file: bool,
+
         /// Preview hunks with indices (don't split)
         #[arg(long)]
         preview: bool,
@@ -72,8 +116,79 @@ pub enum JjCmd {
         #[arg(long)]
         invert: bool,
 
+        /// Match --lines and --pattern against single changed lines instead of whole hunks
+        #[arg(long)]
+        line_precise: bool,
+
         /// Show what would be committed without committing
         #[arg(long)]
         dry_run: bool,
+
+        /// Output format for --preview and --dry-run: text or json
+        #[arg(long, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Squash working-copy hunks into the mutable ancestors that last changed those lines
+    Absorb {
+        /// Revision to absorb from (default: @)
+        #[arg(short, long, default_value = "@")]
+        revision: String,
+
+        /// Only absorb hunks from matching files
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Preview each hunk with its destination (don't absorb)
+        #[arg(long)]
+        preview: bool,
+
+        /// Show where hunks would go without absorbing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Move selected hunks from one revision into another
+    #[command(visible_alias = "mh")]
+    MoveHunks {
+        /// Revision to take hunks from (default: @)
+        #[arg(long, default_value = "@")]
+        from: String,
+
+        /// Revision to apply the hunks to, anywhere in the graph
+        #[arg(long)]
+        to: String,
+
+        /// File to select hunks from
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Line ranges to include (e.g., "10-20,30-40")
+        #[arg(long)]
+        lines: Option<String>,
+
+        /// Hunk indices to include (e.g., "0,2,5")
+        #[arg(long)]
+        hunks: Option<String>,
+
+        /// Regex pattern to match hunk content
+        #[arg(long)]
+        pattern: Option<String>,
+
+        /// Preview hunks with indices (don't move)
+        #[arg(long)]
+        preview: bool,
+
+        /// Exclude matched hunks instead of including them
+        #[arg(long)]
+        invert: bool,
+
+        /// Match --lines and --pattern against single changed lines instead of whole hunks
+        #[arg(long)]
+        line_precise: bool,
+
+        /// Show what would be moved without moving
+        #[arg(long)]
+        dry_run: bool,
     },
 }
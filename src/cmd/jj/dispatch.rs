@@ -1,13 +1,32 @@
-use super::{Jj, JjCmd, split_hunk, stack_sync, tree};
-use eyre::Result;
+use super::{Jj, JjCmd, absorb, move_hunks, split_hunk, stack_sync, tree};
+use eyre::{Context as _, Result};
+use std::path::Path;
 
 pub(super) fn run_with_flags(flags: Jj) -> Result<()> {
+    enter_workspace_root(flags.repository.as_deref())?;
     let neighborhood = flags.neighborhood;
     match flags.subcommand {
         None => run_default(neighborhood),
-        Some(JjCmd::StackSync { push, force }) => {
-            stack_sync::StackSyncCommand::new(push, force).run()
-        }
+        Some(JjCmd::StackSync {
+            push,
+            all,
+            onto,
+            roots,
+            rollback,
+            remote,
+            push_remote,
+            force,
+        }) => stack_sync::StackSyncCommand::new(stack_sync::StackSyncOptions {
+            push,
+            all,
+            onto,
+            roots,
+            rollback,
+            remote,
+            push_remote,
+            force,
+        })
+        .run(),
         Some(JjCmd::Tree { full, from }) => tree::TreeCommand::new(full, from).run(),
         Some(JjCmd::SplitHunk {
             message,
@@ -16,9 +35,13 @@ pub(super) fn run_with_flags(flags: Jj) -> Result<()> {
             lines,
             hunks,
             pattern,
+            groups,
+            by_file,
             preview,
             invert,
+            line_precise,
             dry_run,
+            format,
         }) => split_hunk::SplitHunkCommand::new(split_hunk::SplitHunkOptions {
             message,
             revision,
@@ -26,14 +49,65 @@ pub(super) fn run_with_flags(flags: Jj) -> Result<()> {
             lines,
             hunks,
             pattern,
+            groups,
+            by_file,
+            preview,
+            invert,
+            line_precise,
+            dry_run,
+            format,
+        })
+        .run(),
+        Some(JjCmd::Absorb {
+            revision,
+            file,
+            preview,
+            dry_run,
+        }) => absorb::AbsorbCommand::new(absorb::AbsorbOptions {
+            revision,
+            file_filter: file,
+            preview,
+            dry_run,
+        })
+        .run(),
+        Some(JjCmd::MoveHunks {
+            from,
+            to,
+            file,
+            lines,
+            hunks,
+            pattern,
             preview,
             invert,
+            line_precise,
+            dry_run,
+        }) => move_hunks::MoveHunksCommand::new(move_hunks::MoveHunksOptions {
+            from,
+            to,
+            file_filter: file,
+            lines,
+            hunks,
+            pattern,
+            preview,
+            invert,
+            line_precise,
             dry_run,
         })
         .run(),
     }
 }
 
+/// Run from the workspace root so jj commands, jj-lib loads and file writes all
+/// resolve against the same repo, whether launched from a subdirectory or with `-R`
+fn enter_workspace_root(repository: Option<&Path>) -> Result<()> {
+    let start = match repository {
+        Some(path) => path.to_path_buf(),
+        None => std::env::current_dir().wrap_err("failed to get current directory")?,
+    };
+    let root = jju_jj::repo::find_workspace_root(&start)?;
+    std::env::set_current_dir(&root).wrap_err_with(|| format!("failed to enter {}", root.display()))
+}
+
 fn run_default(neighborhood: bool) -> Result<()> {
     if neighborhood {
         crate::cmd::jj_tui::run_with_options(crate::cmd::jj_tui::AppOptions {
@@ -0,0 +1,38 @@
+pub(crate) struct MoveHunksOptions {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) file_filter: Option<String>,
+    pub(crate) lines: Option<String>,
+    pub(crate) hunks: Option<String>,
+    pub(crate) pattern: Option<String>,
+    pub(crate) preview: bool,
+    pub(crate) invert: bool,
+    pub(crate) line_precise: bool,
+    pub(crate) dry_run: bool,
+}
+
+pub(crate) struct MoveHunksCommand {
+    options: MoveHunksOptions,
+}
+
+impl MoveHunksCommand {
+    pub(crate) fn new(options: MoveHunksOptions) -> Self {
+        Self { options }
+    }
+
+    pub(crate) fn run(self) -> eyre::Result<()> {
+        jju_jj::move_hunks::MoveHunksCommand::new(jju_jj::move_hunks::MoveHunksOptions {
+            from: self.options.from,
+            to: self.options.to,
+            file_filter: self.options.file_filter,
+            lines: self.options.lines,
+            hunks: self.options.hunks,
+            pattern: self.options.pattern,
+            preview: self.options.preview,
+            invert: self.options.invert,
+            line_precise: self.options.line_precise,
+            dry_run: self.options.dry_run,
+        })
+        .run()
+    }
+}
@@ -5,9 +5,13 @@ pub(crate) struct SplitHunkOptions {
     pub(crate) lines: Option<String>,
     pub(crate) hunks: Option<String>,
     pub(crate) pattern: Option<String>,
+    pub(crate) groups: Vec<String>,
+    pub(crate) by_file: bool,
     pub(crate) preview: bool,
     pub(crate) invert: bool,
+    pub(crate) line_precise: bool,
     pub(crate) dry_run: bool,
+    pub(crate) format: jju_jj::split_hunk::OutputFormat,
 }
 
 pub(crate) struct SplitHunkCommand {
@@ -27,9 +31,13 @@ impl SplitHunkCommand {
             lines: self.options.lines,
             hunks: self.options.hunks,
             pattern: self.options.pattern,
+            groups: self.options.groups,
+            by_file: self.options.by_file,
             preview: self.options.preview,
             invert: self.options.invert,
+            line_precise: self.options.line_precise,
             dry_run: self.options.dry_run,
+            format: self.options.format,
         })
         .run()
     }
@@ -1,14 +1,41 @@
+use jju_core::stack_sync::{ConflictRollback, StackSyncScope};
+
+pub(crate) struct StackSyncOptions {
+    pub(crate) push: bool,
+    pub(crate) all: bool,
+    pub(crate) onto: Option<String>,
+    pub(crate) roots: Vec<String>,
+    pub(crate) rollback: ConflictRollback,
+    pub(crate) remote: Option<String>,
+    pub(crate) push_remote: Option<String>,
+    pub(crate) force: bool,
+}
+
 pub(crate) struct StackSyncCommand {
-    push: bool,
-    force: bool,
+    options: StackSyncOptions,
 }
 
 impl StackSyncCommand {
-    pub(crate) fn new(push: bool, force: bool) -> Self {
-        Self { push, force }
+    pub(crate) fn new(options: StackSyncOptions) -> Self {
+        Self { options }
     }
 
     pub(crate) fn run(self) -> eyre::Result<()> {
-        jju_jj::stack_sync::StackSyncCommand::new(self.push, self.force).run()
+        let scope = if self.options.all {
+            StackSyncScope::All
+        } else {
+            StackSyncScope::Current
+        };
+        jju_jj::stack_sync::StackSyncCommand::new(jju_jj::stack_sync::StackSyncOptions {
+            push: self.options.push,
+            force: self.options.force,
+            scope,
+            rollback: self.options.rollback,
+            remote: self.options.remote,
+            push_remote: self.options.push_remote,
+            onto: self.options.onto,
+            roots: self.options.roots,
+        })
+        .run()
     }
 }
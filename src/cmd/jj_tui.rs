@@ -9,6 +9,7 @@ mod handlers;
 mod keybindings;
 mod preview;
 mod refresh;
+mod revset_history;
 mod runner;
 mod state;
 #[cfg(test)]
@@ -16,6 +17,7 @@ mod test_support;
 mod theme;
 pub mod tree;
 mod ui;
+mod views;
 mod vm;
 
 use eyre::Result;
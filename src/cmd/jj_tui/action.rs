@@ -7,6 +7,7 @@
 mod routing;
 
 use super::state::{BookmarkSelectAction, RebaseType};
+use jju_core::stack_sync::StackSyncScope;
 
 pub use routing::ActionDomain;
 
@@ -41,7 +42,7 @@ pub enum Action {
     EnterDiffView,
     ExitDiffView,
     EnterConfirmAbandon,
-    EnterConfirmStackSync,
+    EnterStackSync(StackSyncScope),
     EnterConfirmRebaseOntoTrunk(RebaseType),
     ConfirmYes,
     ConfirmNo,
@@ -97,8 +98,10 @@ pub enum Action {
     EditWorkingCopy,
     CreateNewCommit,
     CommitWorkingCopy,
+    Absorb,
     EditDescription,
     Undo,
+    Redo,
     GitPush,
     GitPushAll,
     GitFetch,
@@ -137,9 +140,82 @@ pub enum Action {
     ConflictsJump,
     StartResolveFromConflicts,
 
+    // Operation log
+    EnterOperationLog,
+    ExitOperationLog,
+    OperationLogUp,
+    OperationLogDown,
+    EnterConfirmRestoreOperation,
+
+    // Split hunk picker
+    EnterSplitHunk,
+    ExitSplitHunk,
+    SplitHunkUp,
+    SplitHunkDown,
+    SplitHunkToggle,
+    SplitHunkToggleLines,
+    SplitHunkSelectAll,
+    SplitHunkSelectNone,
+    SplitHunkStartMessage,
+    SplitHunkMessageChar(char),
+    SplitHunkMessageBackspace,
+    SplitHunkCancelMessage,
+    SplitHunkConfirm,
+
+    // Stack sync
+    ExitStackSync,
+    StackSyncUp,
+    StackSyncDown,
+    StackSyncToggle,
+    StackSyncSelectAll,
+    StackSyncSelectNone,
+    StackSyncTogglePush,
+    StackSyncConfirm,
+    StackSyncToggleDetails,
+
+    // Revset prompt
+    EnterRevsetPrompt,
+    ExitRevsetPrompt,
+    RevsetPromptChar(char),
+    RevsetPromptBackspace,
+    RevsetPromptHistoryPrev,
+    RevsetPromptHistoryNext,
+    RevsetPromptConfirm,
+
+    // Named views
+    EnterViewPicker,
+    ExitViewPicker,
+    ViewPickerDown,
+    ViewPickerUp,
+    ViewPickerConfirm,
+    /// Switch to the view in this slot, `0` being the default view
+    SwitchView(usize),
+
+    // Go-to prompt
+    EnterGotoPrompt,
+    ExitGotoPrompt,
+    GotoPromptChar(char),
+    GotoPromptBackspace,
+    GotoPromptConfirm,
+
+    // Search
+    EnterSearch,
+    ExitSearch,
+    SearchChar(char),
+    SearchBackspace,
+    SearchPromptToggleFilter,
+    SearchConfirm,
+    SearchNext,
+    SearchPrev,
+    SearchToggleFilter,
+    ClearSearch,
+
     // Divergence resolution
     ResolveDivergence,
 
+    // Background effects
+    CancelRunningEffect,
+
     // Lifecycle
     Quit,
     RefreshTree,
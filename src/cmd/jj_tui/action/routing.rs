@@ -42,7 +42,7 @@ impl Action {
             Action::EnterDiffView
             | Action::ExitDiffView
             | Action::EnterConfirmAbandon
-            | Action::EnterConfirmStackSync
+            | Action::EnterStackSync(_)
             | Action::EnterConfirmRebaseOntoTrunk(_)
             | Action::ConfirmYes
             | Action::ConfirmNo
@@ -66,7 +66,62 @@ impl Action {
             | Action::ConflictsUp
             | Action::ConflictsDown
             | Action::ConflictsJump
-            | Action::StartResolveFromConflicts => ActionDomain::Modes,
+            | Action::StartResolveFromConflicts
+            | Action::EnterOperationLog
+            | Action::ExitOperationLog
+            | Action::OperationLogUp
+            | Action::OperationLogDown
+            | Action::EnterConfirmRestoreOperation
+            | Action::EnterSplitHunk
+            | Action::ExitSplitHunk
+            | Action::SplitHunkUp
+            | Action::SplitHunkDown
+            | Action::SplitHunkToggle
+            | Action::SplitHunkToggleLines
+            | Action::SplitHunkSelectAll
+            | Action::SplitHunkSelectNone
+            | Action::SplitHunkStartMessage
+            | Action::SplitHunkMessageChar(_)
+            | Action::SplitHunkMessageBackspace
+            | Action::SplitHunkCancelMessage
+            | Action::SplitHunkConfirm
+            | Action::ExitStackSync
+            | Action::StackSyncUp
+            | Action::StackSyncDown
+            | Action::StackSyncToggle
+            | Action::StackSyncSelectAll
+            | Action::StackSyncSelectNone
+            | Action::StackSyncTogglePush
+            | Action::StackSyncConfirm
+            | Action::StackSyncToggleDetails
+            | Action::EnterRevsetPrompt
+            | Action::ExitRevsetPrompt
+            | Action::RevsetPromptChar(_)
+            | Action::RevsetPromptBackspace
+            | Action::RevsetPromptHistoryPrev
+            | Action::RevsetPromptHistoryNext
+            | Action::RevsetPromptConfirm
+            | Action::EnterViewPicker
+            | Action::ExitViewPicker
+            | Action::ViewPickerDown
+            | Action::ViewPickerUp
+            | Action::ViewPickerConfirm
+            | Action::SwitchView(_)
+            | Action::EnterGotoPrompt
+            | Action::ExitGotoPrompt
+            | Action::GotoPromptChar(_)
+            | Action::GotoPromptBackspace
+            | Action::GotoPromptConfirm
+            | Action::EnterSearch
+            | Action::ExitSearch
+            | Action::SearchChar(_)
+            | Action::SearchBackspace
+            | Action::SearchPromptToggleFilter
+            | Action::SearchConfirm
+            | Action::SearchNext
+            | Action::SearchPrev
+            | Action::SearchToggleFilter
+            | Action::ClearSearch => ActionDomain::Modes,
             Action::CopyBranchSelection(_) | Action::ExitClipboardMode => ActionDomain::Modes,
             Action::EnterMoveBookmarkMode
             | Action::EnterBookmarkPicker(_)
@@ -96,8 +151,10 @@ impl Action {
             Action::EditWorkingCopy
             | Action::CreateNewCommit
             | Action::CommitWorkingCopy
+            | Action::Absorb
             | Action::EditDescription
             | Action::Undo
+            | Action::Redo
             | Action::GitFetch
             | Action::GitImport
             | Action::GitExport
@@ -109,9 +166,11 @@ impl Action {
             | Action::CopyCommitMessage
             | Action::CopyCommitSubject
             | Action::CopySelectionRevset => ActionDomain::Commands,
-            Action::SetPendingKey(_) | Action::ClearPendingKey | Action::Quit | Action::Noop => {
-                ActionDomain::Lifecycle
-            }
+            Action::SetPendingKey(_)
+            | Action::ClearPendingKey
+            | Action::CancelRunningEffect
+            | Action::Quit
+            | Action::Noop => ActionDomain::Lifecycle,
         }
     }
 
@@ -4,17 +4,27 @@
 //! Key handling is delegated to the controller, business logic to the engine,
 //! and IO operations to the runner
 
-mod replaceable_task;
+mod effect_task;
+pub(crate) mod replaceable_task;
+mod repo_watcher;
 mod row_data;
 mod runtime;
 mod startup;
 #[cfg(test)]
+// the neighborhood preview test predates clippy's `useless_vec` firing on its `vec!`
+#[allow(clippy::useless_vec)]
 mod tests;
 
-use super::state::{DiffStats, ModeState, StatusMessage};
+use super::effect::Effect;
+use super::runner::StackSyncTask;
+use super::state::{DiffStats, ModeState, StatusMessage, UndoHistory};
 use super::tree::TreeState;
+use super::views::NamedView;
+use crate::cmd::jj_tui::app::effect_task::EffectTask;
+use crate::cmd::jj_tui::app::repo_watcher::RepoWatcher;
 use crate::cmd::jj_tui::app::row_data::RowDataLoader;
 use eyre::Result;
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use syntect::highlighting::ThemeSet;
 use syntect::parsing::SyntaxSet;
@@ -31,12 +41,22 @@ pub struct App {
     pub split_view: bool,
     pub diff_stats_cache: std::collections::HashMap<String, DiffStats>,
     pub status_message: Option<StatusMessage>,
-    pub last_op: Option<String>,
+    pub undo_history: UndoHistory,
     pub pending_key: Option<char>,
     pub(crate) syntax_set: SyntaxSet,
     pub(crate) theme_set: ThemeSet,
     pub(crate) repo_path: PathBuf,
     pub(crate) row_data_loader: RowDataLoader,
+    /// The stack sync running in the background, if any
+    pub(crate) stack_sync_task: Option<StackSyncTask>,
+    /// Named views from the views config, in config order
+    pub(crate) views: Vec<NamedView>,
+    /// Refreshes the tree when the repo changes outside jju
+    pub(crate) repo_watcher: Option<RepoWatcher>,
+    /// The long effect running on a worker thread, if any
+    pub(crate) effect_task: Option<EffectTask>,
+    /// Batches that change the repo, waiting for `effect_task` to finish
+    pub(crate) queued_effects: VecDeque<Vec<Effect>>,
 }
 
 impl App {
@@ -44,6 +64,13 @@ impl App {
         startup::new_app(options)
     }
 
+    /// Whether the tree was just refreshed for a change made outside jju
+    pub fn repo_recently_updated(&self) -> bool {
+        self.repo_watcher
+            .as_ref()
+            .is_some_and(RepoWatcher::recently_updated)
+    }
+
     pub fn current_has_bookmark(&self) -> bool {
         self.tree
             .current_node()
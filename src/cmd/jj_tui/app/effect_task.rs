@@ -0,0 +1,261 @@
+use super::App;
+use super::replaceable_task::ReplaceableTask;
+use crate::cmd::jj_tui::commands;
+use crate::cmd::jj_tui::effect::Effect;
+use crate::cmd::jj_tui::runner::{self, BackgroundEffect};
+use crate::cmd::jj_tui::state::MessageKind;
+use ratatui::DefaultTerminal;
+use std::sync::mpsc::TryRecvError;
+use std::time::{Duration, Instant};
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_FRAME: Duration = Duration::from_millis(80);
+
+/// A long effect, like a fetch or push, running on a worker thread while the
+/// tree stays usable
+#[derive(Debug)]
+pub(crate) struct EffectTask {
+    /// What's running, for the status bar
+    pub(crate) label: String,
+    started_at: Instant,
+    /// Operation before the effect and its undo label
+    undo: Option<(String, String)>,
+    /// The rest of the effect's batch, run once it finishes
+    then: Vec<Effect>,
+    task: ReplaceableTask<(String, MessageKind)>,
+}
+
+impl EffectTask {
+    fn spawn(background: BackgroundEffect) -> Self {
+        let BackgroundEffect { effect, undo, then } = background;
+        let label = effect.background_label();
+
+        let task = ReplaceableTask::spawn(Duration::ZERO, move |token, sender| {
+            let status = jju_jj::ops::with_cancellation(
+                move || token.is_cancelled(),
+                || runner::run_in_background(&effect),
+            );
+            let _ = sender.send(status);
+        });
+
+        Self {
+            label,
+            started_at: Instant::now(),
+            undo,
+            then,
+            task,
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.task.is_cancelled()
+    }
+
+    pub(crate) fn spinner(&self) -> char {
+        let frame = self.started_at.elapsed().as_millis() / SPINNER_FRAME.as_millis();
+        SPINNER[frame as usize % SPINNER.len()]
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+impl App {
+    /// What's running in the background, a background effect or a stack sync
+    fn busy_label(&self) -> Option<&str> {
+        match (&self.effect_task, &self.stack_sync_task) {
+            (Some(task), _) => Some(&task.label),
+            (None, Some(_)) => Some("stack sync"),
+            (None, None) => None,
+        }
+    }
+
+    /// Queue a batch that changes the repo while a background effect or stack
+    /// sync runs, returning the effects that can run right away
+    pub(super) fn defer_while_busy(&mut self, effects: Vec<Effect>) -> Option<Vec<Effect>> {
+        let Some(label) = self.busy_label() else {
+            return Some(effects);
+        };
+        if !effects.iter().any(Effect::is_mutating) {
+            return Some(effects);
+        }
+
+        let text = format!("Queued until {label} finishes");
+        self.queued_effects.push_back(effects);
+        self.set_status(&text, MessageKind::Warning);
+        None
+    }
+
+    pub(super) fn start_effect_task(&mut self, background: BackgroundEffect) {
+        self.effect_task = Some(EffectTask::spawn(background));
+    }
+
+    /// Kill the running background effect or stack sync, dropping what was
+    /// queued behind it
+    pub(super) fn cancel_effect_task(&mut self) {
+        let text = if let Some(sync) = &mut self.stack_sync_task
+            && !sync.is_cancelled()
+        {
+            sync.task.cancel();
+            "Cancelling stack sync".to_string()
+        } else if let Some(task) = &mut self.effect_task {
+            task.task.cancel();
+            format!("Cancelling {}", task.label)
+        } else {
+            return;
+        };
+
+        self.queued_effects.clear();
+        self.set_status(&text, MessageKind::Warning);
+    }
+
+    /// Once the background effect's worker is done, record it for undo, run
+    /// the rest of its batch and then the batches queued behind it
+    pub(super) fn apply_effect_task_progress(&mut self, terminal: &mut DefaultTerminal) {
+        let Some(task) = &self.effect_task else {
+            return;
+        };
+        let status = match task.task.receiver().try_recv() {
+            Ok(status) => Some(status),
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => None,
+        };
+        let Some(task) = self.effect_task.take() else {
+            return;
+        };
+
+        if let Some((op_before, label)) = task.undo
+            && let Ok(op_after) = commands::get_current_op_id()
+            && op_after != op_before
+        {
+            self.undo_history.record(op_before, label);
+        }
+
+        let (text, kind) = match status {
+            _ if task.task.is_cancelled() => {
+                (format!("Cancelled {}", task.label), MessageKind::Warning)
+            }
+            Some(status) => status,
+            None => (
+                format!("{} stopped unexpectedly", task.label),
+                MessageKind::Error,
+            ),
+        };
+        self.set_status(&text, kind);
+        self.apply_effects(task.then, terminal);
+        self.run_queued_effects(terminal);
+    }
+
+    /// Run the batches queued behind a finished background effect or stack
+    /// sync, until one of them starts another
+    pub(super) fn run_queued_effects(&mut self, terminal: &mut DefaultTerminal) {
+        while self.busy_label().is_none()
+            && let Some(effects) = self.queued_effects.pop_front()
+        {
+            self.apply_effects(effects, terminal);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EffectTask;
+    use crate::cmd::jj_tui::app::replaceable_task::ReplaceableTask;
+    use crate::cmd::jj_tui::effect::Effect;
+    use crate::cmd::jj_tui::runner::StackSyncTask;
+    use crate::cmd::jj_tui::state::MessageKind;
+    use crate::cmd::jj_tui::test_support::{TestNodeKind, make_app_with_tree, make_tree};
+    use std::sync::mpsc::RecvTimeoutError;
+    use std::time::{Duration, Instant};
+
+    fn running_fetch() -> EffectTask {
+        EffectTask {
+            label: "fetch".to_string(),
+            started_at: Instant::now(),
+            undo: None,
+            then: vec![Effect::RefreshTree],
+            task: ReplaceableTask::spawn(Duration::ZERO, |token, sender| {
+                while !token.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                let _ = sender.send(("cancelled".to_string(), MessageKind::Error));
+            }),
+        }
+    }
+
+    #[test]
+    fn mutating_effects_wait_for_the_running_effect() {
+        let mut app = make_app_with_tree(make_tree(vec![TestNodeKind::Plain.make_node("a", 0)]));
+        let edit = vec![
+            Effect::SaveOperationForUndo,
+            Effect::RunEdit {
+                rev: "a".to_string(),
+            },
+            Effect::RefreshTree,
+        ];
+        assert!(app.defer_while_busy(edit.clone()).is_some());
+
+        app.effect_task = Some(running_fetch());
+        assert!(app.defer_while_busy(edit).is_none());
+        assert_eq!(app.queued_effects.len(), 1);
+        assert_eq!(
+            app.status_message
+                .as_ref()
+                .map(|message| message.text.as_str()),
+            Some("Queued until fetch finishes")
+        );
+
+        let copy = vec![Effect::CopyToClipboard {
+            value: "a".to_string(),
+            success: "Copied".to_string(),
+        }];
+        assert!(app.defer_while_busy(copy).is_some());
+    }
+
+    #[test]
+    fn mutating_effects_wait_for_a_running_stack_sync() {
+        let mut app = make_app_with_tree(make_tree(vec![TestNodeKind::Plain.make_node("a", 0)]));
+        app.stack_sync_task = Some(StackSyncTask {
+            op_before: None,
+            task: ReplaceableTask::spawn(Duration::ZERO, |token, _sender| {
+                while !token.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+            }),
+        });
+
+        assert!(app.defer_while_busy(vec![Effect::RefreshTree]).is_none());
+        assert_eq!(
+            app.status_message
+                .as_ref()
+                .map(|message| message.text.as_str()),
+            Some("Queued until stack sync finishes")
+        );
+
+        app.cancel_effect_task();
+        assert!(app.queued_effects.is_empty());
+        assert!(
+            app.stack_sync_task
+                .as_ref()
+                .is_some_and(StackSyncTask::is_cancelled)
+        );
+    }
+
+    #[test]
+    fn cancel_drops_the_queue_and_stops_the_worker() {
+        let mut app = make_app_with_tree(make_tree(vec![TestNodeKind::Plain.make_node("a", 0)]));
+        app.effect_task = Some(running_fetch());
+        app.queued_effects.push_back(vec![Effect::RunGitPushAll]);
+
+        app.cancel_effect_task();
+
+        let task = app.effect_task.as_ref().expect("still winding down");
+        assert!(task.is_cancelled());
+        assert!(app.queued_effects.is_empty());
+        assert!(!matches!(
+            task.task.receiver().recv_timeout(Duration::from_secs(1)),
+            Err(RecvTimeoutError::Timeout)
+        ));
+    }
+}
@@ -29,6 +29,11 @@ impl<T> ReplaceableTask<T> {
         &self.receiver
     }
 
+    /// Whether `cancel` was called, the task possibly still winding down
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancel_token.is_none()
+    }
+
     pub(crate) fn cancel(&mut self) {
         if let Some(token) = self.cancel_token.take() {
             token.cancel();
@@ -92,7 +97,9 @@ mod tests {
             let _ = sender.send("old");
         });
 
+        assert!(!task.is_cancelled());
         task.cancel();
+        assert!(task.is_cancelled());
         std::thread::sleep(Duration::from_millis(80));
 
         assert!(matches!(
@@ -0,0 +1,148 @@
+use super::App;
+use super::replaceable_task::ReplaceableTask;
+use crate::cmd::jj_tui::refresh;
+use crate::cmd::jj_tui::state::{MessageKind, ModeState};
+use crate::jj_lib_helpers::OpHeadsWatch;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const POLL_STEP: Duration = Duration::from_millis(10);
+const UPDATED_INDICATOR_DURATION: Duration = Duration::from_secs(3);
+
+/// Polls the operation heads in the background so operations made outside
+/// jju, like `jj describe` in another terminal, refresh the tree
+#[derive(Debug)]
+pub(crate) struct RepoWatcher {
+    watch: OpHeadsWatch,
+    /// The op heads as of the last tree reload
+    seen: Vec<String>,
+    task: ReplaceableTask<Vec<String>>,
+    /// When the tree was last refreshed for an outside change
+    updated_at: Option<Instant>,
+}
+
+impl RepoWatcher {
+    pub(crate) fn spawn(watch: OpHeadsWatch) -> Self {
+        let seen = watch.op_heads().unwrap_or_default();
+        let task_watch = watch.clone();
+        let mut last = seen.clone();
+
+        let task = ReplaceableTask::spawn(Duration::ZERO, move |token, sender| {
+            while !token.is_cancelled() {
+                if let Ok(heads) = task_watch.op_heads()
+                    && heads != last
+                {
+                    last = heads.clone();
+                    if sender.send(heads).is_err() {
+                        return;
+                    }
+                }
+
+                let mut waited = Duration::ZERO;
+                while waited < POLL_INTERVAL && !token.is_cancelled() {
+                    std::thread::sleep(POLL_STEP);
+                    waited += POLL_STEP;
+                }
+            }
+        });
+
+        Self {
+            watch,
+            seen,
+            task,
+            updated_at: None,
+        }
+    }
+
+    pub(crate) fn recently_updated(&self) -> bool {
+        self.updated_at
+            .is_some_and(|updated_at| updated_at.elapsed() < UPDATED_INDICATOR_DURATION)
+    }
+
+    /// The newest op heads reported by the poller, when the tree doesn't show them yet
+    fn changed_heads(&mut self) -> Option<Vec<String>> {
+        let mut latest = None;
+        while let Ok(heads) = self.task.receiver().try_recv() {
+            latest = Some(heads);
+        }
+        latest.filter(|heads| *heads != self.seen)
+    }
+
+    fn mark_seen(&mut self) {
+        if let Ok(heads) = self.watch.op_heads() {
+            self.seen = heads;
+        }
+    }
+}
+
+impl App {
+    /// Refresh the tree when the repo changed outside jju, waiting until
+    /// normal mode so a pending operation keeps the tree it was started on,
+    /// and for a background effect or stack sync, which refresh once they finish
+    pub(super) fn apply_repo_changes(&mut self) {
+        if !matches!(self.mode, ModeState::Normal)
+            || self.effect_task.is_some()
+            || self.stack_sync_task.is_some()
+        {
+            return;
+        }
+        let Some(watcher) = &mut self.repo_watcher else {
+            return;
+        };
+        let Some(heads) = watcher.changed_heads() else {
+            return;
+        };
+
+        watcher.seen = heads;
+        match refresh::refresh_tree(&mut self.tree, &mut self.diff_stats_cache) {
+            Ok(warning) => {
+                watcher.mark_seen();
+                watcher.updated_at = Some(Instant::now());
+                self.reset_row_data_loader();
+                if let Some(warning) = warning {
+                    self.set_status(&warning, MessageKind::Warning);
+                }
+            }
+            Err(error) => {
+                self.set_status(&format!("Failed to refresh: {error}"), MessageKind::Error);
+            }
+        }
+    }
+
+    /// Record that the tree was reloaded by jju itself, so the poller doesn't
+    /// report jju's own operations as outside changes
+    pub(super) fn mark_repo_seen(&mut self) {
+        if let Some(watcher) = &mut self.repo_watcher {
+            watcher.mark_seen();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RepoWatcher;
+    use jju_jj::repo::test_support::TempRepo;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_reports_operations_made_outside_jju() {
+        let temp = TempRepo::init("repo-watcher");
+        let mut watcher = RepoWatcher::spawn(temp.load().op_heads_watch());
+        assert_eq!(watcher.changed_heads(), None);
+
+        temp.repo
+            .start_transaction()
+            .commit("outside jju")
+            .expect("commit operation");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut changed = None;
+        while changed.is_none() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+            changed = watcher.changed_heads();
+        }
+        assert!(changed.is_some());
+
+        watcher.mark_seen();
+        assert_eq!(watcher.changed_heads(), None);
+    }
+}
@@ -1,7 +1,13 @@
 mod conflicts;
 mod event_loop;
+mod goto;
 mod input;
 mod neighborhood;
+mod operation_log;
+mod revset;
+mod split_hunk;
+mod stack_sync;
+mod views;
 
 use super::App;
 
@@ -18,6 +18,9 @@ fn run_loop(app: &mut App, terminal: &mut DefaultTerminal) -> Result<()> {
         let viewport_width = size.width.saturating_sub(2) as usize;
 
         app.apply_row_data_updates();
+        app.apply_stack_sync_progress(terminal);
+        app.apply_effect_task_progress(terminal);
+        app.apply_repo_changes();
         app.schedule_current_row_data_load();
 
         let vms = vm::build_tree_view(app, viewport_width);
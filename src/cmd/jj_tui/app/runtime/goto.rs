@@ -0,0 +1,78 @@
+use super::super::App;
+use crate::cmd::jj_tui::{
+    refresh,
+    state::{ConfirmAction, ConfirmState, MessageKind, ModeState},
+};
+use crate::jj_lib_helpers::JjRepo;
+
+impl App {
+    /// Jump to the change `prefix` resolves to, offering a reload when it isn't loaded
+    pub(super) fn goto_change(&mut self, prefix: String) {
+        let resolved = JjRepo::load(None).and_then(|jj_repo| {
+            jj_repo.with_short_prefix_index(|index| jj_repo.resolve_change_prefix(index, &prefix))
+        });
+        let commit_ids = match resolved {
+            Ok(commit_ids) => commit_ids,
+            Err(error) => {
+                if let ModeState::GotoPrompt(ref mut state) = self.mode {
+                    state.error = Some(format!("{error:#}"));
+                }
+                return;
+            }
+        };
+
+        self.mode = ModeState::Normal;
+        if let Some(node_index) = self.node_for_commits(&commit_ids) {
+            self.reveal(node_index);
+            return;
+        }
+
+        let commit_id = commit_ids[0].clone();
+        let revset = format!("({}) | {commit_id}", self.tree.loaded_revset("trunk()"));
+        self.mode = ModeState::Confirming(ConfirmState {
+            action: ConfirmAction::LoadChange {
+                commit_id,
+                revset: revset.clone(),
+            },
+            message: format!("{prefix} isn't loaded. Reload with a revset that includes it?"),
+            revs: vec![format!("  {revset}")],
+        });
+    }
+
+    /// Reload the tree with `revset`, then jump to `commit_id`
+    pub(super) fn load_change(&mut self, commit_id: String, revset: String) {
+        if let Err(error) =
+            refresh::reload_tree(&mut self.tree, &mut self.diff_stats_cache, Some(&revset))
+        {
+            self.set_status(
+                &format!("Failed to reload the tree: {error:#}"),
+                MessageKind::Error,
+            );
+            return;
+        }
+
+        self.reset_row_data_loader();
+        self.tree.view.view_name = None;
+        match self.node_for_commits(std::slice::from_ref(&commit_id)) {
+            Some(node_index) => self.reveal(node_index),
+            None => self.set_status("The reloaded tree doesn't include it", MessageKind::Warning),
+        }
+    }
+
+    fn node_for_commits(&self, commit_ids: &[String]) -> Option<usize> {
+        self.tree
+            .nodes()
+            .iter()
+            .position(|node| commit_ids.contains(&node.commit_id))
+    }
+
+    fn reveal(&mut self, node_index: usize) {
+        let change_id = self.tree.nodes()[node_index].change_id.clone();
+        if self.tree.reveal_node(node_index) {
+            self.set_status(
+                &format!("Widened the view to show {change_id}"),
+                MessageKind::Success,
+            );
+        }
+    }
+}
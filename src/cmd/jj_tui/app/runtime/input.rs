@@ -25,6 +25,15 @@ impl App {
             neighborhood_active: self.tree.is_neighborhood_mode(),
             has_neighborhood_history: self.tree.has_neighborhood_history(),
             can_enter_neighborhood_path: self.tree.current_entry_is_neighborhood_preview(),
+            search_active: self.tree.view.search.is_some(),
+            effect_running: self
+                .effect_task
+                .as_ref()
+                .is_some_and(|task| !task.is_cancelled())
+                || self
+                    .stack_sync_task
+                    .as_ref()
+                    .is_some_and(|task| !task.is_cancelled()),
         };
         let action = controller::handle_key(&ctx, key);
         let old_mode = self.mode.clone();
@@ -42,14 +51,62 @@ impl App {
             ),
             action,
         );
+        self.apply_effects(effects, terminal);
+        self.transition_neighborhood_mode(&old_mode);
+    }
+
+    /// Run a batch of effects from the engine, then the loads and reloads the
+    /// app does itself
+    pub(in crate::cmd::jj_tui::app) fn apply_effects(
+        &mut self,
+        effects: Vec<Effect>,
+        terminal: &mut DefaultTerminal,
+    ) {
+        let Some(effects) = self.defer_while_busy(effects) else {
+            return;
+        };
+        let needs_effect_cancel = effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::CancelBackground));
         let needs_conflict_load = effects
             .iter()
             .any(|effect| matches!(effect, Effect::LoadConflictFiles));
+        let needs_operation_log_load = effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::LoadOperationLog));
+        let needs_split_hunk_load = effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::LoadSplitHunkDiff));
+        let needs_stack_sync_load = effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::LoadStackSyncPlan));
+        let needs_revset_history_load = effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::LoadRevsetHistory));
+        let revset_to_apply = effects.iter().find_map(|effect| match effect {
+            Effect::ApplyRevset { revset } => Some(revset.clone()),
+            _ => None,
+        });
+        let needs_views_load = effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::LoadViews));
+        let view_to_apply = effects.iter().find_map(|effect| match effect {
+            Effect::ApplyView { slot } => Some(*slot),
+            _ => None,
+        });
+        let change_to_goto = effects.iter().find_map(|effect| match effect {
+            Effect::GotoChange { prefix } => Some(prefix.clone()),
+            _ => None,
+        });
+        let change_to_load = effects.iter().find_map(|effect| match effect {
+            Effect::LoadChange { commit_id, revset } => Some((commit_id.clone(), revset.clone())),
+            _ => None,
+        });
         let result = runner::run_effects(
             runner::RunCtx::new(
                 &mut self.tree,
                 &mut self.diff_stats_cache,
-                &mut self.last_op,
+                &mut self.undo_history,
                 &self.repo_path,
             ),
             effects,
@@ -60,10 +117,57 @@ impl App {
             self.load_conflict_files();
         }
 
-        self.transition_neighborhood_mode(&old_mode);
+        if needs_operation_log_load {
+            self.load_operation_log();
+        }
+
+        if needs_split_hunk_load {
+            self.load_split_hunk_diff();
+        }
+
+        if needs_stack_sync_load {
+            self.load_stack_sync_plan();
+        }
+
+        if needs_revset_history_load {
+            self.load_revset_history();
+        }
+
+        if let Some(revset) = revset_to_apply {
+            self.apply_revset(revset);
+        }
+
+        if needs_views_load {
+            self.load_views();
+        }
+
+        if let Some(slot) = view_to_apply {
+            self.apply_view(slot);
+        }
+
+        if let Some(prefix) = change_to_goto {
+            self.goto_change(prefix);
+        }
+
+        if let Some((commit_id, revset)) = change_to_load {
+            self.load_change(commit_id, revset);
+        }
+
+        if let Some(task) = result.stack_sync {
+            self.stack_sync_task = Some(task);
+        }
+
+        if needs_effect_cancel {
+            self.cancel_effect_task();
+        }
+
+        if let Some(background) = result.background {
+            self.start_effect_task(background);
+        }
 
         if result.tree_refreshed {
             self.reset_row_data_loader();
+            self.mark_repo_seen();
         }
 
         if let Some((text, kind)) = result.status_message {
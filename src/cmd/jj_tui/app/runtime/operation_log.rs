@@ -0,0 +1,28 @@
+use super::super::App;
+use crate::cmd::jj_tui::state::{MessageKind, ModeState};
+use crate::jj_lib_helpers::JjRepo;
+
+const OPERATION_LOG_LIMIT: usize = 200;
+
+impl App {
+    pub(super) fn load_operation_log(&mut self) {
+        let entries = JjRepo::load(Some(&self.repo_path))
+            .and_then(|repo| repo.operation_log(OPERATION_LOG_LIMIT));
+
+        match entries {
+            Ok(entries) => {
+                if let ModeState::OperationLog(ref mut state) = self.mode {
+                    state.entries = entries;
+                    state.selected_index = 0;
+                }
+            }
+            Err(error) => {
+                self.set_status(
+                    &format!("Failed to load operation log: {error}"),
+                    MessageKind::Error,
+                );
+                self.mode = ModeState::Normal;
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use super::super::App;
+use crate::cmd::jj_tui::{
+    refresh, revset_history,
+    state::{MessageKind, ModeState},
+};
+use crate::jj_lib_helpers::JjRepo;
+
+impl App {
+    pub(super) fn load_revset_history(&mut self) {
+        if let ModeState::RevsetPrompt(ref mut state) = self.mode {
+            state.history = revset_history::load();
+        }
+    }
+
+    /// Reload the tree with `revset`, keeping the prompt open with the error when it fails
+    pub(super) fn apply_revset(&mut self, revset: Option<String>) {
+        let result = match &revset {
+            Some(revset) => check_revset(revset),
+            None => Ok(()),
+        }
+        .and_then(|()| {
+            refresh::reload_tree(
+                &mut self.tree,
+                &mut self.diff_stats_cache,
+                revset.as_deref(),
+            )
+            .map_err(|error| format!("{error:#}"))
+        });
+
+        if let Err(error) = result {
+            if let ModeState::RevsetPrompt(ref mut state) = self.mode {
+                state.error = Some(error);
+            }
+            return;
+        }
+
+        self.reset_row_data_loader();
+        self.mode = ModeState::Normal;
+        self.tree.view.view_name = None;
+        match revset {
+            Some(revset) => {
+                revset_history::record(&revset);
+                self.set_status(&format!("Showing {revset}"), MessageKind::Success);
+            }
+            None => self.set_status("Showing the default revset", MessageKind::Success),
+        }
+    }
+}
+
+/// Parse and evaluate `revset` up front, so a typo doesn't replace the tree
+pub(super) fn check_revset(revset: &str) -> Result<(), String> {
+    let jj_repo = JjRepo::load(None).map_err(|error| format!("{error:#}"))?;
+    match jj_repo.eval_revset(revset) {
+        Ok(commits) if commits.is_empty() => Err("revset matched no commits".to_string()),
+        Ok(_) => Ok(()),
+        Err(error) => Err(format!("{error:#}")),
+    }
+}
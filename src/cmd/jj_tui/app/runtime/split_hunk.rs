@@ -0,0 +1,29 @@
+use super::super::App;
+use crate::cmd::jj_tui::{
+    commands,
+    state::{MessageKind, ModeState},
+};
+
+impl App {
+    pub(super) fn load_split_hunk_diff(&mut self) {
+        let ModeState::SplitHunk(ref mut state) = self.mode else {
+            return;
+        };
+
+        match commands::split_hunk::load_diff(&state.rev) {
+            Ok(diff) if diff.is_empty() => {
+                self.set_status("No changes in revision", MessageKind::Warning);
+                self.mode = ModeState::Normal;
+            }
+            Ok(diff) => {
+                state.diff = diff;
+                state.cursor = 0;
+                state.selected.clear();
+            }
+            Err(error) => {
+                self.set_status(&format!("Failed to load diff: {error}"), MessageKind::Error);
+                self.mode = ModeState::Normal;
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::super::App;
+use crate::cmd::jj_tui::{
+    commands, refresh,
+    state::{MessageKind, ModeState},
+};
+use ratatui::DefaultTerminal;
+use std::sync::mpsc::TryRecvError;
+
+impl App {
+    pub(super) fn load_stack_sync_plan(&mut self) {
+        let ModeState::StackSync(ref mut state) = self.mode else {
+            return;
+        };
+
+        match commands::stack_sync::discover_plan(state.scope) {
+            Ok(plan) => state.set_plan(plan),
+            Err(error) => {
+                self.set_status(
+                    &format!("Failed to find stacks to sync: {error}"),
+                    MessageKind::Error,
+                );
+                self.mode = ModeState::Normal;
+            }
+        }
+    }
+
+    /// Show the steps the background stack sync finished since the last frame,
+    /// then run what was queued behind it once it's over
+    pub(super) fn apply_stack_sync_progress(&mut self, terminal: &mut DefaultTerminal) {
+        let Some(task) = &self.stack_sync_task else {
+            return;
+        };
+
+        let mut finished = false;
+        loop {
+            match task.task.receiver().try_recv() {
+                Ok(progress) => {
+                    if let ModeState::StackSync(ref mut state) = self.mode {
+                        state.apply_progress(progress);
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    finished = true;
+                    break;
+                }
+            }
+        }
+
+        if finished {
+            self.finish_stack_sync();
+            self.run_queued_effects(terminal);
+        }
+    }
+
+    fn finish_stack_sync(&mut self) {
+        let Some(task) = self.stack_sync_task.take() else {
+            return;
+        };
+
+        let cancelled = task.is_cancelled();
+        if let Some(op_before) = task.op_before
+            && let Ok(op_after) = commands::get_current_op_id()
+            && op_after != op_before
+        {
+            self.undo_history.record(op_before, "stack sync");
+        }
+
+        let snapshot_warning =
+            match refresh::refresh_tree(&mut self.tree, &mut self.diff_stats_cache) {
+                Ok(warning) => {
+                    self.reset_row_data_loader();
+                    self.mark_repo_seen();
+                    warning
+                }
+                Err(error) => {
+                    self.set_status(&format!("Failed to refresh: {error}"), MessageKind::Error);
+                    None
+                }
+            };
+
+        if let ModeState::StackSync(ref mut state) = self.mode {
+            state.finish();
+            let (text, kind) = state.summary();
+            self.set_status(&text, kind);
+        } else if cancelled {
+            // the overlay was closed while the cancelled sync wound down
+            self.set_status("Stack sync cancelled", MessageKind::Warning);
+        }
+
+        if let Some(warning) = snapshot_warning {
+            self.set_status(&warning, MessageKind::Warning);
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use super::super::App;
+use super::revset::check_revset;
+use crate::cmd::jj_tui::{
+    refresh,
+    state::{MessageKind, ModeState},
+};
+
+impl App {
+    pub(super) fn load_views(&mut self) {
+        let active = self
+            .tree
+            .view
+            .view_name
+            .as_ref()
+            .and_then(|name| self.views.iter().position(|view| view.name == *name));
+        if let ModeState::ViewPicker(ref mut state) = self.mode {
+            state.views = self.views.clone();
+            state.cursor = active.map_or(0, |index| index + 1);
+        }
+    }
+
+    /// Switch to the view in `slot`, then apply its display defaults
+    pub(super) fn apply_view(&mut self, slot: usize) {
+        let view = match slot.checked_sub(1) {
+            None => None,
+            Some(index) => match self.views.get(index) {
+                Some(view) => Some(view.clone()),
+                None => {
+                    self.set_status(
+                        &format!("No view configured for {slot}"),
+                        MessageKind::Warning,
+                    );
+                    return;
+                }
+            },
+        };
+
+        let revset = view.as_ref().map(|view| view.revset.as_str());
+        let result = revset.map_or(Ok(()), check_revset).and_then(|()| {
+            refresh::reload_tree(&mut self.tree, &mut self.diff_stats_cache, revset)
+                .map_err(|error| format!("{error:#}"))
+        });
+        if let Err(error) = result {
+            let name = view.as_ref().map_or("default", |view| view.name.as_str());
+            self.set_status(
+                &format!("Failed to show view {name}: {error}"),
+                MessageKind::Error,
+            );
+            return;
+        }
+
+        self.reset_row_data_loader();
+        self.mode = ModeState::Normal;
+        let Some(view) = view else {
+            self.tree.view.view_name = None;
+            self.set_status("Showing the default view", MessageKind::Success);
+            return;
+        };
+
+        if let Some(full_mode) = view.full_mode {
+            self.tree.set_full_mode(full_mode);
+        }
+        match view.neighborhood {
+            Some(true) if !self.tree.is_neighborhood_mode() => self.tree.enable_neighborhood(),
+            Some(false) if self.tree.is_neighborhood_mode() => self.tree.disable_neighborhood(),
+            _ => {}
+        }
+        self.tree.view.view_name = Some(view.name.clone());
+        self.set_status(&format!("Showing view {}", view.name), MessageKind::Success);
+    }
+}
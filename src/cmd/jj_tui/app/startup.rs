@@ -1,7 +1,8 @@
+use super::repo_watcher::RepoWatcher;
 use super::{App, AppOptions};
-use crate::cmd::jj_tui::keybindings;
 use crate::cmd::jj_tui::state::{MessageKind, ModeState, StatusMessage};
 use crate::cmd::jj_tui::tree::{TreeLoadScope, TreeState};
+use crate::cmd::jj_tui::{keybindings, refresh, views};
 use crate::jj_lib_helpers::JjRepo;
 use eyre::Result;
 use log::info;
@@ -12,8 +13,15 @@ use syntect::parsing::SyntaxSet;
 pub(super) fn new_app(options: AppOptions) -> Result<App> {
     let startup_started_at = Instant::now();
     let keybindings_warning = keybindings::initialize();
+    let (views, views_warning) = views::load();
     let repo_path = std::env::current_dir()?;
-    let jj_repo = JjRepo::load(Some(&repo_path))?;
+    let mut jj_repo = JjRepo::load(Some(&repo_path))?;
+    let snapshot_warning = refresh::snapshot_working_copy(&mut jj_repo);
+    let warnings: Vec<String> = [keybindings_warning, views_warning, snapshot_warning]
+        .into_iter()
+        .flatten()
+        .collect();
+    let startup_warning = (!warnings.is_empty()).then(|| warnings.join("; "));
     let load_scope = startup_load_scope(options);
     let mut tree = TreeState::load_with_scope(&jj_repo, "trunk()", load_scope)?;
     apply_startup_options(&mut tree, options);
@@ -25,19 +33,24 @@ pub(super) fn new_app(options: AppOptions) -> Result<App> {
         should_quit: false,
         split_view: false,
         diff_stats_cache: std::collections::HashMap::new(),
-        status_message: keybindings_warning.map(|warning| {
+        status_message: startup_warning.map(|warning| {
             StatusMessage::with_duration(
                 warning,
                 MessageKind::Warning,
                 keybindings::warning_duration(),
             )
         }),
-        last_op: None,
+        undo_history: Default::default(),
         pending_key: None,
         syntax_set,
         theme_set,
         repo_path,
         row_data_loader: Default::default(),
+        stack_sync_task: None,
+        views,
+        repo_watcher: Some(RepoWatcher::spawn(jj_repo.op_heads_watch())),
+        effect_task: None,
+        queued_effects: Default::default(),
     };
     info!("Initialized jj_tui in {:?}", startup_started_at.elapsed());
 
@@ -1,10 +1,12 @@
 //! JJ command execution helpers for jj_tui
 
+pub mod absorb;
 pub mod bookmark;
 pub mod diff;
 pub mod git;
 pub mod rebase;
 pub mod revision;
+pub mod split_hunk;
 pub mod stack_sync;
 
 /// Get the current operation ID for potential undo
@@ -12,6 +14,11 @@ pub fn get_current_op_id() -> eyre::Result<String> {
     jju_jj::ops::OperationOps.current_op_id()
 }
 
+/// Open the operation heads, to compare the current operation before and after an effect
+pub fn get_op_heads() -> eyre::Result<crate::jj_lib_helpers::OpHeadsWatch> {
+    jju_jj::ops::OperationOps.op_heads()
+}
+
 /// Restore to a previous operation (undo)
 pub fn restore_op(op_id: &str) -> eyre::Result<()> {
     jju_jj::ops::OperationOps.restore(op_id)
@@ -0,0 +1,5 @@
+use jju_jj::absorb::AbsorbSummary;
+
+pub fn absorb(rev: &str) -> eyre::Result<AbsorbSummary> {
+    jju_jj::absorb::absorb(rev)
+}
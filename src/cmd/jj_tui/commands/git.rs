@@ -1,5 +1,12 @@
+use jju_core::remote::Remotes;
+
+/// The configured trunk and push remotes
+pub fn remotes() -> eyre::Result<Remotes> {
+    jju_jj::ops::ConfigOps.remotes(None, None)
+}
+
 pub fn push_bookmark(name: &str) -> eyre::Result<()> {
-    jju_jj::ops::GitOps.push_bookmark(name)
+    jju_jj::ops::GitOps.push_bookmark(name, &remotes()?.push)
 }
 
 pub fn import() -> eyre::Result<()> {
@@ -11,15 +18,15 @@ pub fn export() -> eyre::Result<()> {
 }
 
 pub fn push_all() -> eyre::Result<()> {
-    jju_jj::ops::GitOps.push_all()
+    jju_jj::ops::GitOps.push_all(&remotes()?.push)
 }
 
 pub fn fetch() -> eyre::Result<()> {
-    jju_jj::ops::GitOps.fetch()
+    jju_jj::ops::GitOps.fetch(&remotes()?)
 }
 
 /// Push a bookmark and create or open its PR
 /// Returns true if an existing PR was opened, false if a new one was created
 pub fn push_and_pr(bookmark: &str) -> eyre::Result<bool> {
-    jju_jj::ops::GitOps.push_and_pr(bookmark)
+    jju_jj::ops::GitOps.push_and_pr(bookmark, &remotes()?.push)
 }
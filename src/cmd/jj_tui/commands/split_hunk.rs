@@ -0,0 +1,9 @@
+use jju_jj::split_hunk::{ParsedDiff, SplitHunkPlan};
+
+pub fn load_diff(rev: &str) -> eyre::Result<ParsedDiff> {
+    jju_jj::split_hunk::load_diff(rev)
+}
+
+pub fn split(plan: &SplitHunkPlan, rev: &str, message: &str) -> eyre::Result<()> {
+    jju_jj::split_hunk::split_plan(plan, rev, message)
+}
@@ -1,29 +1,54 @@
-/// Detect the trunk branch name via jj's trunk() revset
-pub fn detect_trunk_branch() -> eyre::Result<String> {
-    jju_jj::stack_sync::detect_trunk_branch()
+use jju_core::remote::Remotes;
+use jju_core::stack_sync::{
+    ConflictRollback, StackRootPlan, StackRootResult, StackSyncPlan, StackSyncScope,
+};
+use jju_jj::stack_sync::StackSyncOptions;
+
+/// Discover the stacks a sync would rebase, with the commits in each
+pub fn discover_plan(scope: StackSyncScope) -> eyre::Result<StackSyncPlan> {
+    let options = StackSyncOptions {
+        scope,
+        ..StackSyncOptions::default()
+    };
+    jju_jj::stack_sync::discover_plan(&options, super::git::remotes()?)
 }
 
-/// Sync trunk bookmark to match its remote tracking branch
-pub fn sync_trunk_bookmark(trunk: &str) -> eyre::Result<()> {
-    jju_jj::stack_sync::sync_trunk_bookmark(trunk)
+/// Fetch from trunk's remote and the push remote
+pub fn fetch(remotes: &Remotes) -> eyre::Result<()> {
+    jju_jj::ops::GitOps.fetch(remotes)
 }
 
-/// Find the root commits between trunk and the working copy
-pub fn find_stack_roots(trunk: &str) -> eyre::Result<Vec<String>> {
-    jju_jj::stack_sync::find_stack_roots(trunk)
+/// Sync trunk bookmark to match its remote tracking branch
+pub fn sync_trunk_bookmark(trunk: &str, remote: &str) -> eyre::Result<()> {
+    jju_jj::stack_sync::sync_trunk_bookmark(trunk, remote)
 }
 
-/// Get the first line of a commit's description
-pub fn get_commit_description(rev: &str) -> eyre::Result<String> {
-    jju_jj::stack_sync::get_commit_description(rev)
+/// Rebase a stack root with --skip-emptied, reporting the conflicts it leaves
+pub fn sync_root(root: &str, onto: &str) -> StackRootResult {
+    let root = StackRootPlan {
+        change_id: root.to_string(),
+        description: String::new(),
+        commits: Vec::new(),
+        landed: None,
+    };
+    jju_jj::stack_sync::sync_root(&root, onto, ConflictRollback::Keep)
 }
 
-/// Rebase a stack root onto trunk with --skip-emptied
-pub fn rebase_root_onto_trunk(root: &str, trunk: &str) -> eyre::Result<()> {
-    jju_jj::stack_sync::rebase_root_onto_trunk(root, trunk)
+/// Abandon a stack that already landed on trunk, returning its deleted bookmarks
+pub fn abandon_landed_stack(root: &str) -> eyre::Result<Vec<String>> {
+    jju_jj::stack_sync::abandon_landed_stack(root)
 }
 
-/// Delete tracked bookmarks that are marked as [deleted] on the remote
+/// Delete bookmarks that are gone locally but still tracked on a remote
 pub fn cleanup_deleted_bookmarks() -> eyre::Result<Vec<String>> {
     jju_jj::stack_sync::cleanup_deleted_bookmarks()
 }
+
+/// Push the first bookmark of the current stack, returning its name
+pub fn push_first_bookmark(onto: &str, remote: &str) -> eyre::Result<Option<String>> {
+    let Some(bookmark) = jju_jj::stack_sync::first_stack_bookmark(onto)? else {
+        return Ok(None);
+    };
+    jju_jj::ops::GitOps.push_bookmark(&bookmark, remote)?;
+    Ok(Some(bookmark))
+}
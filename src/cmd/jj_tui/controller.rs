@@ -15,6 +15,9 @@ pub struct ControllerContext<'a> {
     pub neighborhood_active: bool,
     pub has_neighborhood_history: bool,
     pub can_enter_neighborhood_path: bool,
+    pub search_active: bool,
+    /// A fetch, push or other long effect is running in the background
+    pub effect_running: bool,
 }
 
 /// Map a key event to an action based on current mode
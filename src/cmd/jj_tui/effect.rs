@@ -3,8 +3,10 @@
 //! Effects represent side effects - IO operations that need to be performed.
 //! The engine produces effects, and the runner executes them.
 
-use super::state::{MessageKind, RebaseType};
+use super::state::{MessageKind, RebaseType, StackSyncStepKind};
 use jju_core::interactive::InteractiveOperation;
+use jju_core::remote::Remotes;
+use jju_jj::split_hunk::SplitHunkPlan;
 
 /// All possible side effects produced by the engine
 #[derive(Debug, Clone)]
@@ -35,6 +37,10 @@ pub enum Effect {
         rebase_type: RebaseType,
     },
     RunUndo,
+    RunRedo,
+    RunRestoreOperation {
+        op_id: String,
+    },
     RunGitPush {
         bookmark: String,
     },
@@ -43,7 +49,13 @@ pub enum Effect {
     },
     RunGitPushAll,
     RunGitFetch,
-    RunStackSync,
+    /// Run the steps of a stack sync in the background, reporting each one's progress
+    RunStackSync {
+        onto: String,
+        trunk: String,
+        remotes: Remotes,
+        steps: Vec<StackSyncStepKind>,
+    },
     RunGitImport,
     RunGitExport,
     RunBookmarkSet {
@@ -64,6 +76,14 @@ pub enum Effect {
     RunCreatePR {
         bookmark: String,
     },
+    RunSplitHunk {
+        rev: String,
+        message: String,
+        plan: SplitHunkPlan,
+    },
+    RunAbsorb {
+        rev: String,
+    },
     RunInteractive(InteractiveOperation),
     CopyToClipboard {
         value: String,
@@ -84,4 +104,168 @@ pub enum Effect {
 
     // Conflicts
     LoadConflictFiles,
+
+    // Operation log
+    LoadOperationLog,
+
+    // Split hunk
+    LoadSplitHunkDiff,
+
+    // Stack sync
+    LoadStackSyncPlan,
+
+    // Revset prompt
+    LoadRevsetHistory,
+    /// Reload the tree with a revset, `None` for the default one
+    ApplyRevset {
+        revset: Option<String>,
+    },
+
+    // Named views
+    LoadViews,
+    /// Switch to the view in this slot, `0` being the default view
+    ApplyView {
+        slot: usize,
+    },
+
+    // Go-to prompt
+    /// Resolve a change id prefix and move the cursor to it
+    GotoChange {
+        prefix: String,
+    },
+    /// Reload the tree with `revset` and move the cursor to `commit_id`
+    LoadChange {
+        commit_id: String,
+        revset: String,
+    },
+
+    // Background effects
+    /// Cancel the effect running on a worker thread, dropping the queued ones
+    CancelBackground,
+}
+
+impl Effect {
+    /// Label for the undo history, or `None` if the effect doesn't change the repo
+    pub fn undo_label(&self) -> Option<String> {
+        let label = match self {
+            Effect::RunEdit { rev } => format!("edit {}", short_rev(rev)),
+            Effect::RunNew { rev } => format!("new on {}", short_rev(rev)),
+            Effect::RunCommit { .. } => "commit".to_string(),
+            Effect::RunAbandon { revset } => {
+                let count = revset.matches('|').count() + 1;
+                if count == 1 {
+                    format!("abandon {}", short_rev(revset))
+                } else {
+                    format!("abandon {count} revisions")
+                }
+            }
+            Effect::RunRebase {
+                source,
+                rebase_type,
+                ..
+            }
+            | Effect::RunRebaseOntoTrunk {
+                source,
+                rebase_type,
+            } => format!("rebase {rebase_type} {}", short_rev(source)),
+            Effect::RunRestoreOperation { op_id } => {
+                format!("restore {}", op_id.chars().take(12).collect::<String>())
+            }
+            Effect::RunGitFetch => "fetch".to_string(),
+            Effect::RunGitImport => "git import".to_string(),
+            Effect::RunGitExport => "git export".to_string(),
+            Effect::RunBookmarkSet { name, .. } | Effect::RunBookmarkSetBackwards { name, .. } => {
+                format!("set bookmark {name}")
+            }
+            Effect::RunBookmarkDelete { name } => format!("delete bookmark {name}"),
+            Effect::RunResolveDivergence { .. } => "resolve divergence".to_string(),
+            Effect::RunSplitHunk { rev, .. } => format!("split {}", short_rev(rev)),
+            Effect::RunAbsorb { rev } => format!("absorb {}", short_rev(rev)),
+            Effect::RunInteractive(InteractiveOperation::EditDescription { rev }) => {
+                format!("describe {}", short_rev(rev))
+            }
+            Effect::RunInteractive(InteractiveOperation::Resolve { file }) => {
+                format!("resolve {file}")
+            }
+            // restoring an operation can't un-push a remote or close a PR
+            Effect::RunGitPush { .. }
+            | Effect::RunGitPushMultiple { .. }
+            | Effect::RunGitPushAll
+            | Effect::RunCreatePR { .. } => return None,
+            // squash records the operation captured when squash mode was entered, and
+            // stack sync the one before its worker started, once the worker finishes
+            Effect::RunInteractive(InteractiveOperation::Squash(_))
+            | Effect::RunStackSync { .. }
+            | Effect::RefreshTree
+            | Effect::RunUndo
+            | Effect::RunRedo
+            | Effect::CopyToClipboard { .. }
+            | Effect::CopyCommitMessageToClipboard { .. }
+            | Effect::SetStatus { .. }
+            | Effect::SaveOperationForUndo
+            | Effect::LoadConflictFiles
+            | Effect::LoadOperationLog
+            | Effect::LoadSplitHunkDiff
+            | Effect::LoadStackSyncPlan
+            | Effect::LoadRevsetHistory
+            | Effect::ApplyRevset { .. }
+            | Effect::LoadViews
+            | Effect::ApplyView { .. }
+            | Effect::GotoChange { .. }
+            | Effect::LoadChange { .. }
+            | Effect::CancelBackground => return None,
+        };
+        Some(label)
+    }
+
+    /// What a background effect is doing, for the status bar while it runs
+    pub fn background_label(&self) -> String {
+        match self {
+            Effect::RunGitPush { bookmark } | Effect::RunCreatePR { bookmark } => {
+                format!("push {bookmark}")
+            }
+            Effect::RunGitPushMultiple { bookmarks } => {
+                format!("push {} bookmarks", bookmarks.len())
+            }
+            Effect::RunGitPushAll => "push all".to_string(),
+            _ => self.undo_label().unwrap_or_else(|| "command".to_string()),
+        }
+    }
+
+    /// Whether the effect waits on the network, so it runs on a worker thread
+    /// instead of freezing the UI
+    pub fn runs_in_background(&self) -> bool {
+        matches!(
+            self,
+            Effect::RunGitPush { .. }
+                | Effect::RunGitPushMultiple { .. }
+                | Effect::RunGitPushAll
+                | Effect::RunGitFetch
+                | Effect::RunGitImport
+                | Effect::RunGitExport
+                | Effect::RunCreatePR { .. }
+        )
+    }
+
+    /// Whether the effect changes the repo, so it has to wait for a running
+    /// background effect instead of racing it
+    pub fn is_mutating(&self) -> bool {
+        self.undo_label().is_some()
+            || matches!(
+                self,
+                Effect::RefreshTree
+                    | Effect::RunGitPush { .. }
+                    | Effect::RunGitPushMultiple { .. }
+                    | Effect::RunGitPushAll
+                    | Effect::RunCreatePR { .. }
+                    | Effect::RunUndo
+                    | Effect::RunRedo
+                    | Effect::RunStackSync { .. }
+                    | Effect::RunInteractive(_)
+            )
+    }
+}
+
+fn short_rev(rev: &str) -> String {
+    rev.chars().take(8).collect()
 }
@@ -56,7 +56,14 @@ fn bookmark_picker_state<'a>(
         | ModeState::PushSelect(_)
         | ModeState::ClipboardBranchSelect(_)
         | ModeState::Help(_)
-        | ModeState::Conflicts(_) => None,
+        | ModeState::Conflicts(_)
+        | ModeState::OperationLog(_)
+        | ModeState::SplitHunk(_)
+        | ModeState::StackSync(_)
+        | ModeState::RevsetPrompt(_)
+        | ModeState::ViewPicker(_)
+        | ModeState::Search(_)
+        | ModeState::GotoPrompt(_) => None,
     }
 }
 
@@ -11,11 +11,16 @@ pub(super) fn handle(ctx: &mut ReduceCtx<'_>, action: Action) {
         Action::EditWorkingCopy => revision::edit_working_copy(ctx),
         Action::CreateNewCommit => revision::create_new_commit(ctx),
         Action::CommitWorkingCopy => revision::commit_working_copy(ctx),
+        Action::Absorb => revision::absorb(ctx),
         Action::EditDescription => revision::edit_description(ctx),
         Action::Undo => {
             ctx.effects.push(Effect::RunUndo);
             ctx.effects.push(Effect::RefreshTree);
         }
+        Action::Redo => {
+            ctx.effects.push(Effect::RunRedo);
+            ctx.effects.push(Effect::RefreshTree);
+        }
         Action::GitFetch => git::run_simple_refresh(ctx, Effect::RunGitFetch),
         Action::GitImport => git::run_simple_refresh(ctx, Effect::RunGitImport),
         Action::GitExport => git::run_simple_refresh(ctx, Effect::RunGitExport),
@@ -20,6 +20,17 @@ pub(super) fn edit_working_copy(ctx: &mut ReduceCtx<'_>) {
     ctx.effects.push(Effect::RefreshTree);
 }
 
+pub(super) fn absorb(ctx: &mut ReduceCtx<'_>) {
+    let rev = current_rev(ctx.tree);
+    if rev.is_empty() {
+        ctx.set_status("No revision selected", MessageKind::Error);
+        return;
+    }
+
+    ctx.effects.push(Effect::RunAbsorb { rev });
+    ctx.effects.push(Effect::RefreshTree);
+}
+
 pub(super) fn create_new_commit(ctx: &mut ReduceCtx<'_>) {
     let rev = current_rev(ctx.tree);
     if rev.is_empty() {
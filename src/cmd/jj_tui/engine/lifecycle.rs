@@ -1,4 +1,4 @@
-use super::{Action, ReduceCtx};
+use super::{Action, Effect, ReduceCtx};
 
 pub(super) fn handle(ctx: &mut ReduceCtx<'_>, action: Action) {
     match action {
@@ -6,6 +6,7 @@ pub(super) fn handle(ctx: &mut ReduceCtx<'_>, action: Action) {
         Action::Noop => {}
         Action::SetPendingKey(prefix) => *ctx.pending_key = Some(prefix),
         Action::ClearPendingKey => *ctx.pending_key = None,
+        Action::CancelRunningEffect => ctx.effects.push(Effect::CancelBackground),
         _ => unreachable!("unsupported lifecycle action: {action:?}"),
     }
 }
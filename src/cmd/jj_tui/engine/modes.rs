@@ -2,17 +2,24 @@ mod clipboard;
 mod confirm;
 mod conflicts;
 mod diff;
+mod goto;
+mod operation_log;
 mod rebase;
+mod revset;
+mod search;
+mod split_hunk;
 mod squash;
+mod stack_sync;
+mod views;
 
 use super::{Action, Effect, ModeState, ReduceCtx};
-use crate::cmd::jj_tui::state::ConflictsState;
+use crate::cmd::jj_tui::state::{ConflictsState, OperationLogState};
 
 pub(super) fn handle(ctx: &mut ReduceCtx<'_>, action: Action) {
     match action {
         Action::EnterDiffView => diff::enter_diff_view(ctx),
         Action::ExitDiffView => *ctx.mode = ModeState::Normal,
-        Action::EnterConfirmStackSync => confirm::enter_stack_sync(ctx),
+        Action::EnterStackSync(scope) => stack_sync::enter(ctx, scope),
         Action::EnterConfirmAbandon => confirm::enter_abandon(ctx),
         Action::EnterConfirmRebaseOntoTrunk(rebase_type) => {
             confirm::enter_rebase_onto_trunk(ctx, rebase_type);
@@ -45,6 +52,64 @@ pub(super) fn handle(ctx: &mut ReduceCtx<'_>, action: Action) {
         Action::ConflictsDown => conflicts::move_down(ctx),
         Action::ConflictsJump => *ctx.mode = ModeState::Normal,
         Action::StartResolveFromConflicts => conflicts::start_resolve(ctx),
+        Action::EnterOperationLog => {
+            *ctx.mode = ModeState::OperationLog(OperationLogState::default());
+            ctx.effects.push(Effect::LoadOperationLog);
+        }
+        Action::ExitOperationLog => *ctx.mode = ModeState::Normal,
+        Action::OperationLogUp => operation_log::move_up(ctx),
+        Action::OperationLogDown => operation_log::move_down(ctx),
+        Action::EnterConfirmRestoreOperation => operation_log::enter_confirm_restore(ctx),
+        Action::EnterSplitHunk => split_hunk::enter(ctx),
+        Action::ExitSplitHunk => *ctx.mode = ModeState::Normal,
+        Action::SplitHunkUp => split_hunk::move_up(ctx),
+        Action::SplitHunkDown => split_hunk::move_down(ctx),
+        Action::SplitHunkToggle => split_hunk::toggle(ctx),
+        Action::SplitHunkToggleLines => split_hunk::toggle_lines(ctx),
+        Action::SplitHunkSelectAll => split_hunk::select_all(ctx),
+        Action::SplitHunkSelectNone => split_hunk::select_none(ctx),
+        Action::SplitHunkStartMessage => split_hunk::start_message(ctx),
+        Action::SplitHunkMessageChar(ch) => split_hunk::message_char(ctx, ch),
+        Action::SplitHunkMessageBackspace => split_hunk::message_backspace(ctx),
+        Action::SplitHunkCancelMessage => split_hunk::cancel_message(ctx),
+        Action::SplitHunkConfirm => split_hunk::confirm(ctx),
+        Action::ExitStackSync => stack_sync::exit(ctx),
+        Action::StackSyncUp => stack_sync::move_up(ctx),
+        Action::StackSyncDown => stack_sync::move_down(ctx),
+        Action::StackSyncToggle => stack_sync::toggle(ctx),
+        Action::StackSyncSelectAll => stack_sync::select_all(ctx),
+        Action::StackSyncSelectNone => stack_sync::select_none(ctx),
+        Action::StackSyncTogglePush => stack_sync::toggle_push(ctx),
+        Action::StackSyncConfirm => stack_sync::confirm(ctx),
+        Action::StackSyncToggleDetails => stack_sync::toggle_details(ctx),
+        Action::EnterRevsetPrompt => revset::enter(ctx),
+        Action::ExitRevsetPrompt => *ctx.mode = ModeState::Normal,
+        Action::RevsetPromptChar(ch) => revset::push_char(ctx, ch),
+        Action::RevsetPromptBackspace => revset::backspace(ctx),
+        Action::RevsetPromptHistoryPrev => revset::history_prev(ctx),
+        Action::RevsetPromptHistoryNext => revset::history_next(ctx),
+        Action::RevsetPromptConfirm => revset::confirm(ctx),
+        Action::EnterViewPicker => views::enter(ctx),
+        Action::ExitViewPicker => *ctx.mode = ModeState::Normal,
+        Action::ViewPickerDown => views::move_down(ctx),
+        Action::ViewPickerUp => views::move_up(ctx),
+        Action::ViewPickerConfirm => views::confirm(ctx),
+        Action::SwitchView(slot) => ctx.effects.push(Effect::ApplyView { slot }),
+        Action::EnterGotoPrompt => goto::enter(ctx),
+        Action::ExitGotoPrompt => *ctx.mode = ModeState::Normal,
+        Action::GotoPromptChar(ch) => goto::push_char(ctx, ch),
+        Action::GotoPromptBackspace => goto::backspace(ctx),
+        Action::GotoPromptConfirm => goto::confirm(ctx),
+        Action::EnterSearch => search::enter(ctx),
+        Action::ExitSearch => search::cancel(ctx),
+        Action::SearchChar(ch) => search::push_char(ctx, ch),
+        Action::SearchBackspace => search::backspace(ctx),
+        Action::SearchPromptToggleFilter => search::toggle_prompt_filter(ctx),
+        Action::SearchConfirm => search::confirm(ctx),
+        Action::SearchNext => search::jump(ctx, true),
+        Action::SearchPrev => search::jump(ctx, false),
+        Action::SearchToggleFilter => search::toggle_filter(ctx),
+        Action::ClearSearch => ctx.tree.set_search(None),
         _ => unreachable!("unsupported mode action: {action:?}"),
     }
 }
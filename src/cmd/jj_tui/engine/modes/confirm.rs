@@ -4,10 +4,6 @@ mod enter;
 use super::super::ReduceCtx;
 use crate::cmd::jj_tui::state::RebaseType;
 
-pub(super) fn enter_stack_sync(ctx: &mut ReduceCtx<'_>) {
-    enter::stack_sync(ctx);
-}
-
 pub(super) fn enter_abandon(ctx: &mut ReduceCtx<'_>) {
     enter::abandon(ctx);
 }
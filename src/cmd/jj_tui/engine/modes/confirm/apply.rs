@@ -9,12 +9,17 @@ pub(super) fn confirm_yes(ctx: &mut ReduceCtx<'_>) {
 
     match state.action {
         ConfirmAction::Abandon => abandon(ctx, state.revs),
-        ConfirmAction::StackSync => stack_sync(ctx),
         ConfirmAction::RebaseOntoTrunk(rebase_type) => rebase_onto_trunk(ctx, rebase_type),
         ConfirmAction::MoveBookmarkBackwards {
             bookmark_name,
             dest_rev,
         } => move_bookmark_backwards(ctx, bookmark_name, dest_rev),
+        ConfirmAction::RestoreOperation { op_id } => {
+            push_with_refresh(ctx, Effect::RunRestoreOperation { op_id });
+        }
+        ConfirmAction::LoadChange { commit_id, revset } => {
+            ctx.effects.push(Effect::LoadChange { commit_id, revset });
+        }
     }
 
     ctx.tree.clear_selection();
@@ -25,10 +30,6 @@ fn abandon(ctx: &mut ReduceCtx<'_>, revs: Vec<String>) {
     push_with_refresh(ctx, Effect::RunAbandon { revset });
 }
 
-fn stack_sync(ctx: &mut ReduceCtx<'_>) {
-    push_with_refresh(ctx, Effect::RunStackSync);
-}
-
 fn rebase_onto_trunk(ctx: &mut ReduceCtx<'_>, rebase_type: crate::cmd::jj_tui::state::RebaseType) {
     let source = current_rev(ctx.tree);
     if source.is_empty() {
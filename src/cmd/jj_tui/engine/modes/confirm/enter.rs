@@ -2,19 +2,6 @@ use super::super::super::selection::{current_rev, get_revs_for_action};
 use super::super::{ModeState, ReduceCtx};
 use crate::cmd::jj_tui::state::{ConfirmAction, ConfirmState, MessageKind, RebaseType};
 
-pub(super) fn stack_sync(ctx: &mut ReduceCtx<'_>) {
-    let trunk = super::super::super::super::commands::stack_sync::detect_trunk_branch()
-        .unwrap_or_else(|_| "trunk".to_string());
-    let roots = super::super::super::super::commands::stack_sync::find_stack_roots(&trunk)
-        .unwrap_or_default();
-
-    *ctx.mode = ModeState::Confirming(ConfirmState {
-        action: ConfirmAction::StackSync,
-        message: format!("Will rebase the following commits on top of {trunk}:"),
-        revs: stack_sync_revs(&trunk, &roots),
-    });
-}
-
 pub(super) fn abandon(ctx: &mut ReduceCtx<'_>) {
     let revs = get_revs_for_action(ctx.tree);
     if revs.iter().any(|rev| is_working_copy(ctx, rev)) {
@@ -57,23 +44,6 @@ pub(super) fn rebase_onto_trunk(ctx: &mut ReduceCtx<'_>, rebase_type: RebaseType
     });
 }
 
-fn stack_sync_revs(trunk: &str, roots: &[String]) -> Vec<String> {
-    if roots.is_empty() {
-        return vec!["  (stack is up to date, nothing to rebase)".to_string()];
-    }
-
-    let mut revs = Vec::new();
-    for root in roots {
-        let desc = super::super::super::super::commands::stack_sync::get_commit_description(root)
-            .unwrap_or_default();
-        revs.push(format!("  {root}  {desc}"));
-        revs.push(format!(
-            "  jj rebase --source (-s) {root} --onto (-o) {trunk} --skip-emptied"
-        ));
-    }
-    revs
-}
-
 fn is_working_copy(ctx: &ReduceCtx<'_>, rev: &str) -> bool {
     ctx.tree
         .nodes()
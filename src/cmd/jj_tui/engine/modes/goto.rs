@@ -0,0 +1,33 @@
+use super::super::{Effect, ModeState, ReduceCtx};
+use crate::cmd::jj_tui::state::GotoPromptState;
+
+pub(super) fn enter(ctx: &mut ReduceCtx<'_>) {
+    *ctx.mode = ModeState::GotoPrompt(GotoPromptState::default());
+}
+
+pub(super) fn push_char(ctx: &mut ReduceCtx<'_>, ch: char) {
+    if let ModeState::GotoPrompt(state) = ctx.mode {
+        state.push_char(ch);
+    }
+}
+
+pub(super) fn backspace(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::GotoPrompt(state) = ctx.mode {
+        state.backspace();
+    }
+}
+
+pub(super) fn confirm(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::GotoPrompt(state) = ctx.mode else {
+        return;
+    };
+
+    let prefix = state.input.trim();
+    if prefix.is_empty() {
+        state.error = Some("type a change id prefix".to_string());
+        return;
+    }
+    ctx.effects.push(Effect::GotoChange {
+        prefix: prefix.to_string(),
+    });
+}
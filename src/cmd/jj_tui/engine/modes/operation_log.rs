@@ -0,0 +1,45 @@
+use super::super::{ModeState, ReduceCtx};
+use crate::cmd::jj_tui::state::{ConfirmAction, ConfirmState, MessageKind};
+
+pub(super) fn move_up(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::OperationLog(state) = ctx.mode
+        && state.selected_index > 0
+    {
+        state.selected_index -= 1;
+    }
+}
+
+pub(super) fn move_down(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::OperationLog(state) = ctx.mode {
+        let max = state.entries.len().saturating_sub(1);
+        if state.selected_index < max {
+            state.selected_index += 1;
+        }
+    }
+}
+
+pub(super) fn enter_confirm_restore(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::OperationLog(state) = &*ctx.mode else {
+        return;
+    };
+    let Some(entry) = state.selected_entry().cloned() else {
+        return;
+    };
+
+    if entry.is_current {
+        ctx.set_status("Already at this operation", MessageKind::Warning);
+        return;
+    }
+
+    let short_id: String = entry.op_id.chars().take(12).collect();
+    let revs = vec![
+        format!("  {short_id}  {}", entry.description),
+        format!("  {}", entry.timestamp),
+        format!("  jj op restore {short_id}"),
+    ];
+    *ctx.mode = ModeState::Confirming(ConfirmState {
+        action: ConfirmAction::RestoreOperation { op_id: entry.op_id },
+        message: format!("Restore repo to operation {short_id}?"),
+        revs,
+    });
+}
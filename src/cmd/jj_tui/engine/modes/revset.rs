@@ -0,0 +1,43 @@
+use super::super::{Effect, ModeState, ReduceCtx};
+use crate::cmd::jj_tui::state::RevsetPromptState;
+
+pub(super) fn enter(ctx: &mut ReduceCtx<'_>) {
+    *ctx.mode = ModeState::RevsetPrompt(RevsetPromptState::new(ctx.tree.view.revset.clone()));
+    ctx.effects.push(Effect::LoadRevsetHistory);
+}
+
+pub(super) fn push_char(ctx: &mut ReduceCtx<'_>, ch: char) {
+    if let ModeState::RevsetPrompt(state) = ctx.mode {
+        state.push_char(ch);
+    }
+}
+
+pub(super) fn backspace(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::RevsetPrompt(state) = ctx.mode {
+        state.backspace();
+    }
+}
+
+pub(super) fn history_prev(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::RevsetPrompt(state) = ctx.mode {
+        state.history_prev();
+    }
+}
+
+pub(super) fn history_next(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::RevsetPrompt(state) = ctx.mode {
+        state.history_next();
+    }
+}
+
+/// Show the typed revset, or go back to the default one when the input is empty
+pub(super) fn confirm(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::RevsetPrompt(state) = ctx.mode else {
+        return;
+    };
+
+    let revset = state.input.trim();
+    ctx.effects.push(Effect::ApplyRevset {
+        revset: (!revset.is_empty()).then(|| revset.to_string()),
+    });
+}
@@ -0,0 +1,98 @@
+use super::super::{ModeState, ReduceCtx};
+use crate::cmd::jj_tui::state::{MessageKind, SearchPromptState};
+use crate::cmd::jj_tui::tree::TreeSearch;
+
+pub(super) fn enter(ctx: &mut ReduceCtx<'_>) {
+    let origin_node = ctx.tree.current_entry().map(|entry| entry.node_index);
+    *ctx.mode = ModeState::Search(SearchPromptState::new(
+        ctx.tree.view.search.clone(),
+        origin_node,
+    ));
+}
+
+pub(super) fn push_char(ctx: &mut ReduceCtx<'_>, ch: char) {
+    if let ModeState::Search(state) = ctx.mode {
+        state.input.push(ch);
+    }
+    update(ctx);
+}
+
+pub(super) fn backspace(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::Search(state) = ctx.mode {
+        state.input.pop();
+    }
+    update(ctx);
+}
+
+pub(super) fn toggle_prompt_filter(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::Search(state) = ctx.mode {
+        state.filter = !state.filter;
+    }
+    update(ctx);
+}
+
+/// Search as the query is typed, moving to the first match from where the prompt opened
+fn update(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::Search(state) = ctx.mode else {
+        return;
+    };
+
+    let origin_node = state.origin_node;
+    ctx.tree.set_search(state.search());
+    let origin_row = origin_node.and_then(|node_index| {
+        ctx.tree
+            .visible_entries()
+            .iter()
+            .position(|entry| entry.node_index == node_index)
+    });
+    if !ctx.tree.jump_to_match_from(origin_row.unwrap_or(0))
+        && let Some(row) = origin_row
+    {
+        ctx.tree.view.cursor = row;
+    }
+}
+
+pub(super) fn confirm(ctx: &mut ReduceCtx<'_>) {
+    *ctx.mode = ModeState::Normal;
+    if let Some(query) = ctx.tree.search_query()
+        && ctx.tree.search_matches().is_empty()
+    {
+        let message = format!("No matches for {query}");
+        ctx.set_status(message, MessageKind::Warning);
+    }
+}
+
+/// Go back to the search and cursor from before the prompt opened
+pub(super) fn cancel(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::Search(state) = std::mem::replace(ctx.mode, ModeState::Normal) else {
+        return;
+    };
+
+    ctx.tree.set_search(state.previous);
+    if let Some(node_index) = state.origin_node {
+        ctx.tree.restore_cursor_to_node(node_index);
+    }
+}
+
+pub(super) fn jump(ctx: &mut ReduceCtx<'_>, forward: bool) {
+    let Some(query) = ctx.tree.search_query().map(ToOwned::to_owned) else {
+        ctx.set_status("No active search", MessageKind::Warning);
+        return;
+    };
+
+    if !ctx.tree.jump_to_adjacent_match(forward) {
+        ctx.set_status(format!("No matches for {query}"), MessageKind::Warning);
+    }
+}
+
+pub(super) fn toggle_filter(ctx: &mut ReduceCtx<'_>) {
+    let Some(search) = ctx.tree.view.search.clone() else {
+        ctx.set_status("No active search", MessageKind::Warning);
+        return;
+    };
+
+    ctx.tree.set_search(Some(TreeSearch {
+        filter: !search.filter,
+        ..search
+    }));
+}
@@ -0,0 +1,157 @@
+use super::super::selection::current_rev;
+use super::super::{Effect, ModeState, ReduceCtx};
+use crate::cmd::jj_tui::state::{MessageKind, SplitHunkRow, SplitHunkState};
+
+pub(super) fn enter(ctx: &mut ReduceCtx<'_>) {
+    let rev = current_rev(ctx.tree);
+    if rev.is_empty() {
+        ctx.set_status("No revision selected", MessageKind::Error);
+        return;
+    }
+
+    *ctx.mode = ModeState::SplitHunk(SplitHunkState::new(rev));
+    ctx.effects.push(Effect::LoadSplitHunkDiff);
+}
+
+pub(super) fn move_up(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::SplitHunk(state) = ctx.mode
+        && state.cursor > 0
+    {
+        state.cursor -= 1;
+    }
+}
+
+pub(super) fn move_down(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::SplitHunk(state) = ctx.mode {
+        let max = state.rows().len().saturating_sub(1);
+        if state.cursor < max {
+            state.cursor += 1;
+        }
+    }
+}
+
+pub(super) fn toggle(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::SplitHunk(state) = ctx.mode else {
+        return;
+    };
+
+    match state.current_row() {
+        Some(SplitHunkRow::File(file_index)) => {
+            let (selected, total) = state.file_selection(file_index);
+            let hunks = state.file_hunks(file_index).collect::<Vec<_>>();
+            let partial = hunks.iter().any(|hunk| state.is_partial(hunk));
+            for hunk in &hunks {
+                state.selected_lines.remove(hunk);
+            }
+            if selected == total && !partial {
+                for hunk in hunks {
+                    state.selected.remove(&hunk);
+                }
+            } else {
+                state.selected.extend(hunks);
+            }
+        }
+        Some(SplitHunkRow::Hunk(hunk)) => state.toggle_hunk(hunk),
+        Some(SplitHunkRow::Line(hunk, line_index)) => state.toggle_line(hunk, line_index),
+        None => {}
+    }
+}
+
+/// Expand or collapse the lines of the hunk under the cursor, keeping the
+/// cursor on that hunk
+pub(super) fn toggle_lines(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::SplitHunk(state) = ctx.mode else {
+        return;
+    };
+    let hunk = match state.current_row() {
+        Some(SplitHunkRow::Hunk(hunk) | SplitHunkRow::Line(hunk, _)) => hunk,
+        Some(SplitHunkRow::File(_)) | None => return,
+    };
+
+    state.toggle_expanded(hunk);
+    if let Some(cursor) = state
+        .rows()
+        .iter()
+        .position(|row| *row == SplitHunkRow::Hunk(hunk))
+    {
+        state.cursor = cursor;
+    }
+}
+
+pub(super) fn select_all(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::SplitHunk(state) = ctx.mode {
+        let hunks = (0..state.diff.files().len())
+            .flat_map(|file_index| state.file_hunks(file_index))
+            .collect::<Vec<_>>();
+        state.selected.extend(hunks);
+        state.selected_lines.clear();
+    }
+}
+
+pub(super) fn select_none(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::SplitHunk(state) = ctx.mode {
+        state.selected.clear();
+        state.selected_lines.clear();
+    }
+}
+
+pub(super) fn start_message(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::SplitHunk(state) = ctx.mode else {
+        return;
+    };
+
+    if state.selected.is_empty() {
+        ctx.set_status("No hunks selected", MessageKind::Warning);
+        return;
+    }
+
+    state.message = Some(String::new());
+}
+
+pub(super) fn message_char(ctx: &mut ReduceCtx<'_>, ch: char) {
+    if let ModeState::SplitHunk(state) = ctx.mode
+        && let Some(message) = &mut state.message
+    {
+        message.push(ch);
+    }
+}
+
+pub(super) fn message_backspace(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::SplitHunk(state) = ctx.mode
+        && let Some(message) = &mut state.message
+    {
+        message.pop();
+    }
+}
+
+pub(super) fn cancel_message(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::SplitHunk(state) = ctx.mode {
+        state.message = None;
+    }
+}
+
+pub(super) fn confirm(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::SplitHunk(state) = &*ctx.mode else {
+        return;
+    };
+
+    let Some(message) = state
+        .message
+        .as_deref()
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(ToOwned::to_owned)
+    else {
+        ctx.set_status("Commit message is required", MessageKind::Warning);
+        return;
+    };
+
+    let effect = Effect::RunSplitHunk {
+        rev: state.rev.clone(),
+        message,
+        plan: state.plan(),
+    };
+    *ctx.mode = ModeState::Normal;
+    ctx.effects.push(effect);
+    ctx.effects.push(Effect::RefreshTree);
+}
@@ -0,0 +1,101 @@
+use super::super::{Effect, ModeState, ReduceCtx};
+use crate::cmd::jj_tui::state::{MessageKind, StackSyncPhase, StackSyncState};
+use jju_core::stack_sync::StackSyncScope;
+
+pub(super) fn enter(ctx: &mut ReduceCtx<'_>, scope: StackSyncScope) {
+    *ctx.mode = ModeState::StackSync(StackSyncState::new(scope));
+    ctx.effects.push(Effect::LoadStackSyncPlan);
+}
+
+/// Close the overlay, or cancel the sync first while it runs; once cancelled
+/// the overlay closes and the sync winds down in the background
+pub(super) fn exit(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode
+        && state.is_running()
+        && !state.cancelled
+    {
+        state.cancelled = true;
+        ctx.effects.push(Effect::CancelBackground);
+        return;
+    }
+
+    *ctx.mode = ModeState::Normal;
+}
+
+pub(super) fn move_up(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode
+        && state.cursor > 0
+    {
+        state.cursor -= 1;
+    }
+}
+
+pub(super) fn move_down(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode {
+        let max = state.row_count().saturating_sub(1);
+        if state.cursor < max {
+            state.cursor += 1;
+        }
+    }
+}
+
+pub(super) fn toggle(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode
+        && state.phase == StackSyncPhase::Choosing
+        && state.cursor < state.root_count()
+    {
+        state.toggle_root(state.cursor);
+    }
+}
+
+pub(super) fn select_all(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode {
+        state.select_all();
+    }
+}
+
+pub(super) fn select_none(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode {
+        state.select_none();
+    }
+}
+
+pub(super) fn toggle_push(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode {
+        state.push = !state.push;
+    }
+}
+
+pub(super) fn confirm(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::StackSync(state) = ctx.mode else {
+        return;
+    };
+    let Some(plan) = state.plan.clone() else {
+        return;
+    };
+    if state.phase != StackSyncPhase::Choosing {
+        return;
+    }
+
+    // with nothing to rebase the sync still fetches and moves trunk
+    if state.root_count() > 0 && !state.has_selection() {
+        ctx.set_status("No stacks selected", MessageKind::Warning);
+        return;
+    }
+
+    let steps = state.start();
+    ctx.effects.push(Effect::RunStackSync {
+        onto: plan.onto.revision,
+        trunk: plan.trunk,
+        remotes: plan.remotes,
+        steps,
+    });
+}
+
+pub(super) fn toggle_details(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::StackSync(state) = ctx.mode
+        && state.shows_progress()
+    {
+        state.show_details = !state.show_details;
+    }
+}
@@ -0,0 +1,27 @@
+use super::super::{Effect, ModeState, ReduceCtx};
+use crate::cmd::jj_tui::state::ViewPickerState;
+
+pub(super) fn enter(ctx: &mut ReduceCtx<'_>) {
+    *ctx.mode = ModeState::ViewPicker(ViewPickerState::default());
+    ctx.effects.push(Effect::LoadViews);
+}
+
+pub(super) fn move_down(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::ViewPicker(state) = ctx.mode {
+        state.move_down();
+    }
+}
+
+pub(super) fn move_up(ctx: &mut ReduceCtx<'_>) {
+    if let ModeState::ViewPicker(state) = ctx.mode {
+        state.move_up();
+    }
+}
+
+pub(super) fn confirm(ctx: &mut ReduceCtx<'_>) {
+    let ModeState::ViewPicker(state) = ctx.mode else {
+        return;
+    };
+
+    ctx.effects.push(Effect::ApplyView { slot: state.cursor });
+}
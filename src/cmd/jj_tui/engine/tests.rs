@@ -1,10 +1,19 @@
 use super::*;
 use crate::cmd::jj_tui::state::{
-    BookmarkPickerState, BookmarkSelectAction, ClipboardBranchSelectState, MessageKind, ModeState,
+    BookmarkPickerState, BookmarkSelectAction, ClipboardBranchSelectState, ConfirmAction,
+    ConfirmState, MessageKind, ModeState, OperationLogState, SplitHunkRow, SplitHunkState,
+    StackSyncProgress, StackSyncState, StackSyncStepKind, StackSyncStepStatus, ViewPickerState,
 };
 use crate::cmd::jj_tui::test_support::{TestNodeKind, make_tree};
 use crate::cmd::jj_tui::tree::{NeighborhoodExtent, TreeLoadScope};
+use crate::cmd::jj_tui::views::NamedView;
+use crate::jj_lib_helpers::OperationLogEntry;
 use jju_core::interactive::{InteractiveOperation, SquashOperation};
+use jju_core::remote::Remotes;
+use jju_core::stack_sync::{
+    ConflictRollback, StackRootPlan, StackSyncPlan, StackSyncScope, StackSyncTarget,
+};
+use jju_jj::split_hunk::{ParsedDiff, SelectedHunk};
 
 struct TestState {
     tree: TreeState,
@@ -743,6 +752,433 @@ fn test_undo_produces_run_undo_and_refresh() {
     assert!(matches!(effects[1], Effect::RefreshTree));
 }
 
+#[test]
+fn test_absorb_runs_on_current_revision_and_refreshes() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::Absorb);
+
+    assert_eq!(effects.len(), 2);
+    assert!(matches!(effects[0], Effect::RunAbsorb { ref rev } if rev == "aaaa"));
+    assert!(matches!(effects[1], Effect::RefreshTree));
+}
+
+fn operation_log_entry(op_id: &str, is_current: bool) -> OperationLogEntry {
+    OperationLogEntry {
+        op_id: op_id.to_string(),
+        description: format!("describe {op_id}"),
+        timestamp: "just now".to_string(),
+        is_current,
+    }
+}
+
+#[test]
+fn test_enter_operation_log_requests_load() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::EnterOperationLog);
+
+    assert!(matches!(state.mode, ModeState::OperationLog(_)));
+    assert!(
+        effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::LoadOperationLog))
+    );
+}
+
+#[test]
+fn test_operation_log_restore_confirms_before_running() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::OperationLog(OperationLogState {
+        entries: vec![
+            operation_log_entry("op111", true),
+            operation_log_entry("op222", false),
+        ],
+        selected_index: 0,
+    });
+
+    state.reduce(Action::OperationLogDown);
+    state.reduce(Action::OperationLogDown);
+    assert!(matches!(
+        state.mode,
+        ModeState::OperationLog(ref log) if log.selected_index == 1
+    ));
+
+    let effects = state.reduce(Action::EnterConfirmRestoreOperation);
+    assert!(effects.is_empty());
+    assert!(matches!(
+        state.mode,
+        ModeState::Confirming(ref confirm)
+            if confirm.action == ConfirmAction::RestoreOperation { op_id: "op222".to_string() }
+    ));
+
+    let effects = state.reduce(Action::ConfirmYes);
+    assert_eq!(effects.len(), 3);
+    assert!(matches!(effects[0], Effect::SaveOperationForUndo));
+    assert!(matches!(
+        effects[1],
+        Effect::RunRestoreOperation { ref op_id } if op_id == "op222"
+    ));
+    assert!(matches!(effects[2], Effect::RefreshTree));
+}
+
+fn stack_sync_plan(roots: &[&str]) -> StackSyncPlan {
+    StackSyncPlan {
+        trunk: "main".to_string(),
+        onto: StackSyncTarget {
+            revision: "main".to_string(),
+            label: "main".to_string(),
+        },
+        remotes: Remotes::default(),
+        scope: StackSyncScope::All,
+        roots: roots
+            .iter()
+            .map(|change_id| StackRootPlan {
+                change_id: change_id.to_string(),
+                description: String::new(),
+                commits: Vec::new(),
+                landed: None,
+            })
+            .collect(),
+        push_bookmark_after_sync: false,
+        rollback: ConflictRollback::Keep,
+    }
+}
+
+#[test]
+fn test_enter_stack_sync_requests_plan() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::EnterStackSync(StackSyncScope::All));
+
+    assert!(matches!(effects[..], [Effect::LoadStackSyncPlan]));
+    assert!(matches!(
+        state.mode,
+        ModeState::StackSync(ref sync) if sync.scope == StackSyncScope::All
+    ));
+}
+
+#[test]
+fn test_confirm_stack_sync_rebases_only_selected_roots() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    let mut sync = StackSyncState::new(StackSyncScope::All);
+    sync.set_plan(stack_sync_plan(&["root1", "root2"]));
+    state.mode = ModeState::StackSync(sync);
+
+    state.reduce(Action::StackSyncToggle);
+    state.reduce(Action::StackSyncTogglePush);
+    let effects = state.reduce(Action::StackSyncConfirm);
+
+    let [Effect::RunStackSync { onto, steps, .. }] = &effects[..] else {
+        panic!("expected a stack sync, got {effects:?}");
+    };
+    assert_eq!(onto, "main");
+    assert_eq!(
+        steps,
+        &[
+            StackSyncStepKind::Fetch,
+            StackSyncStepKind::SyncTrunk,
+            StackSyncStepKind::Rebase {
+                root: "root2".to_string()
+            },
+            StackSyncStepKind::Cleanup,
+            StackSyncStepKind::Push,
+        ]
+    );
+    assert!(matches!(state.mode, ModeState::StackSync(ref sync) if sync.is_running()));
+}
+
+#[test]
+fn test_stack_sync_abandons_landed_roots_instead_of_rebasing() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    let mut plan = stack_sync_plan(&["root1", "root2"]);
+    plan.roots[0].landed = Some("trunk1".to_string());
+    let mut sync = StackSyncState::new(StackSyncScope::All);
+    sync.set_plan(plan);
+    state.mode = ModeState::StackSync(sync);
+
+    let effects = state.reduce(Action::StackSyncConfirm);
+
+    let [Effect::RunStackSync { steps, .. }] = &effects[..] else {
+        panic!("expected a stack sync, got {effects:?}");
+    };
+    assert_eq!(
+        steps[2..4],
+        [
+            StackSyncStepKind::Abandon {
+                root: "root1".to_string()
+            },
+            StackSyncStepKind::Rebase {
+                root: "root2".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_stack_sync_toggle_cycles_landed_root() {
+    let mut plan = stack_sync_plan(&["root1"]);
+    plan.roots[0].landed = Some("trunk1".to_string());
+    let mut sync = StackSyncState::new(StackSyncScope::All);
+    sync.set_plan(plan);
+    assert!(sync.abandon.contains(&0));
+
+    sync.toggle_root(0);
+    assert!(sync.selected.contains(&0) && sync.abandon.is_empty());
+    sync.toggle_root(0);
+    assert!(!sync.has_selection());
+    sync.toggle_root(0);
+    assert!(sync.abandon.contains(&0));
+}
+
+#[test]
+fn test_confirm_stack_sync_without_selection_warns() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    let mut sync = StackSyncState::new(StackSyncScope::All);
+    sync.set_plan(stack_sync_plan(&["root1"]));
+    state.mode = ModeState::StackSync(sync);
+
+    state.reduce(Action::StackSyncSelectNone);
+    let effects = state.reduce(Action::StackSyncConfirm);
+
+    assert!(matches!(
+        effects[..],
+        [Effect::SetStatus {
+            kind: MessageKind::Warning,
+            ..
+        }]
+    ));
+}
+
+#[test]
+fn test_exit_stack_sync_cancels_while_running() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    let mut sync = StackSyncState::new(StackSyncScope::Current);
+    sync.set_plan(stack_sync_plan(&["root1"]));
+    sync.start();
+    state.mode = ModeState::StackSync(sync);
+
+    let effects = state.reduce(Action::ExitStackSync);
+    assert!(matches!(effects.as_slice(), [Effect::CancelBackground]));
+    if let ModeState::StackSync(ref mut sync) = state.mode {
+        assert!(sync.cancelled);
+        sync.finish();
+        assert_eq!(sync.steps[0].status, StackSyncStepStatus::Skipped);
+        assert_eq!(
+            sync.summary(),
+            ("Stack sync cancelled".to_string(), MessageKind::Warning)
+        );
+    }
+
+    state.reduce(Action::ExitStackSync);
+    assert!(matches!(state.mode, ModeState::Normal));
+}
+
+#[test]
+fn test_stack_sync_finish_skips_pending_steps_and_reports_failure() {
+    let mut sync = StackSyncState::new(StackSyncScope::Current);
+    sync.set_plan(stack_sync_plan(&["root1"]));
+    sync.start();
+    sync.apply_progress(StackSyncProgress {
+        step: 0,
+        status: StackSyncStepStatus::Done(None),
+    });
+    sync.apply_progress(StackSyncProgress {
+        step: 1,
+        status: StackSyncStepStatus::Failed("trunk diverged".to_string()),
+    });
+
+    sync.finish();
+
+    assert_eq!(sync.steps[2].status, StackSyncStepStatus::Skipped);
+    assert_eq!(sync.cursor, 1);
+    assert_eq!(sync.current_error(), Some("trunk diverged"));
+    assert_eq!(sync.summary().1, MessageKind::Error);
+}
+
+#[test]
+fn test_operation_log_restore_current_operation_warns() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::OperationLog(OperationLogState {
+        entries: vec![operation_log_entry("op111", true)],
+        selected_index: 0,
+    });
+
+    let effects = state.reduce(Action::EnterConfirmRestoreOperation);
+
+    assert!(matches!(state.mode, ModeState::OperationLog(_)));
+    assert!(effects.iter().any(|effect| matches!(
+        effect,
+        Effect::SetStatus {
+            kind: MessageKind::Warning,
+            ..
+        }
+    )));
+}
+
+fn split_hunk_state() -> SplitHunkState {
+    let mut state = SplitHunkState::new("aaaa".to_string());
+    state.diff = ParsedDiff::parse(
+        "diff --git a/one.rs b/one.rs\n\
+         @@ -1,1 +1,1 @@\n\
+         -old\n\
+         +new\n\
+         @@ -10,0 +10,1 @@\n\
+         +added\n\
+         diff --git a/two.rs b/two.rs\n\
+         @@ -1,0 +1,1 @@\n\
+         +first\n",
+    );
+    state
+}
+
+#[test]
+fn test_enter_split_hunk_requests_diff_load() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::EnterSplitHunk);
+
+    assert!(matches!(state.mode, ModeState::SplitHunk(ref split) if split.rev == "aaaa"));
+    assert!(
+        effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::LoadSplitHunkDiff))
+    );
+}
+
+#[test]
+fn test_split_hunk_toggle_file_selects_all_its_hunks() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::SplitHunk(split_hunk_state());
+
+    state.reduce(Action::SplitHunkToggle);
+
+    let ModeState::SplitHunk(ref split) = state.mode else {
+        panic!("expected split hunk mode");
+    };
+    assert_eq!(split.file_selection(0), (2, 2));
+    assert_eq!(split.file_selection(1), (0, 1));
+}
+
+#[test]
+fn test_split_hunk_confirm_requires_message() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::SplitHunk(split_hunk_state());
+
+    let effects = state.reduce(Action::SplitHunkStartMessage);
+    assert!(effects.iter().any(|effect| matches!(
+        effect,
+        Effect::SetStatus {
+            kind: MessageKind::Warning,
+            ..
+        }
+    )));
+
+    state.reduce(Action::SplitHunkDown);
+    state.reduce(Action::SplitHunkToggle);
+    state.reduce(Action::SplitHunkStartMessage);
+    let effects = state.reduce(Action::SplitHunkConfirm);
+
+    assert!(matches!(state.mode, ModeState::SplitHunk(_)));
+    assert!(
+        !effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::RunSplitHunk { .. }))
+    );
+}
+
+#[test]
+fn test_split_hunk_confirm_runs_split_with_selected_hunks() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::SplitHunk(split_hunk_state());
+
+    state.reduce(Action::SplitHunkDown);
+    state.reduce(Action::SplitHunkToggle);
+    state.reduce(Action::SplitHunkStartMessage);
+    for ch in "feat: part".chars() {
+        state.reduce(Action::SplitHunkMessageChar(ch));
+    }
+    let effects = state.reduce(Action::SplitHunkConfirm);
+
+    assert!(matches!(state.mode, ModeState::Normal));
+    assert_eq!(effects.len(), 2);
+    assert!(matches!(
+        effects[0],
+        Effect::RunSplitHunk { ref rev, ref message, ref plan }
+            if rev == "aaaa" && message == "feat: part" && plan.selected_count() == 1
+    ));
+    assert!(matches!(effects[1], Effect::RefreshTree));
+}
+
+#[test]
+fn test_split_hunk_toggle_lines_narrows_the_hunk() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::SplitHunk(split_hunk_state());
+    let hunk = SelectedHunk {
+        file_index: 0,
+        hunk_index: 0,
+    };
+
+    state.reduce(Action::SplitHunkDown);
+    state.reduce(Action::SplitHunkToggleLines);
+    state.reduce(Action::SplitHunkDown);
+    state.reduce(Action::SplitHunkToggle);
+
+    let ModeState::SplitHunk(ref split) = state.mode else {
+        panic!("expected split hunk mode");
+    };
+    assert_eq!(split.current_row(), Some(SplitHunkRow::Line(hunk, 0)));
+    assert!(split.is_partial(&hunk));
+    let plan = split.plan();
+    assert!(plan.is_selected(&hunk));
+    assert_eq!(
+        plan.selected_lines(&hunk)
+            .map(|lines| lines.iter().copied().collect::<Vec<_>>()),
+        Some(vec![0])
+    );
+
+    state.reduce(Action::SplitHunkDown);
+    state.reduce(Action::SplitHunkToggle);
+    let ModeState::SplitHunk(ref split) = state.mode else {
+        panic!("expected split hunk mode");
+    };
+    assert!(split.selected.contains(&hunk));
+    assert!(!split.is_partial(&hunk));
+
+    state.reduce(Action::SplitHunkToggleLines);
+    let ModeState::SplitHunk(ref split) = state.mode else {
+        panic!("expected split hunk mode");
+    };
+    assert_eq!(split.current_row(), Some(SplitHunkRow::Hunk(hunk)));
+    assert_eq!(split.rows().len(), 5);
+}
+
+#[test]
+fn test_redo_produces_run_redo_and_refresh() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::Redo);
+
+    assert_eq!(effects.len(), 2);
+    assert!(matches!(effects[0], Effect::RunRedo));
+    assert!(matches!(effects[1], Effect::RefreshTree));
+}
+
 #[test]
 fn test_edit_working_copy_with_empty_tree_shows_error() {
     let tree = make_tree(vec![]);
@@ -805,3 +1241,236 @@ fn test_edit_description_with_empty_tree_shows_error() {
             .any(|effect| matches!(effect, Effect::RunInteractive(_)))
     );
 }
+
+#[test]
+fn test_enter_revset_prompt_prefills_current_revset() {
+    let mut tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    tree.view.revset = Some("mine()".to_string());
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::EnterRevsetPrompt);
+
+    assert!(matches!(effects[..], [Effect::LoadRevsetHistory]));
+    assert!(matches!(
+        state.mode,
+        ModeState::RevsetPrompt(ref prompt) if prompt.input == "mine()"
+    ));
+}
+
+#[test]
+fn test_revset_prompt_confirm_applies_typed_revset() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.reduce(Action::EnterRevsetPrompt);
+
+    for ch in "@-".chars() {
+        state.reduce(Action::RevsetPromptChar(ch));
+    }
+    let effects = state.reduce(Action::RevsetPromptConfirm);
+
+    assert!(matches!(
+        effects[..],
+        [Effect::ApplyRevset { revset: Some(ref revset) }] if revset == "@-"
+    ));
+}
+
+#[test]
+fn test_revset_prompt_confirm_empty_restores_default() {
+    let mut tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    tree.view.revset = Some("x".to_string());
+    let mut state = TestState::new(tree);
+    state.reduce(Action::EnterRevsetPrompt);
+
+    state.reduce(Action::RevsetPromptBackspace);
+    let effects = state.reduce(Action::RevsetPromptConfirm);
+
+    assert!(matches!(
+        effects[..],
+        [Effect::ApplyRevset { revset: None }]
+    ));
+}
+
+#[test]
+fn test_revset_prompt_history_recall_restores_draft() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.reduce(Action::EnterRevsetPrompt);
+    if let ModeState::RevsetPrompt(ref mut prompt) = state.mode {
+        prompt.history = vec!["newer".to_string(), "older".to_string()];
+    }
+    state.reduce(Action::RevsetPromptChar('d'));
+
+    state.reduce(Action::RevsetPromptHistoryPrev);
+    state.reduce(Action::RevsetPromptHistoryPrev);
+    state.reduce(Action::RevsetPromptHistoryPrev);
+    assert!(matches!(state.mode, ModeState::RevsetPrompt(ref prompt) if prompt.input == "older"));
+
+    state.reduce(Action::RevsetPromptHistoryNext);
+    state.reduce(Action::RevsetPromptHistoryNext);
+    assert!(matches!(state.mode, ModeState::RevsetPrompt(ref prompt) if prompt.input == "d"));
+}
+
+#[test]
+fn test_enter_view_picker_requests_views() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::EnterViewPicker);
+
+    assert!(matches!(effects[..], [Effect::LoadViews]));
+    assert!(matches!(state.mode, ModeState::ViewPicker(ref picker) if picker.cursor == 0));
+}
+
+#[test]
+fn test_view_picker_confirm_applies_view_under_cursor() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::ViewPicker(ViewPickerState {
+        views: vec![named_view("mine"), named_view("recent")],
+        cursor: 0,
+    });
+
+    state.reduce(Action::ViewPickerDown);
+    state.reduce(Action::ViewPickerDown);
+    state.reduce(Action::ViewPickerDown);
+    let effects = state.reduce(Action::ViewPickerConfirm);
+
+    assert!(matches!(effects[..], [Effect::ApplyView { slot: 2 }]));
+}
+
+#[test]
+fn test_switch_view_applies_slot() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+
+    let effects = state.reduce(Action::SwitchView(3));
+
+    assert!(matches!(effects[..], [Effect::ApplyView { slot: 3 }]));
+}
+
+#[test]
+fn test_goto_prompt_confirm_resolves_typed_prefix() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.reduce(Action::EnterGotoPrompt);
+
+    for ch in "qpvu".chars() {
+        state.reduce(Action::GotoPromptChar(ch));
+    }
+    let effects = state.reduce(Action::GotoPromptConfirm);
+
+    assert!(matches!(
+        effects[..],
+        [Effect::GotoChange { ref prefix }] if prefix == "qpvu"
+    ));
+}
+
+#[test]
+fn test_goto_prompt_confirm_empty_shows_error() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.reduce(Action::EnterGotoPrompt);
+
+    let effects = state.reduce(Action::GotoPromptConfirm);
+
+    assert!(effects.is_empty());
+    assert!(matches!(state.mode, ModeState::GotoPrompt(ref prompt) if prompt.error.is_some()));
+}
+
+#[test]
+fn test_confirm_load_change_requests_reload() {
+    let tree = make_tree(vec![TestNodeKind::Plain.make_node("aaaa", 0)]);
+    let mut state = TestState::new(tree);
+    state.mode = ModeState::Confirming(ConfirmState {
+        action: ConfirmAction::LoadChange {
+            commit_id: "abc".to_string(),
+            revset: "@ | abc".to_string(),
+        },
+        message: String::new(),
+        revs: vec![],
+    });
+
+    let effects = state.reduce(Action::ConfirmYes);
+
+    assert!(matches!(
+        effects[..],
+        [Effect::LoadChange { ref commit_id, .. }] if commit_id == "abc"
+    ));
+}
+
+fn search_tree() -> TreeState {
+    make_tree(vec![
+        TestNodeKind::Plain.make_node("aaaa", 0),
+        TestNodeKind::Plain.make_node("bbbb", 1),
+        TestNodeKind::Plain.make_node("abab", 2),
+    ])
+}
+
+#[test]
+fn test_search_moves_cursor_to_first_match_while_typing() {
+    let mut state = TestState::new(search_tree());
+    state.tree.view.cursor = 1;
+    state.reduce(Action::EnterSearch);
+
+    state.reduce(Action::SearchChar('a'));
+
+    assert_eq!(state.tree.view.cursor, 2);
+    assert_eq!(state.tree.search_query(), Some("a"));
+}
+
+#[test]
+fn test_cancel_search_restores_previous_search_and_cursor() {
+    let mut state = TestState::new(search_tree());
+    state.reduce(Action::EnterSearch);
+    state.reduce(Action::SearchChar('b'));
+    state.reduce(Action::SearchConfirm);
+    let cursor = state.tree.view.cursor;
+
+    state.reduce(Action::EnterSearch);
+    state.reduce(Action::SearchChar('a'));
+    state.reduce(Action::ExitSearch);
+
+    assert!(matches!(state.mode, ModeState::Normal));
+    assert_eq!(state.tree.search_query(), Some("b"));
+    assert_eq!(state.tree.view.cursor, cursor);
+}
+
+#[test]
+fn test_search_next_wraps_and_clear_search_drops_query() {
+    let mut state = TestState::new(search_tree());
+    state.reduce(Action::EnterSearch);
+    state.reduce(Action::SearchChar('a'));
+    state.reduce(Action::SearchConfirm);
+
+    state.reduce(Action::SearchNext);
+    assert_eq!(state.tree.view.cursor, 2);
+    state.reduce(Action::SearchNext);
+    assert_eq!(state.tree.view.cursor, 0);
+
+    state.reduce(Action::ClearSearch);
+    assert_eq!(state.tree.search_query(), None);
+}
+
+#[test]
+fn test_search_filter_without_search_warns() {
+    let mut state = TestState::new(search_tree());
+
+    let effects = state.reduce(Action::SearchToggleFilter);
+
+    assert!(matches!(
+        effects[..],
+        [Effect::SetStatus {
+            kind: MessageKind::Warning,
+            ..
+        }]
+    ));
+}
+
+fn named_view(name: &str) -> NamedView {
+    NamedView {
+        name: name.to_string(),
+        revset: format!("{name}()"),
+        full_mode: None,
+        neighborhood: None,
+    }
+}
@@ -1,16 +1,18 @@
 use super::super::ActionTemplate::{
-    CenterCursorViewport, NormalEnterConditional, NormalEscConditional, PageDownHalfViewport,
-    PageUpHalfViewport,
+    CenterCursorViewport, NormalEnterConditional, NormalEscConditional, NormalNewConditional,
+    PageDownHalfViewport, PageUpHalfViewport,
 };
 use super::super::ModeId::Normal;
 use super::super::{BindingBehavior, BindingSpec, CommandSpec, KeyDef};
 use super::{chord, fixed, pending_prefix, single};
 use crate::cmd::jj_tui::action::Action;
 use crate::cmd::jj_tui::state::{BookmarkSelectAction, RebaseType};
+use crate::cmd::jj_tui::views::VIEW_SLOTS;
+use jju_core::stack_sync::StackSyncScope;
 use ratatui::crossterm::event::KeyCode;
 
 pub(super) fn commands() -> Vec<CommandSpec> {
-    vec![
+    let mut commands = vec![
         CommandSpec::new(
             Normal,
             "quit",
@@ -138,6 +140,48 @@ pub(super) fn commands() -> Vec<CommandSpec> {
             vec![single(KeyDef::Char('R'))],
         )
         .help("View", "Refresh tree"),
+        CommandSpec::new(
+            Normal,
+            "revset",
+            fixed(Action::EnterRevsetPrompt),
+            vec![single(KeyDef::Char('L'))],
+        )
+        .help("View", "Show a revset"),
+        CommandSpec::new(
+            Normal,
+            "search",
+            fixed(Action::EnterSearch),
+            vec![single(KeyDef::Char('/'))],
+        )
+        .help("Navigation", "Search commits"),
+        CommandSpec::new(
+            Normal,
+            "search_prev",
+            fixed(Action::SearchPrev),
+            vec![single(KeyDef::Char('N'))],
+        )
+        .help("Navigation", "Previous search match"),
+        CommandSpec::new(
+            Normal,
+            "search_filter",
+            fixed(Action::SearchToggleFilter),
+            vec![single(KeyDef::Char('F'))],
+        )
+        .help("Navigation", "Filter to search matches"),
+        CommandSpec::new(
+            Normal,
+            "views",
+            fixed(Action::EnterViewPicker),
+            vec![single(KeyDef::Char('V'))],
+        )
+        .help("View", "Pick a named view"),
+        CommandSpec::new(
+            Normal,
+            "view_default",
+            fixed(Action::SwitchView(0)),
+            vec![single(KeyDef::Char('0'))],
+        )
+        .help("View", "Show the default view"),
         CommandSpec::new(
             Normal,
             "diff",
@@ -162,7 +206,7 @@ pub(super) fn commands() -> Vec<CommandSpec> {
         BindingSpec::new(
             Normal,
             "new",
-            fixed(Action::CreateNewCommit),
+            BindingBehavior::Action(NormalNewConditional),
             vec![single(KeyDef::Char('n'))],
         )
         .help("Edit Operations", "New commit (jj new)"),
@@ -173,6 +217,20 @@ pub(super) fn commands() -> Vec<CommandSpec> {
             vec![single(KeyDef::Char('c'))],
         )
         .help("Edit Operations", "Commit changes (jj commit)"),
+        BindingSpec::new(
+            Normal,
+            "split_hunk",
+            fixed(Action::EnterSplitHunk),
+            vec![single(KeyDef::Char('h'))],
+        )
+        .help("Edit Operations", "Split hunks into new commit"),
+        BindingSpec::new(
+            Normal,
+            "absorb",
+            fixed(Action::Absorb),
+            vec![single(KeyDef::Char('A'))],
+        )
+        .help("Edit Operations", "Absorb hunks into ancestors"),
         BindingSpec::new(
             Normal,
             "toggle",
@@ -231,6 +289,20 @@ pub(super) fn commands() -> Vec<CommandSpec> {
             vec![single(KeyDef::Char('u'))],
         )
         .help("Rebase", "Undo last operation"),
+        BindingSpec::new(
+            Normal,
+            "redo",
+            fixed(Action::Redo),
+            vec![single(KeyDef::Ctrl('r'))],
+        )
+        .help("Rebase", "Redo undone operation"),
+        BindingSpec::new(
+            Normal,
+            "operation_log",
+            fixed(Action::EnterOperationLog),
+            vec![single(KeyDef::Char('o'))],
+        )
+        .help("Rebase", "Operation log (restore)"),
         BindingSpec::new(
             Normal,
             "push",
@@ -295,10 +367,18 @@ pub(super) fn commands() -> Vec<CommandSpec> {
         BindingSpec::new(
             Normal,
             "stack_sync",
-            fixed(Action::EnterConfirmStackSync),
+            fixed(Action::EnterStackSync(StackSyncScope::Current)),
             vec![single(KeyDef::Char('S'))],
         )
         .help("Bookmarks & Git", "Stack sync (fetch, rebase, clean up)"),
+        BindingSpec::new(
+            Normal,
+            "stack_sync_all",
+            fixed(Action::EnterStackSync(StackSyncScope::All)),
+            vec![chord('g', KeyDef::Char('S'))],
+        )
+        .help("Bookmarks & Git", "Stack sync every mutable stack")
+        .prefix_title("git"),
         BindingSpec::new(
             Normal,
             "conflicts",
@@ -394,6 +474,14 @@ pub(super) fn commands() -> Vec<CommandSpec> {
         )
         .help("Navigation", "Zoom neighborhood in")
         .prefix_title("nav"),
+        BindingSpec::new(
+            Normal,
+            "goto",
+            fixed(Action::EnterGotoPrompt),
+            vec![chord('z', KeyDef::Char('g'))],
+        )
+        .help("Navigation", "Go to change id")
+        .prefix_title("nav"),
         BindingSpec::new(
             Normal,
             "set",
@@ -410,5 +498,33 @@ pub(super) fn commands() -> Vec<CommandSpec> {
         )
         .help("Bookmarks & Git", "Delete bookmark")
         .prefix_title("bookmark"),
-    ]
+    ];
+    commands.extend(view_slot_commands());
+    commands
+}
+
+const VIEW_SLOT_LABELS: [&str; VIEW_SLOTS] = [
+    "view_1", "view_2", "view_3", "view_4", "view_5", "view_6", "view_7", "view_8", "view_9",
+];
+
+/// `1`-`9` switch straight to the configured views, in config order
+fn view_slot_commands() -> Vec<CommandSpec> {
+    VIEW_SLOT_LABELS
+        .iter()
+        .zip('1'..='9')
+        .enumerate()
+        .map(|(index, (label, key))| {
+            let command = CommandSpec::new(
+                Normal,
+                label,
+                fixed(Action::SwitchView(index + 1)),
+                vec![single(KeyDef::Char(key))],
+            );
+            if index == 0 {
+                command.help("View", "Switch to named views 1-9")
+            } else {
+                command
+            }
+        })
+        .collect()
 }
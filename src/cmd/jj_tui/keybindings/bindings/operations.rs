@@ -1,7 +1,11 @@
-use super::super::ActionTemplate::{BookmarkFilterChar, PushSelectFilterChar};
+use super::super::ActionTemplate::{
+    BookmarkFilterChar, GotoPromptChar, PushSelectFilterChar, RevsetPromptChar, SearchChar,
+    SplitHunkMessageChar,
+};
 use super::super::ModeId::{
-    BookmarkPicker, BookmarkSelect, ClipboardBranchSelect, Conflicts, MovingBookmark, PushSelect,
-    Rebase, Squash,
+    BookmarkPicker, BookmarkSelect, ClipboardBranchSelect, Conflicts, GotoPrompt, MovingBookmark,
+    OperationLog, PushSelect, Rebase, RevsetPrompt, Search, SplitHunk, SplitHunkMessage, Squash,
+    StackSync, StackSyncProgress, ViewPicker,
 };
 use super::super::{BindingBehavior, BindingSpec, CommandSpec, KeyDef};
 use super::{fixed, single};
@@ -247,6 +251,303 @@ pub(super) fn commands() -> Vec<CommandSpec> {
             fixed(Action::ExitConflicts),
             vec![single(KeyDef::Char('q')), single(KeyDef::Key(KeyCode::Esc))],
         ),
+        BindingSpec::new(
+            OperationLog,
+            "down",
+            fixed(Action::OperationLogDown),
+            vec![
+                single(KeyDef::Char('j')),
+                single(KeyDef::Key(KeyCode::Down)),
+            ],
+        ),
+        BindingSpec::new(
+            OperationLog,
+            "up",
+            fixed(Action::OperationLogUp),
+            vec![single(KeyDef::Char('k')), single(KeyDef::Key(KeyCode::Up))],
+        ),
+        BindingSpec::new(
+            OperationLog,
+            "restore",
+            fixed(Action::EnterConfirmRestoreOperation),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            OperationLog,
+            "exit",
+            fixed(Action::ExitOperationLog),
+            vec![single(KeyDef::Char('q')), single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "down",
+            fixed(Action::SplitHunkDown),
+            vec![
+                single(KeyDef::Char('j')),
+                single(KeyDef::Key(KeyCode::Down)),
+            ],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "up",
+            fixed(Action::SplitHunkUp),
+            vec![single(KeyDef::Char('k')), single(KeyDef::Key(KeyCode::Up))],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "toggle",
+            fixed(Action::SplitHunkToggle),
+            vec![single(KeyDef::Char(' '))],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "lines",
+            fixed(Action::SplitHunkToggleLines),
+            vec![single(KeyDef::Key(KeyCode::Tab)), single(KeyDef::Char('l'))],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "all",
+            fixed(Action::SplitHunkSelectAll),
+            vec![single(KeyDef::Char('a'))],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "none",
+            fixed(Action::SplitHunkSelectNone),
+            vec![single(KeyDef::Char('n'))],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "confirm",
+            fixed(Action::SplitHunkStartMessage),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            SplitHunk,
+            "cancel",
+            fixed(Action::ExitSplitHunk),
+            vec![single(KeyDef::Char('q')), single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            SplitHunkMessage,
+            "confirm",
+            fixed(Action::SplitHunkConfirm),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            SplitHunkMessage,
+            "cancel",
+            fixed(Action::SplitHunkCancelMessage),
+            vec![single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            SplitHunkMessage,
+            "backspace",
+            fixed(Action::SplitHunkMessageBackspace),
+            vec![single(KeyDef::Key(KeyCode::Backspace))],
+        ),
+        BindingSpec::new(
+            SplitHunkMessage,
+            "type",
+            BindingBehavior::Action(SplitHunkMessageChar),
+            vec![single(KeyDef::AnyChar)],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "down",
+            fixed(Action::StackSyncDown),
+            vec![
+                single(KeyDef::Char('j')),
+                single(KeyDef::Key(KeyCode::Down)),
+            ],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "up",
+            fixed(Action::StackSyncUp),
+            vec![single(KeyDef::Char('k')), single(KeyDef::Key(KeyCode::Up))],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "toggle",
+            fixed(Action::StackSyncToggle),
+            vec![single(KeyDef::Char(' '))],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "all",
+            fixed(Action::StackSyncSelectAll),
+            vec![single(KeyDef::Char('a'))],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "none",
+            fixed(Action::StackSyncSelectNone),
+            vec![single(KeyDef::Char('n'))],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "push",
+            fixed(Action::StackSyncTogglePush),
+            vec![single(KeyDef::Char('p'))],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "confirm",
+            fixed(Action::StackSyncConfirm),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            StackSync,
+            "cancel",
+            fixed(Action::ExitStackSync),
+            vec![single(KeyDef::Char('q')), single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            StackSyncProgress,
+            "down",
+            fixed(Action::StackSyncDown),
+            vec![
+                single(KeyDef::Char('j')),
+                single(KeyDef::Key(KeyCode::Down)),
+            ],
+        ),
+        BindingSpec::new(
+            StackSyncProgress,
+            "up",
+            fixed(Action::StackSyncUp),
+            vec![single(KeyDef::Char('k')), single(KeyDef::Key(KeyCode::Up))],
+        ),
+        BindingSpec::new(
+            StackSyncProgress,
+            "details",
+            fixed(Action::StackSyncToggleDetails),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            StackSyncProgress,
+            "close",
+            fixed(Action::ExitStackSync),
+            vec![single(KeyDef::Char('q')), single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            RevsetPrompt,
+            "confirm",
+            fixed(Action::RevsetPromptConfirm),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            RevsetPrompt,
+            "cancel",
+            fixed(Action::ExitRevsetPrompt),
+            vec![single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            RevsetPrompt,
+            "up",
+            fixed(Action::RevsetPromptHistoryPrev),
+            vec![single(KeyDef::Key(KeyCode::Up))],
+        ),
+        BindingSpec::new(
+            RevsetPrompt,
+            "down",
+            fixed(Action::RevsetPromptHistoryNext),
+            vec![single(KeyDef::Key(KeyCode::Down))],
+        ),
+        BindingSpec::new(
+            RevsetPrompt,
+            "backspace",
+            fixed(Action::RevsetPromptBackspace),
+            vec![single(KeyDef::Key(KeyCode::Backspace))],
+        ),
+        BindingSpec::new(
+            RevsetPrompt,
+            "type",
+            BindingBehavior::Action(RevsetPromptChar),
+            vec![single(KeyDef::AnyChar)],
+        ),
+        BindingSpec::new(
+            GotoPrompt,
+            "confirm",
+            fixed(Action::GotoPromptConfirm),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            GotoPrompt,
+            "cancel",
+            fixed(Action::ExitGotoPrompt),
+            vec![single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            GotoPrompt,
+            "backspace",
+            fixed(Action::GotoPromptBackspace),
+            vec![single(KeyDef::Key(KeyCode::Backspace))],
+        ),
+        BindingSpec::new(
+            GotoPrompt,
+            "type",
+            BindingBehavior::Action(GotoPromptChar),
+            vec![single(KeyDef::AnyChar)],
+        ),
+        BindingSpec::new(
+            ViewPicker,
+            "down",
+            fixed(Action::ViewPickerDown),
+            vec![
+                single(KeyDef::Char('j')),
+                single(KeyDef::Key(KeyCode::Down)),
+            ],
+        ),
+        BindingSpec::new(
+            ViewPicker,
+            "up",
+            fixed(Action::ViewPickerUp),
+            vec![single(KeyDef::Char('k')), single(KeyDef::Key(KeyCode::Up))],
+        ),
+        BindingSpec::new(
+            ViewPicker,
+            "confirm",
+            fixed(Action::ViewPickerConfirm),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            ViewPicker,
+            "cancel",
+            fixed(Action::ExitViewPicker),
+            vec![single(KeyDef::Char('q')), single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            Search,
+            "confirm",
+            fixed(Action::SearchConfirm),
+            vec![single(KeyDef::Key(KeyCode::Enter))],
+        ),
+        BindingSpec::new(
+            Search,
+            "cancel",
+            fixed(Action::ExitSearch),
+            vec![single(KeyDef::Key(KeyCode::Esc))],
+        ),
+        BindingSpec::new(
+            Search,
+            "toggle",
+            fixed(Action::SearchPromptToggleFilter),
+            vec![single(KeyDef::Key(KeyCode::Tab))],
+        ),
+        BindingSpec::new(
+            Search,
+            "backspace",
+            fixed(Action::SearchBackspace),
+            vec![single(KeyDef::Key(KeyCode::Backspace))],
+        ),
+        BindingSpec::new(
+            Search,
+            "type",
+            BindingBehavior::Action(SearchChar),
+            vec![single(KeyDef::AnyChar)],
+        ),
         BindingSpec::new(
             ClipboardBranchSelect,
             "cancel",
@@ -20,6 +20,7 @@ pub mod command_id {
     pub const NEIGHBORHOOD: &str = "neighborhood";
     pub const NEIGHBORHOOD_LESS: &str = "neighborhood_less";
     pub const NEIGHBORHOOD_MORE: &str = "neighborhood_more";
+    pub const NEW: &str = "new";
     pub const PAGE_DOWN: &str = "page_down";
     pub const PAGE_UP: &str = "page_up";
     pub const PUSH: &str = "push";
@@ -27,12 +28,17 @@ pub mod command_id {
     pub const REBASE_DESC: &str = "rebase_desc";
     pub const REBASE_SINGLE: &str = "rebase_single";
     pub const RESOLVE: &str = "resolve";
+    pub const RESTORE: &str = "restore";
     pub const RUN: &str = "run";
     pub const SCROLL_DOWN: &str = "scroll_down";
     pub const SCROLL_UP: &str = "scroll_up";
+    pub const SEARCH: &str = "search";
+    pub const SEARCH_FILTER: &str = "search_filter";
+    pub const SEARCH_PREV: &str = "search_prev";
     pub const SELECT: &str = "select";
     pub const STACK_SYNC: &str = "stack_sync";
     pub const TOGGLE: &str = "toggle";
+    pub const LINES: &str = "lines";
     pub const TOP: &str = "top";
     pub const TRUNK_SINGLE: &str = "trunk_single";
     pub const UP: &str = "up";
@@ -44,6 +50,7 @@ pub mod command_id {
     pub const BRANCHES: &str = "branches";
     pub const CLOSE: &str = "close";
     pub const COPY: &str = "copy";
+    pub const DETAILS: &str = "details";
     pub const NAV: &str = "nav";
     pub const NO: &str = "no";
     pub const YES: &str = "yes";
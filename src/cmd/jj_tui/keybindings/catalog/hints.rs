@@ -45,6 +45,7 @@ pub(crate) enum HintSpec {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum HintScenario {
     NormalSelection,
+    NormalSearch,
     NormalNeighborhood,
     NormalFocus,
     NormalBookmarked,
@@ -61,11 +62,21 @@ pub(crate) enum HintScenario {
     ClipboardBranchSelect,
     PushSelect,
     Conflicts,
+    OperationLog,
+    SplitHunk,
+    SplitHunkMessage,
+    StackSync,
+    StackSyncProgress,
+    RevsetPrompt,
+    ViewPicker,
+    Search,
+    GotoPrompt,
 }
 
 pub(crate) fn hint_specs(scenario: HintScenario) -> &'static [HintSpec] {
     match scenario {
         HintScenario::NormalSelection => normal::NORMAL_SELECTION_HINTS,
+        HintScenario::NormalSearch => normal::NORMAL_SEARCH_HINTS,
         HintScenario::NormalNeighborhood => normal::NORMAL_NEIGHBORHOOD_HINTS,
         HintScenario::NormalFocus => normal::NORMAL_FOCUS_HINTS,
         HintScenario::NormalBookmarked => normal::NORMAL_BOOKMARKED_HINTS,
@@ -82,5 +93,14 @@ pub(crate) fn hint_specs(scenario: HintScenario) -> &'static [HintSpec] {
         HintScenario::ClipboardBranchSelect => operations::CLIPBOARD_BRANCH_SELECT_HINTS,
         HintScenario::PushSelect => operations::PUSH_SELECT_HINTS,
         HintScenario::Conflicts => operations::CONFLICTS_HINTS,
+        HintScenario::OperationLog => operations::OPERATION_LOG_HINTS,
+        HintScenario::SplitHunk => operations::SPLIT_HUNK_HINTS,
+        HintScenario::SplitHunkMessage => operations::SPLIT_HUNK_MESSAGE_HINTS,
+        HintScenario::StackSync => operations::STACK_SYNC_HINTS,
+        HintScenario::StackSyncProgress => operations::STACK_SYNC_PROGRESS_HINTS,
+        HintScenario::RevsetPrompt => operations::REVSET_PROMPT_HINTS,
+        HintScenario::ViewPicker => operations::VIEW_PICKER_HINTS,
+        HintScenario::Search => operations::SEARCH_HINTS,
+        HintScenario::GotoPrompt => operations::GOTO_PROMPT_HINTS,
     }
 }
@@ -16,6 +16,26 @@ pub(super) const NORMAL_SELECTION_HINTS: &[HintSpec] = &[
     },
 ];
 
+pub(super) const NORMAL_SEARCH_HINTS: &[HintSpec] = &[
+    HintSpec::CommandPair {
+        left: cmd::NEW,
+        right: cmd::SEARCH_PREV,
+        value: "match",
+    },
+    HintSpec::Command {
+        label: cmd::SEARCH_FILTER,
+        value: "filter",
+    },
+    HintSpec::Command {
+        label: cmd::SEARCH,
+        value: "search",
+    },
+    HintSpec::Command {
+        label: cmd::ESC,
+        value: "clear",
+    },
+];
+
 pub(super) const NORMAL_NEIGHBORHOOD_HINTS: &[HintSpec] = &[
     HintSpec::CommandAnyPending {
         label: cmd::NEIGHBORHOOD,
@@ -137,3 +137,168 @@ pub(super) const CONFLICTS_HINTS: &[HintSpec] = &[
         value: "exit",
     },
 ];
+
+pub(super) const OPERATION_LOG_HINTS: &[HintSpec] = &[
+    HintSpec::CommandPair {
+        left: cmd::DOWN,
+        right: cmd::UP,
+        value: "nav",
+    },
+    HintSpec::Command {
+        label: cmd::RESTORE,
+        value: "restore",
+    },
+    HintSpec::LabelKeys {
+        label: cmd::EXIT,
+        value: "exit",
+    },
+];
+
+pub(super) const SPLIT_HUNK_HINTS: &[HintSpec] = &[
+    HintSpec::CommandPair {
+        left: cmd::DOWN,
+        right: cmd::UP,
+        value: "nav",
+    },
+    HintSpec::Command {
+        label: cmd::TOGGLE,
+        value: "toggle",
+    },
+    HintSpec::Command {
+        label: cmd::LINES,
+        value: "lines",
+    },
+    HintSpec::Command {
+        label: cmd::ALL,
+        value: "all",
+    },
+    HintSpec::Command {
+        label: cmd::NONE,
+        value: "none",
+    },
+    HintSpec::Command {
+        label: cmd::CONFIRM,
+        value: "message",
+    },
+    HintSpec::LabelKeys {
+        label: cmd::CANCEL,
+        value: "cancel",
+    },
+];
+
+pub(super) const SPLIT_HUNK_MESSAGE_HINTS: &[HintSpec] = &[
+    HintSpec::Command {
+        label: cmd::CONFIRM,
+        value: "split",
+    },
+    HintSpec::Command {
+        label: cmd::CANCEL,
+        value: "back",
+    },
+];
+
+pub(super) const STACK_SYNC_HINTS: &[HintSpec] = &[
+    HintSpec::CommandPair {
+        left: cmd::DOWN,
+        right: cmd::UP,
+        value: "nav",
+    },
+    HintSpec::Command {
+        label: cmd::TOGGLE,
+        value: "toggle",
+    },
+    HintSpec::Command {
+        label: cmd::ALL,
+        value: "all",
+    },
+    HintSpec::Command {
+        label: cmd::NONE,
+        value: "none",
+    },
+    HintSpec::Command {
+        label: cmd::PUSH,
+        value: "push",
+    },
+    HintSpec::Command {
+        label: cmd::CONFIRM,
+        value: "sync",
+    },
+    HintSpec::LabelKeys {
+        label: cmd::CANCEL,
+        value: "cancel",
+    },
+];
+
+pub(super) const STACK_SYNC_PROGRESS_HINTS: &[HintSpec] = &[
+    HintSpec::CommandPair {
+        left: cmd::DOWN,
+        right: cmd::UP,
+        value: "nav",
+    },
+    HintSpec::Command {
+        label: cmd::DETAILS,
+        value: "details",
+    },
+    HintSpec::LabelKeys {
+        label: cmd::CLOSE,
+        value: "close",
+    },
+];
+
+pub(super) const REVSET_PROMPT_HINTS: &[HintSpec] = &[
+    HintSpec::CommandPair {
+        left: cmd::UP,
+        right: cmd::DOWN,
+        value: "history",
+    },
+    HintSpec::Command {
+        label: cmd::CONFIRM,
+        value: "show",
+    },
+    HintSpec::Command {
+        label: cmd::CANCEL,
+        value: "cancel",
+    },
+];
+
+pub(super) const VIEW_PICKER_HINTS: &[HintSpec] = &[
+    HintSpec::CommandPair {
+        left: cmd::DOWN,
+        right: cmd::UP,
+        value: "nav",
+    },
+    HintSpec::Command {
+        label: cmd::CONFIRM,
+        value: "show",
+    },
+    HintSpec::Command {
+        label: cmd::CANCEL,
+        value: "cancel",
+    },
+];
+
+pub(super) const SEARCH_HINTS: &[HintSpec] = &[
+    HintSpec::Command {
+        label: cmd::TOGGLE,
+        value: "filter",
+    },
+    HintSpec::Command {
+        label: cmd::CONFIRM,
+        value: "done",
+    },
+    HintSpec::Command {
+        label: cmd::CANCEL,
+        value: "cancel",
+    },
+];
+
+pub(super) const GOTO_PROMPT_HINTS: &[HintSpec] = &[
+    HintSpec::Command {
+        label: cmd::CONFIRM,
+        value: "go",
+    },
+    HintSpec::Command {
+        label: cmd::CANCEL,
+        value: "cancel",
+    },
+];
@@ -76,6 +76,15 @@ fn parse_mode(mode: &str) -> Result<ModeId> {
         "bookmark_picker" => Ok(ModeId::BookmarkPicker),
         "push_select" => Ok(ModeId::PushSelect),
         "conflicts" => Ok(ModeId::Conflicts),
+        "operation_log" => Ok(ModeId::OperationLog),
+        "split_hunk" => Ok(ModeId::SplitHunk),
+        "split_hunk_message" => Ok(ModeId::SplitHunkMessage),
+        "stack_sync" => Ok(ModeId::StackSync),
+        "stack_sync_progress" => Ok(ModeId::StackSyncProgress),
+        "revset_prompt" => Ok(ModeId::RevsetPrompt),
+        "view_picker" => Ok(ModeId::ViewPicker),
+        "search" => Ok(ModeId::Search),
+        "goto_prompt" => Ok(ModeId::GotoPrompt),
         _ => bail!("unknown keybinding mode `{mode}`"),
     }
 }
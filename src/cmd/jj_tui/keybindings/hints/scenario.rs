@@ -6,6 +6,7 @@ pub struct StatusHintContext {
     pub has_selection: bool,
     pub has_focus: bool,
     pub neighborhood_active: bool,
+    pub search_active: bool,
     pub current_has_bookmark: bool,
     pub rebase_allow_branches: Option<bool>,
 }
@@ -13,6 +14,7 @@ pub struct StatusHintContext {
 pub(super) fn scenario_for_context(ctx: &StatusHintContext) -> HintScenario {
     match ctx.mode {
         ModeId::Normal if ctx.has_selection => HintScenario::NormalSelection,
+        ModeId::Normal if ctx.search_active => HintScenario::NormalSearch,
         ModeId::Normal if ctx.neighborhood_active => HintScenario::NormalNeighborhood,
         ModeId::Normal if ctx.has_focus => HintScenario::NormalFocus,
         ModeId::Normal if ctx.current_has_bookmark => HintScenario::NormalBookmarked,
@@ -29,5 +31,14 @@ pub(super) fn scenario_for_context(ctx: &StatusHintContext) -> HintScenario {
         ModeId::ClipboardBranchSelect => HintScenario::ClipboardBranchSelect,
         ModeId::PushSelect => HintScenario::PushSelect,
         ModeId::Conflicts => HintScenario::Conflicts,
+        ModeId::OperationLog => HintScenario::OperationLog,
+        ModeId::SplitHunk => HintScenario::SplitHunk,
+        ModeId::SplitHunkMessage => HintScenario::SplitHunkMessage,
+        ModeId::StackSync => HintScenario::StackSync,
+        ModeId::StackSyncProgress => HintScenario::StackSyncProgress,
+        ModeId::RevsetPrompt => HintScenario::RevsetPrompt,
+        ModeId::ViewPicker => HintScenario::ViewPicker,
+        ModeId::Search => HintScenario::Search,
+        ModeId::GotoPrompt => HintScenario::GotoPrompt,
     }
 }
@@ -11,6 +11,7 @@ fn ctx(mode: ModeId) -> StatusHintContext {
         has_selection: false,
         has_focus: false,
         neighborhood_active: false,
+        search_active: false,
         current_has_bookmark: false,
         rebase_allow_branches: None,
     }
@@ -80,6 +80,15 @@ pub(super) fn mode_name(mode: ModeId) -> &'static str {
         ModeId::ClipboardBranchSelect => "clipboard_branch_select",
         ModeId::PushSelect => "push_select",
         ModeId::Conflicts => "conflicts",
+        ModeId::OperationLog => "operation_log",
+        ModeId::SplitHunk => "split_hunk",
+        ModeId::SplitHunkMessage => "split_hunk_message",
+        ModeId::StackSync => "stack_sync",
+        ModeId::StackSyncProgress => "stack_sync_progress",
+        ModeId::RevsetPrompt => "revset_prompt",
+        ModeId::ViewPicker => "view_picker",
+        ModeId::Search => "search",
+        ModeId::GotoPrompt => "goto_prompt",
     }
 }
 
@@ -150,6 +150,11 @@ keys = [["X", "r"]]
 mode = "normal"
 command = "create_pr"
 keys = [["X", "p"]]
+
+[[binding]]
+mode = "normal"
+command = "stack_sync_all"
+keys = [["X", "S"]]
 "#,
     );
 
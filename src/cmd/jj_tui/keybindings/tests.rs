@@ -25,6 +25,8 @@ fn ctx<'a>(
         neighborhood_active: false,
         has_neighborhood_history: false,
         can_enter_neighborhood_path: false,
+        search_active: false,
+        effect_running: false,
     }
 }
 
@@ -90,12 +92,54 @@ fn test_dispatch_normal_esc_exits_neighborhood_path() {
             neighborhood_active: true,
             has_neighborhood_history: true,
             can_enter_neighborhood_path: false,
+            search_active: false,
+            effect_running: false,
         },
         KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
     );
     assert_eq!(action, Action::ExitNeighborhoodPath);
 }
 
+#[test]
+fn test_dispatch_n_jumps_to_next_match_while_searching() {
+    let mode = ModeState::Normal;
+    let searching = ControllerContext {
+        search_active: true,
+        ..ctx(&mode, None, 20, false, false)
+    };
+    let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+
+    assert_eq!(handle_key(&searching, n), Action::SearchNext);
+    assert_eq!(
+        handle_key(&searching, KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE)),
+        Action::ClearSearch
+    );
+    assert_eq!(
+        handle_key(&ctx(&mode, None, 20, false, false), n),
+        Action::CreateNewCommit
+    );
+}
+
+#[test]
+fn test_dispatch_esc_cancels_running_effect_first() {
+    let mode = ModeState::Normal;
+    let running = ControllerContext {
+        effect_running: true,
+        search_active: true,
+        ..ctx(&mode, None, 20, false, false)
+    };
+    let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+
+    assert_eq!(handle_key(&running, esc), Action::CancelRunningEffect);
+    assert_eq!(
+        handle_key(
+            &running,
+            KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)
+        ),
+        Action::MoveCursorDown
+    );
+}
+
 #[test]
 fn test_dispatch_enter_opens_neighborhood_preview() {
     let mode = ModeState::Normal;
@@ -109,6 +153,8 @@ fn test_dispatch_enter_opens_neighborhood_preview() {
             neighborhood_active: true,
             has_neighborhood_history: false,
             can_enter_neighborhood_path: true,
+            search_active: false,
+            effect_running: false,
         },
         KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
     );
@@ -235,6 +281,7 @@ fn test_hint_contains_d_desc() {
         has_selection: false,
         has_focus: false,
         neighborhood_active: false,
+        search_active: false,
         current_has_bookmark: false,
         rebase_allow_branches: None,
     });
@@ -251,6 +298,7 @@ fn test_hint_neighborhood_uses_zn_full() {
         has_selection: false,
         has_focus: false,
         neighborhood_active: true,
+        search_active: false,
         current_has_bookmark: false,
         rebase_allow_branches: None,
     });
@@ -271,6 +319,7 @@ fn test_hint_bookmark_picker_uses_arrows() {
         has_selection: false,
         has_focus: false,
         neighborhood_active: false,
+        search_active: false,
         current_has_bookmark: false,
         rebase_allow_branches: None,
     });
@@ -287,6 +336,7 @@ fn test_hint_diff_has_zt_zb() {
         has_selection: false,
         has_focus: false,
         neighborhood_active: false,
+        search_active: false,
         current_has_bookmark: false,
         rebase_allow_branches: None,
     });
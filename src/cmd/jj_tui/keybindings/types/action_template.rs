@@ -10,7 +10,12 @@ pub enum ActionTemplate {
     BookmarkFilterChar,
     PushSelectFilterChar,
     ClipboardBranchSelectChar,
+    SplitHunkMessageChar,
+    RevsetPromptChar,
+    SearchChar,
+    GotoPromptChar,
     NormalEscConditional,
+    NormalNewConditional,
 }
 
 impl ActionTemplate {
@@ -38,8 +43,18 @@ impl ActionTemplate {
             ActionTemplate::ClipboardBranchSelectChar => {
                 Action::CopyBranchSelection(captured.unwrap_or(' ').to_ascii_lowercase())
             }
+            ActionTemplate::SplitHunkMessageChar => {
+                Action::SplitHunkMessageChar(captured.unwrap_or(' '))
+            }
+            ActionTemplate::RevsetPromptChar => Action::RevsetPromptChar(captured.unwrap_or(' ')),
+            ActionTemplate::SearchChar => Action::SearchChar(captured.unwrap_or(' ')),
+            ActionTemplate::GotoPromptChar => Action::GotoPromptChar(captured.unwrap_or(' ')),
             ActionTemplate::NormalEscConditional => {
-                if ctx.has_focus {
+                if ctx.effect_running {
+                    Action::CancelRunningEffect
+                } else if ctx.search_active {
+                    Action::ClearSearch
+                } else if ctx.has_focus {
                     Action::Unfocus
                 } else if ctx.has_selection {
                     Action::ClearSelection
@@ -49,6 +64,14 @@ impl ActionTemplate {
                     Action::Noop
                 }
             }
+            // `n` jumps between matches while a search is active
+            ActionTemplate::NormalNewConditional => {
+                if ctx.search_active {
+                    Action::SearchNext
+                } else {
+                    Action::CreateNewCommit
+                }
+            }
         }
     }
 }
@@ -15,6 +15,15 @@ pub enum ModeId {
     ClipboardBranchSelect,
     PushSelect,
     Conflicts,
+    OperationLog,
+    SplitHunk,
+    SplitHunkMessage,
+    StackSync,
+    StackSyncProgress,
+    RevsetPrompt,
+    ViewPicker,
+    Search,
+    GotoPrompt,
 }
 
 pub fn mode_id_from_state(mode: &ModeState) -> ModeId {
@@ -32,5 +41,14 @@ pub fn mode_id_from_state(mode: &ModeState) -> ModeId {
         ModeState::ClipboardBranchSelect(_) => ModeId::ClipboardBranchSelect,
         ModeState::PushSelect(_) => ModeId::PushSelect,
         ModeState::Conflicts(_) => ModeId::Conflicts,
+        ModeState::OperationLog(_) => ModeId::OperationLog,
+        ModeState::SplitHunk(state) if state.is_editing_message() => ModeId::SplitHunkMessage,
+        ModeState::SplitHunk(_) => ModeId::SplitHunk,
+        ModeState::StackSync(state) if state.shows_progress() => ModeId::StackSyncProgress,
+        ModeState::StackSync(_) => ModeId::StackSync,
+        ModeState::RevsetPrompt(_) => ModeId::RevsetPrompt,
+        ModeState::ViewPicker(_) => ModeId::ViewPicker,
+        ModeState::Search(_) => ModeId::Search,
+        ModeState::GotoPrompt(_) => ModeId::GotoPrompt,
     }
 }
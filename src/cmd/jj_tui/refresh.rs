@@ -7,15 +7,47 @@ use super::tree::TreeState;
 use crate::jj_lib_helpers::JjRepo;
 use remap::TreeRefreshRemapper;
 
-/// Reload the tree while preserving cursor and focus state when possible
+/// Snapshot the working copy and reload the tree while preserving cursor and
+/// focus state when possible, returning a warning when the snapshot failed
 pub fn refresh_tree(
     tree: &mut TreeState,
     diff_stats_cache: &mut std::collections::HashMap<String, DiffStats>,
+) -> eyre::Result<Option<String>> {
+    let mut jj_repo = JjRepo::load(None)?;
+    let warning = snapshot_working_copy(&mut jj_repo);
+    let revset = tree.view.revset.clone();
+    reload_from(&jj_repo, tree, diff_stats_cache, revset.as_deref())?;
+    Ok(warning)
+}
+
+/// Reload the tree with another revset, `None` restoring the default one
+pub fn reload_tree(
+    tree: &mut TreeState,
+    diff_stats_cache: &mut std::collections::HashMap<String, DiffStats>,
+    revset: Option<&str>,
 ) -> eyre::Result<()> {
-    let remapper = TreeRefreshRemapper::capture(tree);
     let jj_repo = JjRepo::load(None)?;
+    reload_from(&jj_repo, tree, diff_stats_cache, revset)
+}
+
+/// Snapshot the files on disk so `@` shows edits made outside jj, describing
+/// why when it couldn't be taken
+pub fn snapshot_working_copy(jj_repo: &mut JjRepo) -> Option<String> {
+    jj_repo
+        .snapshot_working_copy()
+        .err()
+        .map(|error| format!("Couldn't snapshot the working copy: {error:#}"))
+}
+
+fn reload_from(
+    jj_repo: &JjRepo,
+    tree: &mut TreeState,
+    diff_stats_cache: &mut std::collections::HashMap<String, DiffStats>,
+    revset: Option<&str>,
+) -> eyre::Result<()> {
+    let remapper = TreeRefreshRemapper::capture(tree);
     let mut refreshed_tree =
-        TreeState::load_with_scope(&jj_repo, "trunk()", remapper.load_scope())?;
+        TreeState::load_with_revset(jj_repo, "trunk()", remapper.load_scope(), revset)?;
 
     remapper.restore(&mut refreshed_tree);
     refreshed_tree.clear_selection();
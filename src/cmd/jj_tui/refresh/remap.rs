@@ -3,7 +3,7 @@ mod restore;
 #[cfg(test)]
 mod tests;
 
-use crate::cmd::jj_tui::tree::{TreeLoadScope, TreeState, ViewMode};
+use crate::cmd::jj_tui::tree::{TreeLoadScope, TreeSearch, TreeState, ViewMode};
 
 #[derive(Debug, Clone)]
 pub(super) struct TreeRefreshRemapper {
@@ -11,6 +11,8 @@ pub(super) struct TreeRefreshRemapper {
     parent_change_id: Option<String>,
     old_cursor: usize,
     full_mode: bool,
+    view_name: Option<String>,
+    search: Option<TreeSearch>,
     load_scope: TreeLoadScope,
     view_mode: ViewMode,
     focus_stack_change_ids: Vec<String>,
@@ -9,6 +9,8 @@ pub(super) fn capture(tree: &TreeState) -> TreeRefreshRemapper {
             .and_then(|node| node.parent_ids.first().cloned()),
         old_cursor: tree.view.cursor,
         full_mode: tree.view.full_mode,
+        view_name: tree.view.view_name.clone(),
+        search: tree.view.search.clone(),
         load_scope: tree.view.load_scope,
         view_mode: tree.view.view_mode.clone(),
         focus_stack_change_ids: tree
@@ -3,6 +3,8 @@ use crate::cmd::jj_tui::tree::{NeighborhoodState, TreeState, ViewMode};
 
 pub(super) fn restore(remapper: &TreeRefreshRemapper, tree: &mut TreeState) {
     tree.view.full_mode = remapper.full_mode;
+    tree.view.view_name = remapper.view_name.clone();
+    tree.set_search(remapper.search.clone());
     remapper.restore_mode(tree);
     remapper.restore_cursor(tree);
 }
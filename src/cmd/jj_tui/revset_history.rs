@@ -0,0 +1,75 @@
+//! Recent revsets typed into the revset prompt, kept across sessions
+
+use log::warn;
+use std::path::PathBuf;
+
+const MAX_ENTRIES: usize = 50;
+
+/// Recent revsets, most recent first
+pub(crate) fn load() -> Vec<String> {
+    let Some(path) = history_path() else {
+        return Vec::new();
+    };
+
+    std::fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(ToOwned::to_owned)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Move `revset` to the front of the history, saving it when possible
+pub(crate) fn record(revset: &str) {
+    let Some(path) = history_path() else {
+        return;
+    };
+
+    let mut history = load();
+    push_recent(&mut history, revset);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(error) = std::fs::write(&path, history.join("\n") + "\n") {
+        warn!("failed to save revset history: {error}");
+    }
+}
+
+fn push_recent(history: &mut Vec<String>, revset: &str) {
+    // revsets are single line, so a newline would corrupt the file
+    let revset = revset.replace('\n', " ");
+    history.retain(|entry| *entry != revset);
+    history.insert(0, revset);
+    history.truncate(MAX_ENTRIES);
+}
+
+fn history_path() -> Option<PathBuf> {
+    if let Some(state_home) = std::env::var_os("XDG_STATE_HOME") {
+        return Some(PathBuf::from(state_home).join("jju/revset_history"));
+    }
+
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .map(|home| home.join(".local/state/jju/revset_history"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_ENTRIES, push_recent};
+
+    #[test]
+    fn push_recent_moves_repeats_to_the_front_and_caps_the_history() {
+        let mut history = vec!["mine()".to_string(), "@::".to_string()];
+        push_recent(&mut history, "@::");
+        assert_eq!(history, vec!["@::".to_string(), "mine()".to_string()]);
+
+        for index in 0..MAX_ENTRIES {
+            push_recent(&mut history, &format!("@-{index}"));
+        }
+        assert_eq!(history.len(), MAX_ENTRIES);
+        assert_eq!(history[0], format!("@-{}", MAX_ENTRIES - 1));
+    }
+}
@@ -3,6 +3,7 @@
 //! The runner executes effects by performing IO operations.
 //! It handles terminal restore/init for operations that need the terminal.
 
+mod background;
 mod bookmarks;
 mod clipboard;
 mod context;
@@ -14,16 +15,24 @@ mod operations;
 mod revision;
 
 use super::effect::Effect;
+pub use background::{BackgroundEffect, run as run_in_background};
 pub use context::{RunCtx, RunResult};
+pub use operations::StackSyncTask;
 use ratatui::DefaultTerminal;
 
-/// Execute a list of effects
+/// Execute a list of effects, handing the first one that runs in the
+/// background and everything after it back to the app
 pub fn run_effects(
     mut ctx: RunCtx<'_>,
     effects: Vec<Effect>,
     terminal: &mut DefaultTerminal,
 ) -> RunResult {
-    for effect in effects {
+    let mut effects = effects.into_iter();
+    while let Some(effect) = effects.next() {
+        if effect.runs_in_background() {
+            ctx.result.background = Some(background::prepare(&mut ctx, effect, effects.collect()));
+            break;
+        }
         dispatch::run_effect(&mut ctx, effect, terminal);
     }
 
@@ -0,0 +1,29 @@
+use super::super::commands;
+use super::super::state::MessageKind;
+use super::{Effect, RunCtx, git};
+
+/// A long effect handed to the app to run on a worker thread
+#[derive(Debug)]
+pub struct BackgroundEffect {
+    pub effect: Effect,
+    /// Operation before the effect and its undo label, recorded once it finishes
+    pub undo: Option<(String, String)>,
+    /// The rest of its batch, like the tree refresh, run once it finishes
+    pub then: Vec<Effect>,
+}
+
+pub(super) fn prepare(ctx: &mut RunCtx<'_>, effect: Effect, then: Vec<Effect>) -> BackgroundEffect {
+    let undo = effect.undo_label().and_then(|label| {
+        ctx.pending_undo_op
+            .take()
+            .or_else(|| commands::get_current_op_id().ok())
+            .map(|op_before| (op_before, label))
+    });
+
+    BackgroundEffect { effect, undo, then }
+}
+
+/// Run a background effect to completion, returning its status message
+pub fn run(effect: &Effect) -> (String, MessageKind) {
+    git::run(effect)
+}
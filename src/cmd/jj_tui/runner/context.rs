@@ -1,6 +1,7 @@
 use super::super::refresh;
-use super::super::state::{DiffStats, MessageKind};
+use super::super::state::{DiffStats, MessageKind, UndoHistory};
 use super::super::tree::TreeState;
+use super::{BackgroundEffect, StackSyncTask};
 use std::path::Path;
 use std::time::Duration;
 
@@ -10,29 +11,36 @@ pub struct RunResult {
     pub status_message: Option<(String, MessageKind)>,
     pub status_duration: Option<Duration>,
     pub tree_refreshed: bool,
+    /// A stack sync started in the background, for the app to follow
+    pub stack_sync: Option<StackSyncTask>,
+    /// A long effect for the app to run on a worker thread
+    pub background: Option<BackgroundEffect>,
 }
 
 pub struct RunCtx<'a> {
     pub tree: &'a mut TreeState,
     pub diff_stats_cache: &'a mut std::collections::HashMap<String, DiffStats>,
-    pub last_op: &'a mut Option<String>,
+    pub undo_history: &'a mut UndoHistory,
     pub repo_path: &'a Path,
     pub result: RunResult,
+    /// Operation captured by `SaveOperationForUndo` for the next mutating effect
+    pub(super) pending_undo_op: Option<String>,
 }
 
 impl<'a> RunCtx<'a> {
     pub fn new(
         tree: &'a mut TreeState,
         diff_stats_cache: &'a mut std::collections::HashMap<String, DiffStats>,
-        last_op: &'a mut Option<String>,
+        undo_history: &'a mut UndoHistory,
         repo_path: &'a Path,
     ) -> Self {
         Self {
             tree,
             diff_stats_cache,
-            last_op,
+            undo_history,
             repo_path,
             result: RunResult::default(),
+            pending_undo_op: None,
         }
     }
 
@@ -53,10 +61,14 @@ impl<'a> RunCtx<'a> {
     }
 
     pub(super) fn refresh_tree(&mut self) {
-        if let Err(error) = refresh::refresh_tree(self.tree, self.diff_stats_cache) {
-            self.error(format!("Failed to refresh: {error}"));
-        } else {
-            self.result.tree_refreshed = true;
+        match refresh::refresh_tree(self.tree, self.diff_stats_cache) {
+            Ok(warning) => {
+                self.result.tree_refreshed = true;
+                if let Some(warning) = warning {
+                    self.warn(warning);
+                }
+            }
+            Err(error) => self.error(format!("Failed to refresh: {error}")),
         }
     }
 }
@@ -1,8 +1,33 @@
+use super::super::commands;
 use super::super::effect::Effect;
 use super::{RunCtx, bookmarks, clipboard, git, interactive, revision};
 use ratatui::DefaultTerminal;
 
 pub(super) fn run_effect(ctx: &mut RunCtx<'_>, effect: Effect, terminal: &mut DefaultTerminal) {
+    let Some(label) = effect.undo_label() else {
+        dispatch_effect(ctx, effect, terminal);
+        return;
+    };
+
+    let op_heads = commands::get_op_heads().ok();
+    let op_before = ctx.pending_undo_op.take().or_else(|| {
+        op_heads
+            .as_ref()
+            .and_then(|op_heads| op_heads.current_op_id().ok())
+    });
+    dispatch_effect(ctx, effect, terminal);
+
+    // only record actions that actually produced a new operation
+    if let Some(op_before) = op_before
+        && let Some(op_heads) = &op_heads
+        && let Ok(op_after) = op_heads.current_op_id()
+        && op_after != op_before
+    {
+        ctx.undo_history.record(op_before, label);
+    }
+}
+
+fn dispatch_effect(ctx: &mut RunCtx<'_>, effect: Effect, terminal: &mut DefaultTerminal) {
     match effect {
         Effect::RefreshTree => ctx.refresh_tree(),
         Effect::SaveOperationForUndo => save_operation_for_undo(ctx),
@@ -13,11 +38,15 @@ pub(super) fn run_effect(ctx: &mut RunCtx<'_>, effect: Effect, terminal: &mut De
         | Effect::RunRebase { .. }
         | Effect::RunRebaseOntoTrunk { .. }
         | Effect::RunUndo
-        | Effect::RunResolveDivergence { .. } => revision::handle(ctx, effect),
+        | Effect::RunRedo
+        | Effect::RunRestoreOperation { .. }
+        | Effect::RunResolveDivergence { .. }
+        | Effect::RunSplitHunk { .. }
+        | Effect::RunAbsorb { .. } => revision::handle(ctx, effect),
         Effect::RunGitPush { .. }
         | Effect::RunGitPushMultiple { .. }
         | Effect::RunGitPushAll
-        | Effect::RunStackSync
+        | Effect::RunStackSync { .. }
         | Effect::RunGitFetch
         | Effect::RunGitImport
         | Effect::RunGitExport
@@ -30,12 +59,22 @@ pub(super) fn run_effect(ctx: &mut RunCtx<'_>, effect: Effect, terminal: &mut De
             clipboard::handle(ctx, effect)
         }
         Effect::SetStatus { text, kind } => ctx.set_status(text, kind),
-        Effect::LoadConflictFiles => {}
+        Effect::LoadConflictFiles
+        | Effect::LoadOperationLog
+        | Effect::LoadSplitHunkDiff
+        | Effect::LoadStackSyncPlan
+        | Effect::LoadRevsetHistory
+        | Effect::ApplyRevset { .. }
+        | Effect::LoadViews
+        | Effect::ApplyView { .. }
+        | Effect::GotoChange { .. }
+        | Effect::LoadChange { .. }
+        | Effect::CancelBackground => {}
     }
 }
 
 fn save_operation_for_undo(ctx: &mut RunCtx<'_>) {
-    if let Ok(op_id) = crate::cmd::jj_tui::commands::get_current_op_id() {
-        *ctx.last_op = Some(op_id);
+    if let Ok(op_id) = commands::get_current_op_id() {
+        ctx.pending_undo_op = Some(op_id);
     }
 }
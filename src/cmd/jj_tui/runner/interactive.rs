@@ -55,7 +55,7 @@ fn handle_squash(ctx: &mut RunCtx<'_>, terminal: &mut DefaultTerminal, squash: S
 
     match status {
         Ok(exit_status) if exit_status.success() => {
-            *ctx.last_op = Some(squash.op_before);
+            ctx.undo_history.record(squash.op_before, "squash");
             let has_conflicts = ConflictOps.has_conflicts().unwrap_or(false);
             ctx.refresh_tree();
             if has_conflicts {
@@ -3,6 +3,8 @@ mod rebase;
 mod stack_sync;
 
 use crate::cmd::jj_tui::state::{MessageKind, RebaseType};
+pub(super) use stack_sync::StackSyncRun;
+pub use stack_sync::StackSyncTask;
 
 pub(super) fn run_bookmark_set(name: &str, rev: &str) -> (String, MessageKind) {
     bookmarks::run_bookmark_set(name, rev)
@@ -28,6 +30,6 @@ pub(super) fn run_rebase_onto_trunk(
     rebase::run_rebase_onto_trunk(source, rebase_type)
 }
 
-pub(super) fn run_stack_sync() -> (String, MessageKind) {
-    stack_sync::run_stack_sync()
+pub(super) fn spawn_stack_sync(run: StackSyncRun) -> StackSyncTask {
+    stack_sync::spawn_stack_sync(run)
 }
@@ -1,62 +1,132 @@
+use crate::cmd::jj_tui::app::replaceable_task::{CancellationToken, ReplaceableTask};
 use crate::cmd::jj_tui::commands;
-use crate::cmd::jj_tui::runner::error::set_error_with_details;
-use crate::cmd::jj_tui::state::MessageKind;
+use crate::cmd::jj_tui::state::{StackSyncProgress, StackSyncStepKind, StackSyncStepStatus};
+use jju_core::remote::Remotes;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
 
-pub(super) fn run_stack_sync() -> (String, MessageKind) {
-    let result = (|| -> eyre::Result<String> {
-        commands::git::fetch().map_err(|e| eyre::eyre!("Stack sync failed (fetch): {e}"))?;
-
-        let trunk = commands::stack_sync::detect_trunk_branch()
-            .map_err(|e| eyre::eyre!("Stack sync failed (detect trunk): {e}"))?;
+/// A stack sync running on a worker thread
+#[derive(Debug)]
+pub struct StackSyncTask {
+    /// Operation before the sync, recorded for undo once it finishes
+    pub op_before: Option<String>,
+    /// Progress of each step, disconnected once the sync is over
+    pub task: ReplaceableTask<StackSyncProgress>,
+}
 
-        commands::stack_sync::sync_trunk_bookmark(&trunk)
-            .map_err(|e| eyre::eyre!("Stack sync failed (sync trunk): {e}"))?;
+impl StackSyncTask {
+    /// Whether the sync was cancelled, its worker possibly still killing a step
+    pub fn is_cancelled(&self) -> bool {
+        self.task.is_cancelled()
+    }
+}
 
-        let roots = commands::stack_sync::find_stack_roots(&trunk)
-            .map_err(|e| eyre::eyre!("Stack sync failed (find roots): {e}"))?;
+/// What a stack sync runs, taken from its plan
+#[derive(Debug, Clone)]
+pub(in crate::cmd::jj_tui::runner) struct StackSyncRun {
+    pub onto: String,
+    pub trunk: String,
+    pub remotes: Remotes,
+    pub steps: Vec<StackSyncStepKind>,
+}
 
-        if roots.is_empty() {
-            return Ok("Nothing to rebase, stack is up to date".to_string());
-        }
+pub(super) fn spawn_stack_sync(run: StackSyncRun) -> StackSyncTask {
+    let op_before = commands::get_current_op_id().ok();
+    let task = ReplaceableTask::spawn(Duration::ZERO, move |token, sender| {
+        let check_token = token.clone();
+        jju_jj::ops::with_cancellation(
+            move || check_token.is_cancelled(),
+            || run_steps(&run, &token, &sender),
+        );
+    });
 
-        for root in &roots {
-            commands::stack_sync::rebase_root_onto_trunk(root, &trunk)
-                .map_err(|e| eyre::eyre!("Stack sync failed (rebase {root}): {e}"))?;
-        }
+    StackSyncTask { op_before, task }
+}
 
-        let deleted = commands::stack_sync::cleanup_deleted_bookmarks().unwrap_or_default();
+fn run_steps(run: &StackSyncRun, token: &CancellationToken, sender: &Sender<StackSyncProgress>) {
+    let send = |step, status| {
+        let _ = sender.send(StackSyncProgress { step, status });
+    };
 
-        if commands::has_conflicts().unwrap_or(false) {
-            return Ok("Stack synced (conflicts detected, u to undo)".to_string());
+    // nothing after a failed fetch or trunk sync can be trusted, and a failed
+    // rebase leaves nothing worth pushing
+    let mut blocked = false;
+    let mut push_blocked = false;
+    for (index, kind) in run.steps.iter().enumerate() {
+        if blocked || token.is_cancelled() || (push_blocked && *kind == StackSyncStepKind::Push) {
+            send(index, StackSyncStepStatus::Skipped);
+            continue;
         }
 
-        Ok(success_message(&trunk, &deleted))
-    })();
+        send(index, StackSyncStepStatus::Running);
+        let status = run_step(run, kind);
+        if let StackSyncStepStatus::Failed(_) = status {
+            match kind {
+                StackSyncStepKind::Fetch | StackSyncStepKind::SyncTrunk => blocked = true,
+                StackSyncStepKind::Rebase { .. } => push_blocked = true,
+                StackSyncStepKind::Abandon { .. }
+                | StackSyncStepKind::Cleanup
+                | StackSyncStepKind::Push => {}
+            }
+        }
+        send(index, status);
+    }
+}
 
-    match result {
-        Ok(message) => {
-            let kind = if message.contains("conflicts") {
-                MessageKind::Warning
-            } else {
-                MessageKind::Success
-            };
-            (message, kind)
+fn run_step(run: &StackSyncRun, kind: &StackSyncStepKind) -> StackSyncStepStatus {
+    match kind {
+        StackSyncStepKind::Fetch => done(commands::stack_sync::fetch(&run.remotes)),
+        StackSyncStepKind::SyncTrunk => done(commands::stack_sync::sync_trunk_bookmark(
+            &run.trunk,
+            &run.remotes.trunk,
+        )),
+        StackSyncStepKind::Abandon { root } => {
+            match commands::stack_sync::abandon_landed_stack(root) {
+                Ok(bookmarks) if bookmarks.is_empty() => StackSyncStepStatus::Done(None),
+                Ok(bookmarks) => {
+                    StackSyncStepStatus::Done(Some(format!("deleted {}", bookmarks.join(", "))))
+                }
+                Err(error) => StackSyncStepStatus::Failed(format!("{error:#}")),
+            }
+        }
+        StackSyncStepKind::Rebase { root } => {
+            let result = commands::stack_sync::sync_root(root, &run.onto);
+            if let Some(error) = result.error {
+                return StackSyncStepStatus::Failed(error);
+            }
+            if result.conflicts.is_empty() {
+                let note = result
+                    .emptied
+                    .then(|| "root emptied and abandoned".to_string());
+                return StackSyncStepStatus::Done(note);
+            }
+            let conflicted = result
+                .conflicts
+                .iter()
+                .map(|commit| commit.change_id.as_str())
+                .collect::<Vec<_>>();
+            StackSyncStepStatus::Warning(format!("conflicts in {}", conflicted.join(", ")))
+        }
+        StackSyncStepKind::Cleanup => match commands::stack_sync::cleanup_deleted_bookmarks() {
+            Ok(deleted) if deleted.is_empty() => StackSyncStepStatus::Done(None),
+            Ok(deleted) => {
+                StackSyncStepStatus::Done(Some(format!("deleted {}", deleted.join(", "))))
+            }
+            Err(error) => StackSyncStepStatus::Failed(format!("{error:#}")),
+        },
+        StackSyncStepKind::Push => {
+            match commands::stack_sync::push_first_bookmark(&run.onto, &run.remotes.push) {
+                Ok(Some(bookmark)) => StackSyncStepStatus::Done(Some(format!("pushed {bookmark}"))),
+                Ok(None) => StackSyncStepStatus::Done(Some("no bookmark to push".to_string())),
+                Err(error) => StackSyncStepStatus::Failed(format!("{error:#}")),
+            }
         }
-        Err(error) => (
-            set_error_with_details("Stack sync failed", &error.to_string()),
-            MessageKind::Error,
-        ),
     }
 }
 
-fn success_message(trunk: &str, deleted: &[String]) -> String {
-    let mut message = format!("Stack synced onto {trunk}");
-    if !deleted.is_empty() {
-        message.push_str(&format!(
-            ", cleaned up {} bookmark{}",
-            deleted.len(),
-            if deleted.len() == 1 { "" } else { "s" }
-        ));
+fn done(result: eyre::Result<()>) -> StackSyncStepStatus {
+    match result {
+        Ok(()) => StackSyncStepStatus::Done(None),
+        Err(error) => StackSyncStepStatus::Failed(format!("{error:#}")),
     }
-    message
 }
@@ -1,6 +1,7 @@
 use super::{Effect, RunCtx};
 use crate::cmd::jj_tui::runner::{error, operations};
-use crate::cmd::jj_tui::state::{MessageKind, RebaseType};
+use crate::cmd::jj_tui::state::{MessageKind, RebaseType, UndoEntry};
+use jju_jj::split_hunk::SplitHunkPlan;
 
 pub(super) struct RevisionRunner<'a, 'b>(&'a mut RunCtx<'b>);
 
@@ -23,10 +24,16 @@ pub(super) fn handle(ctx: &mut RunCtx<'_>, effect: Effect) {
             rebase_type,
         } => runner.run_rebase_onto_trunk(&source, rebase_type),
         Effect::RunUndo => runner.run_undo(),
+        Effect::RunRedo => runner.run_redo(),
+        Effect::RunRestoreOperation { op_id } => runner.run_restore_operation(&op_id),
         Effect::RunResolveDivergence {
             keep_commit_id,
             abandon_commit_ids,
         } => runner.run_resolve_divergence(&keep_commit_id, abandon_commit_ids),
+        Effect::RunSplitHunk { rev, message, plan } => {
+            runner.run_split_hunk(&rev, &message, &plan);
+        }
+        Effect::RunAbsorb { rev } => runner.run_absorb(&rev),
         _ => unreachable!("unsupported revision effect: {effect:?}"),
     }
 }
@@ -98,6 +105,64 @@ impl RevisionRunner<'_, '_> {
         }
     }
 
+    pub(super) fn run_split_hunk(&mut self, rev: &str, message: &str, plan: &SplitHunkPlan) {
+        match crate::cmd::jj_tui::commands::split_hunk::split(plan, rev, message) {
+            Ok(_) => {
+                let count = plan.selected_count();
+                self.0.success(format!(
+                    "Split {count} hunk{} into new commit",
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
+            Err(error_value) => {
+                let details = format!("{error_value}");
+                self.0.set_status(
+                    error::set_error_with_details("Split failed", &details),
+                    MessageKind::Error,
+                );
+            }
+        }
+    }
+
+    pub(super) fn run_absorb(&mut self, rev: &str) {
+        let summary = match crate::cmd::jj_tui::commands::absorb::absorb(rev) {
+            Ok(summary) => summary,
+            Err(error_value) => {
+                let details = format!("{error_value}");
+                self.0.set_status(
+                    error::set_error_with_details("Absorb failed", &details),
+                    MessageKind::Error,
+                );
+                return;
+            }
+        };
+
+        if summary.absorbed == 0 {
+            self.0.set_status(
+                "No hunks could be absorbed into a single ancestor",
+                MessageKind::Warning,
+            );
+            return;
+        }
+
+        let mut text = format!(
+            "Absorbed {} hunk{} into {} revision{}",
+            summary.absorbed,
+            if summary.absorbed == 1 { "" } else { "s" },
+            summary.destinations,
+            if summary.destinations == 1 { "" } else { "s" }
+        );
+        if summary.skipped > 0 {
+            text.push_str(&format!(", {} left in place", summary.skipped));
+        }
+        if summary.conflicted > 0 {
+            text.push_str(&format!(" ({} with new conflicts)", summary.conflicted));
+            self.0.set_status(text, MessageKind::Warning);
+        } else {
+            self.0.success(text);
+        }
+    }
+
     pub(super) fn run_rebase(
         &mut self,
         source: &str,
@@ -115,14 +180,64 @@ impl RevisionRunner<'_, '_> {
     }
 
     pub(super) fn run_undo(&mut self) {
-        match self.0.last_op.take() {
-            Some(op_id) if !op_id.is_empty() => {
-                match crate::cmd::jj_tui::commands::restore_op(&op_id) {
-                    Ok(_) => self.0.success("Operation undone"),
-                    Err(error) => self.0.error(format!("Undo failed: {error}")),
+        let Some(entry) = self.0.undo_history.take_undo() else {
+            self.0.warn("Nothing to undo");
+            return;
+        };
+
+        match restore_from_history(&entry) {
+            Ok(op_after) => {
+                self.0.success(format!("Undid {}", entry.label));
+                if let Some(op_id) = op_after {
+                    self.0.undo_history.push_redo(UndoEntry {
+                        op_id,
+                        label: entry.label,
+                    });
                 }
             }
-            _ => self.0.warn("Nothing to undo"),
+            Err(error) => {
+                self.0.error(format!("Undo failed: {error}"));
+                self.0.undo_history.push_undo(entry);
+            }
         }
     }
+
+    pub(super) fn run_redo(&mut self) {
+        let Some(entry) = self.0.undo_history.take_redo() else {
+            self.0.warn("Nothing to redo");
+            return;
+        };
+
+        match restore_from_history(&entry) {
+            Ok(op_after) => {
+                self.0.success(format!("Redid {}", entry.label));
+                if let Some(op_id) = op_after {
+                    self.0.undo_history.push_undo(UndoEntry {
+                        op_id,
+                        label: entry.label,
+                    });
+                }
+            }
+            Err(error) => {
+                self.0.error(format!("Redo failed: {error}"));
+                self.0.undo_history.push_redo(entry);
+            }
+        }
+    }
+
+    pub(super) fn run_restore_operation(&mut self, op_id: &str) {
+        let short_id = &op_id[..op_id.len().min(12)];
+        match crate::cmd::jj_tui::commands::restore_op(op_id) {
+            Ok(_) => self.0.success(format!("Restored to operation {short_id}")),
+            Err(error) => self.0.error(format!("Restore failed: {error}")),
+        }
+    }
+}
+
+/// Restore to a history entry, returning the operation it replaced so it can be
+/// pushed onto the opposite stack
+fn restore_from_history(entry: &UndoEntry) -> eyre::Result<Option<String>> {
+    let op_after = crate::cmd::jj_tui::commands::get_current_op_id().ok();
+    crate::cmd::jj_tui::commands::restore_op(&entry.op_id)?;
+    Ok(op_after)
 }
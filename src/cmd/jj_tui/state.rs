@@ -6,12 +6,17 @@ mod diff;
 mod message;
 mod mode;
 mod operations;
+mod undo;
 
 pub use diff::{DiffLine, DiffLineKind, DiffState, DiffStats, StyledSpan};
 pub use message::{MessageKind, StatusMessage};
 pub use mode::{HelpState, ModeState};
 pub use operations::{
     BookmarkPickerState, BookmarkSelectAction, BookmarkSelectState, ClipboardBranchOption,
-    ClipboardBranchSelectState, ConfirmAction, ConfirmState, ConflictsState, MovingBookmarkState,
-    PushSelectState, RebaseState, RebaseType, SquashState,
+    ClipboardBranchSelectState, ConfirmAction, ConfirmState, ConflictsState, GotoPromptState,
+    MovingBookmarkState, OperationLogState, PushSelectState, RebaseState, RebaseType,
+    RevsetPromptState, SearchPromptState, SplitHunkRow, SplitHunkState, SquashState,
+    StackSyncPhase, StackSyncProgress, StackSyncState, StackSyncStep, StackSyncStepKind,
+    StackSyncStepStatus, ViewPickerState,
 };
+pub use undo::{UndoEntry, UndoHistory};
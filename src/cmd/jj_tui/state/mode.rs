@@ -1,6 +1,8 @@
 use super::{
     BookmarkPickerState, BookmarkSelectState, ClipboardBranchSelectState, ConfirmState,
-    ConflictsState, DiffState, MovingBookmarkState, PushSelectState, RebaseState, SquashState,
+    ConflictsState, DiffState, GotoPromptState, MovingBookmarkState, OperationLogState,
+    PushSelectState, RebaseState, RevsetPromptState, SearchPromptState, SplitHunkState,
+    SquashState, StackSyncState, ViewPickerState,
 };
 
 /// Unified mode state - single source of truth for current mode and its associated state
@@ -19,6 +21,13 @@ pub enum ModeState {
     PushSelect(PushSelectState),
     Squashing(SquashState),
     Conflicts(ConflictsState),
+    OperationLog(OperationLogState),
+    SplitHunk(SplitHunkState),
+    StackSync(StackSyncState),
+    RevsetPrompt(RevsetPromptState),
+    ViewPicker(ViewPickerState),
+    Search(SearchPromptState),
+    GotoPrompt(GotoPromptState),
 }
 
 #[derive(Debug, Clone)]
@@ -1,16 +1,33 @@
 mod bookmarks;
 mod clipboard;
 mod confirm;
+mod goto;
+mod operation_log;
 mod push_select;
 mod rebase;
+mod revset;
+mod search;
+mod split_hunk;
+mod stack_sync;
+mod views;
 
 pub use bookmarks::{
     BookmarkPickerState, BookmarkSelectAction, BookmarkSelectState, MovingBookmarkState,
 };
 pub use clipboard::{ClipboardBranchOption, ClipboardBranchSelectState};
 pub use confirm::{ConfirmAction, ConfirmState};
+pub use goto::GotoPromptState;
+pub use operation_log::OperationLogState;
 pub use push_select::PushSelectState;
 pub use rebase::{RebaseState, RebaseType};
+pub use revset::RevsetPromptState;
+pub use search::SearchPromptState;
+pub use split_hunk::{SplitHunkRow, SplitHunkState};
+pub use stack_sync::{
+    StackSyncPhase, StackSyncProgress, StackSyncState, StackSyncStep, StackSyncStepKind,
+    StackSyncStepStatus,
+};
+pub use views::ViewPickerState;
 
 #[derive(Debug, Clone, Default)]
 pub struct ConflictsState {
@@ -3,12 +3,19 @@ use super::rebase::RebaseType;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfirmAction {
     Abandon,
-    StackSync,
     RebaseOntoTrunk(RebaseType),
     MoveBookmarkBackwards {
         bookmark_name: String,
         dest_rev: String,
     },
+    RestoreOperation {
+        op_id: String,
+    },
+    /// Reload the tree with `revset` and jump to `commit_id`
+    LoadChange {
+        commit_id: String,
+        revset: String,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,19 @@
+/// The go-to prompt, jumping to the change a typed id prefix resolves to
+#[derive(Debug, Clone, Default)]
+pub struct GotoPromptState {
+    pub input: String,
+    /// Why the last submitted prefix couldn't be resolved
+    pub error: Option<String>,
+}
+
+impl GotoPromptState {
+    pub fn push_char(&mut self, ch: char) {
+        self.input.push(ch);
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+        self.error = None;
+    }
+}
@@ -0,0 +1,13 @@
+use crate::jj_lib_helpers::OperationLogEntry;
+
+#[derive(Debug, Clone, Default)]
+pub struct OperationLogState {
+    pub entries: Vec<OperationLogEntry>,
+    pub selected_index: usize,
+}
+
+impl OperationLogState {
+    pub fn selected_entry(&self) -> Option<&OperationLogEntry> {
+        self.entries.get(self.selected_index)
+    }
+}
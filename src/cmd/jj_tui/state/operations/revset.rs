@@ -0,0 +1,68 @@
+/// The revset prompt, with the recent revsets it can recall
+#[derive(Debug, Clone, Default)]
+pub struct RevsetPromptState {
+    pub input: String,
+    /// Why the last submitted revset couldn't be shown
+    pub error: Option<String>,
+    /// Recent revsets, most recent first
+    pub history: Vec<String>,
+    /// Position in `history` while recalling, `None` while editing
+    pub history_index: Option<usize>,
+    /// The input as typed before recalling history
+    draft: String,
+}
+
+impl RevsetPromptState {
+    /// Start from the revset the tree currently shows, if it isn't the default
+    pub fn new(current: Option<String>) -> Self {
+        Self {
+            input: current.unwrap_or_default(),
+            ..Self::default()
+        }
+    }
+
+    pub fn push_char(&mut self, ch: char) {
+        self.input.push(ch);
+        self.stop_recalling();
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+        self.stop_recalling();
+    }
+
+    /// Recall the next older revset
+    pub fn history_prev(&mut self) {
+        let index = match self.history_index {
+            Some(index) if index + 1 < self.history.len() => index + 1,
+            Some(_) => return,
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = self.input.clone();
+                0
+            }
+        };
+        self.history_index = Some(index);
+        self.input = self.history[index].clone();
+    }
+
+    /// Recall the next newer revset, back to the draft after the newest
+    pub fn history_next(&mut self) {
+        match self.history_index {
+            Some(0) => {
+                self.history_index = None;
+                self.input = std::mem::take(&mut self.draft);
+            }
+            Some(index) => {
+                self.history_index = Some(index - 1);
+                self.input = self.history[index - 1].clone();
+            }
+            None => {}
+        }
+    }
+
+    fn stop_recalling(&mut self) {
+        self.history_index = None;
+        self.error = None;
+    }
+}
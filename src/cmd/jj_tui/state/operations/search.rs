@@ -0,0 +1,31 @@
+use crate::cmd::jj_tui::tree::TreeSearch;
+
+/// The `/` prompt, searching the tree as the query is typed
+#[derive(Debug, Clone, Default)]
+pub struct SearchPromptState {
+    pub input: String,
+    /// Show only the matches and their ancestors
+    pub filter: bool,
+    /// Search to go back to when the prompt is cancelled
+    pub previous: Option<TreeSearch>,
+    /// Node under the cursor when the prompt opened, matches are searched from it
+    pub origin_node: Option<usize>,
+}
+
+impl SearchPromptState {
+    pub fn new(previous: Option<TreeSearch>, origin_node: Option<usize>) -> Self {
+        Self {
+            input: String::new(),
+            filter: previous.as_ref().is_some_and(|search| search.filter),
+            previous,
+            origin_node,
+        }
+    }
+
+    pub fn search(&self) -> Option<TreeSearch> {
+        (!self.input.is_empty()).then(|| TreeSearch {
+            query: self.input.clone(),
+            filter: self.filter,
+        })
+    }
+}
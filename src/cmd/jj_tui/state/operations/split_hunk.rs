@@ -0,0 +1,204 @@
+use ahash::{HashMap, HashSet};
+use jju_jj::split_hunk::{DiffLine, DiffLineKind, ParsedDiff, SelectedHunk, SplitHunkPlan};
+use std::collections::BTreeSet;
+
+/// A row of the split-hunk checklist: a file header, one of its hunks, or a
+/// changed line of an expanded hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitHunkRow {
+    File(usize),
+    Hunk(SelectedHunk),
+    /// Index into the hunk's `DiffHunk::lines()`
+    Line(SelectedHunk, usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct SplitHunkState {
+    pub rev: String,
+    pub diff: ParsedDiff,
+    pub cursor: usize,
+    pub selected: HashSet<SelectedHunk>,
+    /// Changed lines kept for selected hunks narrowed line by line; a selected
+    /// hunk without an entry is taken whole
+    pub selected_lines: HashMap<SelectedHunk, BTreeSet<usize>>,
+    /// Hunks showing a row per changed line
+    pub expanded: HashSet<SelectedHunk>,
+    /// Commit message being typed, `Some` once the selection is confirmed
+    pub message: Option<String>,
+}
+
+impl SplitHunkState {
+    pub fn new(rev: String) -> Self {
+        Self {
+            rev,
+            diff: ParsedDiff::empty(),
+            cursor: 0,
+            selected: HashSet::default(),
+            selected_lines: HashMap::default(),
+            expanded: HashSet::default(),
+            message: None,
+        }
+    }
+
+    pub fn rows(&self) -> Vec<SplitHunkRow> {
+        let mut rows = Vec::new();
+        for (file_index, file) in self.diff.files().iter().enumerate() {
+            rows.push(SplitHunkRow::File(file_index));
+            for (hunk_index, diff_hunk) in file.hunks().iter().enumerate() {
+                let hunk = SelectedHunk {
+                    file_index,
+                    hunk_index,
+                };
+                rows.push(SplitHunkRow::Hunk(hunk));
+                if self.expanded.contains(&hunk) {
+                    rows.extend(
+                        changed_lines(diff_hunk.lines())
+                            .map(|line_index| SplitHunkRow::Line(hunk, line_index)),
+                    );
+                }
+            }
+        }
+        rows
+    }
+
+    pub fn current_row(&self) -> Option<SplitHunkRow> {
+        self.rows().get(self.cursor).copied()
+    }
+
+    pub fn file_hunks(&self, file_index: usize) -> impl Iterator<Item = SelectedHunk> + use<> {
+        let count = self
+            .diff
+            .files()
+            .get(file_index)
+            .map(|file| file.hunks().len())
+            .unwrap_or(0);
+        (0..count).map(move |hunk_index| SelectedHunk {
+            file_index,
+            hunk_index,
+        })
+    }
+
+    /// Number of selected hunks and total hunks in a file
+    pub fn file_selection(&self, file_index: usize) -> (usize, usize) {
+        self.file_hunks(file_index)
+            .fold((0, 0), |(selected, total), hunk| {
+                (
+                    selected + usize::from(self.selected.contains(&hunk)),
+                    total + 1,
+                )
+            })
+    }
+
+    /// Whether only some of a selected hunk's changed lines are kept
+    pub fn is_partial(&self, hunk: &SelectedHunk) -> bool {
+        self.selected_lines.contains_key(hunk)
+    }
+
+    pub fn is_line_selected(&self, hunk: &SelectedHunk, line_index: usize) -> bool {
+        match self.selected_lines.get(hunk) {
+            Some(lines) => lines.contains(&line_index),
+            None => self.selected.contains(hunk),
+        }
+    }
+
+    /// Show or hide a row per changed line of `hunk`; files that only split
+    /// as a whole have no lines to pick
+    pub fn toggle_expanded(&mut self, hunk: SelectedHunk) {
+        if self.is_whole_file(hunk.file_index) {
+            return;
+        }
+        if !self.expanded.remove(&hunk) {
+            self.expanded.insert(hunk);
+        }
+    }
+
+    /// Toggle one changed line, selecting its hunk as long as any line is kept
+    pub fn toggle_line(&mut self, hunk: SelectedHunk, line_index: usize) {
+        let Some(diff_hunk) = self
+            .diff
+            .files()
+            .get(hunk.file_index)
+            .and_then(|file| file.hunks().get(hunk.hunk_index))
+        else {
+            return;
+        };
+        let all_lines = changed_lines(diff_hunk.lines()).collect::<BTreeSet<_>>();
+
+        let mut lines = match self.selected_lines.remove(&hunk) {
+            Some(lines) => lines,
+            None if self.selected.contains(&hunk) => all_lines.clone(),
+            None => BTreeSet::new(),
+        };
+        if !lines.remove(&line_index) {
+            lines.insert(line_index);
+        }
+
+        if lines.is_empty() {
+            self.selected.remove(&hunk);
+            return;
+        }
+        self.selected.insert(hunk);
+        if lines != all_lines {
+            self.selected_lines.insert(hunk, lines);
+        }
+    }
+
+    /// Toggle one hunk, or every hunk of a file that only splits as a whole
+    pub fn toggle_hunk(&mut self, hunk: SelectedHunk) {
+        self.selected_lines.remove(&hunk);
+        if !self.is_whole_file(hunk.file_index) {
+            if !self.selected.remove(&hunk) {
+                self.selected.insert(hunk);
+            }
+            return;
+        }
+
+        let hunks = self.file_hunks(hunk.file_index).collect::<Vec<_>>();
+        if self.selected.contains(&hunk) {
+            for hunk in hunks {
+                self.selected.remove(&hunk);
+            }
+        } else {
+            self.selected.extend(hunks);
+        }
+    }
+
+    fn is_whole_file(&self, file_index: usize) -> bool {
+        self.diff
+            .files()
+            .get(file_index)
+            .is_some_and(|file| file.is_whole_file())
+    }
+
+    pub fn hunk_count(&self) -> usize {
+        self.diff
+            .files()
+            .iter()
+            .map(|file| file.hunks().len())
+            .sum()
+    }
+
+    pub fn is_editing_message(&self) -> bool {
+        self.message.is_some()
+    }
+
+    pub fn plan(&self) -> SplitHunkPlan {
+        let mut selected = self.selected.iter().copied().collect::<Vec<_>>();
+        selected.sort();
+        let mut narrowed = self.selected_lines.iter().collect::<Vec<_>>();
+        narrowed.sort_by_key(|(hunk, _)| **hunk);
+        narrowed.into_iter().fold(
+            SplitHunkPlan::new(self.diff.clone(), selected),
+            |plan, (hunk, lines)| plan.with_selected_lines(*hunk, lines.iter().copied()),
+        )
+    }
+}
+
+/// Indices of the added and removed lines of a hunk
+fn changed_lines(lines: &[DiffLine]) -> impl Iterator<Item = usize> + '_ {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.kind != DiffLineKind::Context)
+        .map(|(index, _)| index)
+}
@@ -0,0 +1,278 @@
+use crate::cmd::jj_tui::state::MessageKind;
+use ahash::HashSet;
+use jju_core::stack_sync::{StackSyncPlan, StackSyncScope};
+
+/// One step of a running stack sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackSyncStepKind {
+    Fetch,
+    SyncTrunk,
+    /// Abandon a stack that already landed on trunk
+    Abandon {
+        root: String,
+    },
+    Rebase {
+        root: String,
+    },
+    Cleanup,
+    Push,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StackSyncStepStatus {
+    Pending,
+    Running,
+    /// Finished, with an optional note such as the bookmark that was pushed
+    Done(Option<String>),
+    /// Finished but left something to look at, like conflicts
+    Warning(String),
+    /// Failed with the command's stderr
+    Failed(String),
+    /// Not run because an earlier step failed or the sync was cancelled
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackSyncStep {
+    pub kind: StackSyncStepKind,
+    pub status: StackSyncStepStatus,
+}
+
+/// A step's new status, sent by the worker running the sync
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackSyncProgress {
+    pub step: usize,
+    pub status: StackSyncStepStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackSyncPhase {
+    Loading,
+    Choosing,
+    Running,
+    Finished,
+}
+
+#[derive(Debug, Clone)]
+pub struct StackSyncState {
+    pub scope: StackSyncScope,
+    pub phase: StackSyncPhase,
+    /// The discovered stacks, `None` until loaded
+    pub plan: Option<StackSyncPlan>,
+    /// Indices of the roots to rebase
+    pub selected: HashSet<usize>,
+    /// Indices of the landed roots to abandon
+    pub abandon: HashSet<usize>,
+    /// Push the first bookmark of the current stack afterwards
+    pub push: bool,
+    pub steps: Vec<StackSyncStep>,
+    /// Row under the cursor: a root while choosing, a step once running
+    pub cursor: usize,
+    /// Show the stderr of the failed step under the cursor
+    pub show_details: bool,
+    /// Cancel was pressed while running, the worker stops before the next step
+    pub cancelled: bool,
+}
+
+impl StackSyncState {
+    pub fn new(scope: StackSyncScope) -> Self {
+        Self {
+            scope,
+            phase: StackSyncPhase::Loading,
+            plan: None,
+            selected: HashSet::default(),
+            abandon: HashSet::default(),
+            push: false,
+            steps: Vec::new(),
+            cursor: 0,
+            show_details: false,
+            cancelled: false,
+        }
+    }
+
+    /// Show the discovered stacks, landed ones set to be abandoned and the rest to be rebased
+    pub fn set_plan(&mut self, plan: StackSyncPlan) {
+        self.plan = Some(plan);
+        self.select_all();
+        self.phase = StackSyncPhase::Choosing;
+        self.cursor = 0;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.phase == StackSyncPhase::Running
+    }
+
+    pub fn shows_progress(&self) -> bool {
+        matches!(
+            self.phase,
+            StackSyncPhase::Running | StackSyncPhase::Finished
+        )
+    }
+
+    pub fn root_count(&self) -> usize {
+        self.plan.as_ref().map_or(0, |plan| plan.roots.len())
+    }
+
+    /// Number of rows the cursor moves over in the current phase
+    pub fn row_count(&self) -> usize {
+        if self.shows_progress() {
+            self.steps.len()
+        } else {
+            self.root_count()
+        }
+    }
+
+    pub fn is_landed(&self, index: usize) -> bool {
+        self.plan
+            .as_ref()
+            .and_then(|plan| plan.roots.get(index))
+            .is_some_and(|root| root.landed.is_some())
+    }
+
+    /// Toggle rebasing a root, or cycle a landed root through abandon, rebase and neither
+    pub fn toggle_root(&mut self, index: usize) {
+        if !self.is_landed(index) {
+            if !self.selected.remove(&index) {
+                self.selected.insert(index);
+            }
+        } else if self.abandon.remove(&index) {
+            self.selected.insert(index);
+        } else if !self.selected.remove(&index) {
+            self.abandon.insert(index);
+        }
+    }
+
+    pub fn select_all(&mut self) {
+        let (landed, unlanded) = (0..self.root_count()).partition(|index| self.is_landed(*index));
+        self.abandon = landed;
+        self.selected = unlanded;
+    }
+
+    pub fn select_none(&mut self) {
+        self.selected.clear();
+        self.abandon.clear();
+    }
+
+    pub fn has_selection(&self) -> bool {
+        !self.selected.is_empty() || !self.abandon.is_empty()
+    }
+
+    /// Lay out the steps for the selected roots and switch to showing progress
+    pub fn start(&mut self) -> Vec<StackSyncStepKind> {
+        let mut kinds = vec![StackSyncStepKind::Fetch, StackSyncStepKind::SyncTrunk];
+        if let Some(plan) = &self.plan {
+            kinds.extend(
+                plan.roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| self.abandon.contains(index))
+                    .map(|(_, root)| StackSyncStepKind::Abandon {
+                        root: root.change_id.clone(),
+                    }),
+            );
+            kinds.extend(
+                plan.roots
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| self.selected.contains(index))
+                    .map(|(_, root)| StackSyncStepKind::Rebase {
+                        root: root.change_id.clone(),
+                    }),
+            );
+        }
+        kinds.push(StackSyncStepKind::Cleanup);
+        if self.push {
+            kinds.push(StackSyncStepKind::Push);
+        }
+
+        self.steps = kinds
+            .iter()
+            .map(|kind| StackSyncStep {
+                kind: kind.clone(),
+                status: StackSyncStepStatus::Pending,
+            })
+            .collect();
+        self.phase = StackSyncPhase::Running;
+        self.cursor = 0;
+        self.show_details = false;
+        kinds
+    }
+
+    pub fn apply_progress(&mut self, progress: StackSyncProgress) {
+        if let Some(step) = self.steps.get_mut(progress.step) {
+            step.status = progress.status;
+        }
+        // follow the sync unless the user is reading a failure
+        if !self.show_details && progress.step < self.steps.len() {
+            self.cursor = progress.step;
+        }
+    }
+
+    /// Mark the sync done, moving the cursor to the first failure
+    pub fn finish(&mut self) {
+        self.phase = StackSyncPhase::Finished;
+        for step in &mut self.steps {
+            if matches!(
+                step.status,
+                StackSyncStepStatus::Pending | StackSyncStepStatus::Running
+            ) {
+                step.status = StackSyncStepStatus::Skipped;
+            }
+        }
+        if let Some(index) = self
+            .steps
+            .iter()
+            .position(|step| matches!(step.status, StackSyncStepStatus::Failed(_)))
+        {
+            self.cursor = index;
+        }
+    }
+
+    /// The stderr of the step under the cursor, if it failed
+    pub fn current_error(&self) -> Option<&str> {
+        match &self.steps.get(self.cursor)?.status {
+            StackSyncStepStatus::Failed(stderr) => Some(stderr),
+            _ => None,
+        }
+    }
+
+    /// How the finished sync went, for the status bar
+    pub fn summary(&self) -> (String, MessageKind) {
+        if self.cancelled {
+            return ("Stack sync cancelled".to_string(), MessageKind::Warning);
+        }
+
+        let failed = self
+            .steps
+            .iter()
+            .filter(|step| matches!(step.status, StackSyncStepStatus::Failed(_)))
+            .count();
+        if failed > 0 {
+            return (
+                format!(
+                    "Stack sync failed at {failed} step{}",
+                    if failed == 1 { "" } else { "s" }
+                ),
+                MessageKind::Error,
+            );
+        }
+
+        if self
+            .steps
+            .iter()
+            .any(|step| matches!(step.status, StackSyncStepStatus::Warning(_)))
+        {
+            return (
+                "Stack synced with conflicts, u to undo".to_string(),
+                MessageKind::Warning,
+            );
+        }
+
+        let onto = self
+            .plan
+            .as_ref()
+            .map(|plan| plan.onto.label.as_str())
+            .unwrap_or("trunk");
+        (format!("Stack synced onto {onto}"), MessageKind::Success)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::cmd::jj_tui::views::NamedView;
+
+/// The view picker, listing the default view ahead of the configured ones
+#[derive(Debug, Clone, Default)]
+pub struct ViewPickerState {
+    pub views: Vec<NamedView>,
+    /// Row under the cursor, `0` being the default view
+    pub cursor: usize,
+}
+
+impl ViewPickerState {
+    pub fn row_count(&self) -> usize {
+        self.views.len() + 1
+    }
+
+    pub fn move_down(&mut self) {
+        if self.cursor + 1 < self.row_count() {
+            self.cursor += 1;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+}
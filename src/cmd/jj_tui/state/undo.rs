@@ -0,0 +1,88 @@
+/// An operation to restore to, labelled with the action it reverses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoEntry {
+    pub op_id: String,
+    pub label: String,
+}
+
+/// Undo/redo stacks of operation ids recorded during this session
+#[derive(Debug, Clone, Default)]
+pub struct UndoHistory {
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+}
+
+impl UndoHistory {
+    /// Record the operation preceding a new action, discarding anything left to redo
+    pub fn record(&mut self, op_id: String, label: impl Into<String>) {
+        self.undo.push(UndoEntry {
+            op_id,
+            label: label.into(),
+        });
+        self.redo.clear();
+    }
+
+    pub fn next_undo(&self) -> Option<&UndoEntry> {
+        self.undo.last()
+    }
+
+    pub fn take_undo(&mut self) -> Option<UndoEntry> {
+        self.undo.pop()
+    }
+
+    pub fn take_redo(&mut self) -> Option<UndoEntry> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, entry: UndoEntry) {
+        self.undo.push(entry);
+    }
+
+    pub fn push_redo(&mut self, entry: UndoEntry) {
+        self.redo.push(entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(op_id: &str, label: &str) -> UndoEntry {
+        UndoEntry {
+            op_id: op_id.to_string(),
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_undo_walks_back_through_recorded_actions() {
+        let mut history = UndoHistory::default();
+        history.record("op1".to_string(), "new");
+        history.record("op2".to_string(), "abandon");
+
+        assert_eq!(history.take_undo(), Some(entry("op2", "abandon")));
+        assert_eq!(history.take_undo(), Some(entry("op1", "new")));
+        assert_eq!(history.take_undo(), None);
+    }
+
+    #[test]
+    fn test_record_clears_redo() {
+        let mut history = UndoHistory::default();
+        history.push_redo(entry("op3", "abandon"));
+
+        history.record("op4".to_string(), "commit");
+
+        assert_eq!(history.take_redo(), None);
+        assert_eq!(history.next_undo(), Some(&entry("op4", "commit")));
+    }
+
+    #[test]
+    fn test_push_undo_keeps_redo() {
+        let mut history = UndoHistory::default();
+        history.push_redo(entry("op3", "abandon"));
+        history.push_undo(entry("op2", "new"));
+
+        assert_eq!(history.take_redo(), Some(entry("op3", "abandon")));
+        assert_eq!(history.next_undo(), Some(&entry("op2", "new")));
+    }
+}
@@ -80,10 +80,15 @@ pub(crate) fn make_app_with_tree(tree: TreeState) -> App {
         diff_stats_cache: std::collections::HashMap::new(),
         status_message: None,
         pending_key: None,
-        last_op: None,
+        undo_history: Default::default(),
         syntax_set: SyntaxSet::load_defaults_newlines(),
         theme_set: ThemeSet::load_defaults(),
         repo_path: std::env::current_dir().unwrap_or_default(),
         row_data_loader: Default::default(),
+        stack_sync_task: None,
+        views: Vec::new(),
+        repo_watcher: None,
+        effect_task: None,
+        queued_effects: Default::default(),
     }
 }
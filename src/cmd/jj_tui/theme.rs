@@ -6,6 +6,7 @@ use ratatui::style::Color;
 pub const CURSOR_BG: Color = Color::Rgb(40, 40, 60);
 pub const SOURCE_BG: Color = Color::Rgb(50, 50, 30);
 pub const SELECTED_BG: Color = Color::Rgb(40, 50, 40);
+pub const SEARCH_MATCH_BG: Color = Color::Rgb(180, 150, 40);
 
 // Status bar
 pub const STATUS_BAR_BG: Color = Color::Rgb(30, 30, 50);
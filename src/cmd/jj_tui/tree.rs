@@ -2,6 +2,7 @@ mod load;
 mod navigation;
 mod neighborhood;
 mod projection;
+mod search;
 mod selection;
 mod topology;
 mod types;
@@ -11,6 +12,7 @@ use crate::jj_lib_helpers::{CommitDetails, JjRepo};
 use eyre::Result;
 
 pub use projection::TreeProjection;
+pub use search::{TreeSearch, match_ranges};
 pub use topology::TreeTopology;
 #[cfg(test)]
 pub use types::NeighborhoodExtent;
@@ -29,7 +31,19 @@ impl TreeState {
         base: &str,
         load_scope: TreeLoadScope,
     ) -> Result<Self> {
-        load::load_tree_state(jj_repo, base, load_scope)
+        Self::load_with_revset(jj_repo, base, load_scope, None)
+    }
+
+    /// Load the commits of `revset`, or of the default revset for `load_scope` when `None`
+    pub fn load_with_revset(
+        jj_repo: &JjRepo,
+        base: &str,
+        load_scope: TreeLoadScope,
+        revset: Option<&str>,
+    ) -> Result<Self> {
+        let mut tree = load::load_tree_state(jj_repo, base, load_scope, revset)?;
+        tree.view.revset = revset.map(ToOwned::to_owned);
+        Ok(tree)
     }
 
     fn empty(load_scope: TreeLoadScope) -> Self {
@@ -99,11 +113,25 @@ impl TreeState {
         true
     }
 
+    /// The revset the tree was loaded from, `base` filling in the default one
+    pub fn loaded_revset(&self, base: &str) -> String {
+        match &self.view.revset {
+            Some(revset) => revset.clone(),
+            None => load::revset_for_scope(base, self.view.load_scope),
+        }
+    }
+
     pub fn toggle_full_mode(&mut self) {
         self.view.full_mode = !self.view.full_mode;
         self.recompute_projection();
     }
 
+    pub fn set_full_mode(&mut self, full_mode: bool) {
+        if self.view.full_mode != full_mode {
+            self.toggle_full_mode();
+        }
+    }
+
     fn recompute_projection(&mut self) {
         let current_entry_node_index = self.current_entry().map(|entry| entry.node_index);
         self.projection =
@@ -14,16 +14,22 @@ use jj_lib::id_prefix::IdPrefixIndex;
 use log::info;
 use std::time::Instant;
 
+pub(super) use revset::revset_for_scope;
+
 const CHANGE_ID_MIN_LEN: usize = 4;
 
 pub(super) fn load_tree_state(
     jj_repo: &JjRepo,
     base: &str,
     load_scope: TreeLoadScope,
+    revset: Option<&str>,
 ) -> Result<TreeState> {
     let started_at = Instant::now();
     let working_copy = jj_repo.working_copy_commit()?;
-    let revset = revset::revset_for_scope(base, load_scope);
+    let revset = match revset {
+        Some(revset) => revset.to_string(),
+        None => revset::revset_for_scope(base, load_scope),
+    };
     let commits = jj_repo.eval_revset(&revset)?;
 
     if commits.is_empty() {
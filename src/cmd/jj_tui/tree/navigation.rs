@@ -1,6 +1,7 @@
 mod cursor;
 mod expansion;
 mod focus;
+mod reveal;
 mod scroll;
 
 use super::TreeState;
@@ -0,0 +1,87 @@
+use super::super::{NeighborhoodResize, NeighborhoodState, TreeSearch, TreeState, ViewMode};
+
+impl TreeState {
+    /// Move the cursor to `node_index`, widening the view until it's shown,
+    /// returning whether the view had to change
+    pub fn reveal_node(&mut self, node_index: usize) -> bool {
+        if self.restore_cursor_to_node(node_index) {
+            return false;
+        }
+
+        if let Some(search) = self.view.search.clone().filter(|search| search.filter) {
+            self.set_search(Some(TreeSearch {
+                filter: false,
+                ..search
+            }));
+            if self.restore_cursor_to_node(node_index) {
+                return true;
+            }
+        }
+
+        if self.is_focused() {
+            self.view.focus_stack.clear();
+            self.recompute_projection();
+            if self.restore_cursor_to_node(node_index) {
+                return true;
+            }
+        }
+
+        if self.is_neighborhood_mode() {
+            self.reveal_in_neighborhood(node_index);
+        } else {
+            self.set_full_mode(true);
+        }
+        self.restore_cursor_to_node(node_index);
+        true
+    }
+
+    /// Zoom out while that doesn't need a reload, then re-anchor on the node
+    fn reveal_in_neighborhood(&mut self, node_index: usize) {
+        while self.expand_neighborhood() == NeighborhoodResize::Reprojected {
+            if self.restore_cursor_to_node(node_index) {
+                return;
+            }
+        }
+
+        if let ViewMode::Neighborhood(state) = &mut self.view.view_mode {
+            *state = NeighborhoodState::new(self.snapshot.nodes[node_index].change_id.clone());
+        }
+        self.sync_neighborhood_load_scope();
+        self.recompute_projection();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cmd::jj_tui::test_support::{TestNodeKind, make_tree};
+
+    #[test]
+    fn reveal_switches_to_full_mode_for_a_hidden_node() {
+        let mut tree = make_tree(vec![
+            TestNodeKind::Bookmarked(&["main"]).make_node("aaaa", 0),
+            TestNodeKind::Plain.make_node("bbbb", 1),
+            TestNodeKind::Bookmarked(&["feature"]).make_node("cccc", 2),
+        ]);
+        tree.set_full_mode(false);
+        assert!(!tree.restore_cursor_to_node(1));
+
+        assert!(tree.reveal_node(1));
+        assert!(tree.view.full_mode);
+        assert_eq!(tree.current_node().unwrap().change_id, "bbbb");
+    }
+
+    #[test]
+    fn reveal_leaves_focus_for_a_node_outside_it() {
+        let mut tree = make_tree(vec![
+            TestNodeKind::Plain.make_node("aaaa", 0),
+            TestNodeKind::Plain.make_node("bbbb", 1),
+            TestNodeKind::Plain.make_node("cccc", 1),
+        ]);
+        tree.focus_on(2);
+
+        assert!(tree.reveal_node(1));
+        assert!(!tree.is_focused());
+        assert_eq!(tree.current_node().unwrap().change_id, "bbbb");
+        assert!(!tree.reveal_node(1));
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
     TreeSnapshot, TreeViewState,
     neighborhood::{focused_root_index, neighborhood_state, resolve_neighborhood_anchor_index},
+    search::matches_with_ancestors,
     visible::{self, NeighborhoodFilter, VisibleOptions},
 };
 
@@ -35,6 +36,13 @@ impl TreeProjection {
                         })
                     })
                 }),
+                search_filter: view
+                    .search
+                    .as_ref()
+                    .filter(|search| search.filter)
+                    .map(|search| {
+                        matches_with_ancestors(&snapshot.nodes, &snapshot.topology, &search.query)
+                    }),
             },
         );
         Self { visible_entries }
@@ -0,0 +1,209 @@
+use super::{TreeNode, TreeState, TreeTopology};
+use ahash::HashSet;
+use std::ops::Range;
+
+/// A search over the commits in the tree
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TreeSearch {
+    pub query: String,
+    /// Show only the matches and their ancestors
+    pub filter: bool,
+}
+
+impl TreeNode {
+    /// Whether `query` appears in the description, a bookmark, either id or
+    /// the author once details are loaded, ignoring case
+    pub fn matches_search(&self, query: &str) -> bool {
+        if query.is_empty() {
+            return false;
+        }
+
+        let contains = |text: &str| !match_ranges(text, query).is_empty();
+        contains(&self.description)
+            || self
+                .bookmarks
+                .iter()
+                .any(|bookmark| contains(&bookmark.name))
+            || contains(&self.change_id)
+            || contains(&self.commit_id)
+            || self.details.as_ref().is_some_and(|details| {
+                contains(&details.author_name) || contains(&details.author_email)
+            })
+    }
+}
+
+/// Byte ranges of `query` in `text`, ignoring ASCII case
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() || query.len() > text.len() {
+        return Vec::new();
+    }
+
+    let text = text.to_ascii_lowercase();
+    let query = query.to_ascii_lowercase();
+    text.match_indices(&query)
+        .map(|(start, matched)| start..start + matched.len())
+        .collect()
+}
+
+/// Nodes matching `query` along with every ancestor, so matches keep their place in the tree
+pub(super) fn matches_with_ancestors(
+    nodes: &[TreeNode],
+    topology: &TreeTopology,
+    query: &str,
+) -> HashSet<usize> {
+    let mut keep = HashSet::default();
+    for (node_index, node) in nodes.iter().enumerate() {
+        if !node.matches_search(query) {
+            continue;
+        }
+
+        let mut current = Some(node_index);
+        while let Some(index) = current {
+            if !keep.insert(index) {
+                break;
+            }
+            current = topology.parent_of(index);
+        }
+    }
+    keep
+}
+
+impl TreeState {
+    pub fn search_query(&self) -> Option<&str> {
+        self.view
+            .search
+            .as_ref()
+            .map(|search| search.query.as_str())
+    }
+
+    pub fn set_search(&mut self, search: Option<TreeSearch>) {
+        let current = self.current_entry().map(|entry| entry.node_index);
+        self.view.search = search.filter(|search| !search.query.is_empty());
+        self.recompute_projection();
+        if let Some(node_index) = current {
+            self.restore_cursor_to_node(node_index);
+        }
+    }
+
+    /// Visible rows matching the active search, in display order
+    pub fn search_matches(&self) -> Vec<usize> {
+        let Some(query) = self.search_query() else {
+            return Vec::new();
+        };
+
+        self.visible_entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| self.snapshot.nodes[entry.node_index].matches_search(query))
+            .map(|(visible_index, _)| visible_index)
+            .collect()
+    }
+
+    /// Move the cursor to the first match at or after row `from`, wrapping
+    /// around, returning whether there was one
+    pub fn jump_to_match_from(&mut self, from: usize) -> bool {
+        let matches = self.search_matches();
+        let Some(&target) = matches
+            .iter()
+            .find(|&&index| index >= from)
+            .or_else(|| matches.first())
+        else {
+            return false;
+        };
+
+        self.view.cursor = target;
+        true
+    }
+
+    /// Move the cursor to the next or previous match, wrapping around
+    pub fn jump_to_adjacent_match(&mut self, forward: bool) -> bool {
+        let matches = self.search_matches();
+        let cursor = self.view.cursor;
+        let target = if forward {
+            matches
+                .iter()
+                .find(|&&index| index > cursor)
+                .or_else(|| matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|&&index| index < cursor)
+                .or_else(|| matches.last())
+        };
+
+        let Some(&target) = target else {
+            return false;
+        };
+        self.view.cursor = target;
+        true
+    }
+
+    /// Move the cursor to `node_index`, returning whether it's visible
+    pub fn restore_cursor_to_node(&mut self, node_index: usize) -> bool {
+        let Some(visible_index) = self
+            .visible_entries()
+            .iter()
+            .position(|entry| entry.node_index == node_index)
+        else {
+            return false;
+        };
+
+        self.view.cursor = visible_index;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TreeSearch, match_ranges};
+    use crate::cmd::jj_tui::test_support::{TestNodeKind, make_tree};
+
+    #[test]
+    fn match_ranges_ignores_case() {
+        assert_eq!(match_ranges("Fix the FIX", "fix"), vec![0..3, 8..11]);
+        assert!(match_ranges("abc", "").is_empty());
+    }
+
+    #[test]
+    fn jumps_wrap_around_matches() {
+        let mut tree = make_tree(vec![
+            TestNodeKind::Plain.make_node("aaaa", 0),
+            TestNodeKind::Plain.make_node("bbbb", 1),
+            TestNodeKind::Plain.make_node("abab", 2),
+        ]);
+        tree.set_search(Some(TreeSearch {
+            query: "a".to_string(),
+            filter: false,
+        }));
+
+        assert_eq!(tree.search_matches(), vec![0, 2]);
+        assert!(tree.jump_to_adjacent_match(true));
+        assert_eq!(tree.view.cursor, 2);
+        assert!(tree.jump_to_adjacent_match(true));
+        assert_eq!(tree.view.cursor, 0);
+        assert!(tree.jump_to_adjacent_match(false));
+        assert_eq!(tree.view.cursor, 2);
+    }
+
+    #[test]
+    fn filter_keeps_matches_and_their_ancestors() {
+        let mut tree = make_tree(vec![
+            TestNodeKind::Plain.make_node("root", 0),
+            TestNodeKind::Plain.make_node("side", 1),
+            TestNodeKind::Plain.make_node("main", 1),
+            TestNodeKind::Plain.make_node("target", 2),
+        ]);
+        tree.set_search(Some(TreeSearch {
+            query: "target".to_string(),
+            filter: true,
+        }));
+
+        let visible = tree
+            .visible_entries()
+            .iter()
+            .map(|entry| tree.nodes()[entry.node_index].change_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(visible, vec!["root", "main", "target"]);
+    }
+}
@@ -86,6 +86,11 @@ pub struct TreeViewState {
     pub scroll_offset: usize,
     pub full_mode: bool,
     pub load_scope: TreeLoadScope,
+    /// Revset typed in by the user, replacing the default one for `load_scope`
+    pub revset: Option<String>,
+    /// Named view the tree was switched to, cleared by any other revset
+    pub view_name: Option<String>,
+    pub search: Option<super::TreeSearch>,
     pub view_mode: ViewMode,
     pub expanded_entry: Option<usize>,
     pub selected: HashSet<usize>,
@@ -100,6 +105,9 @@ impl TreeViewState {
             scroll_offset: 0,
             full_mode: true,
             load_scope,
+            revset: None,
+            view_name: None,
+            search: None,
             view_mode: ViewMode::Tree,
             expanded_entry: None,
             selected: HashSet::default(),
@@ -4,6 +4,7 @@ mod neighborhood;
 mod tests;
 
 use super::{TreeNode, TreeTopology, VisibleEntry};
+use ahash::HashSet;
 use compact::{compact_mode_entries, full_mode_entries, visible_scope};
 use neighborhood::neighborhood_entries;
 
@@ -17,6 +18,8 @@ pub(super) struct VisibleOptions {
     pub full_mode: bool,
     pub focused_root: Option<usize>,
     pub neighborhood: Option<NeighborhoodFilter>,
+    /// Nodes to keep while filtering by a search, the matches and their ancestors
+    pub search_filter: Option<HashSet<usize>>,
 }
 
 pub(super) fn compute_visible_entries(
@@ -25,16 +28,26 @@ pub(super) fn compute_visible_entries(
     options: VisibleOptions,
 ) -> Vec<VisibleEntry> {
     if let Some(neighborhood) = options.neighborhood {
-        return neighborhood_entries(
+        let mut entries = neighborhood_entries(
             nodes.len(),
             topology,
             neighborhood.anchor_index,
             neighborhood.ancestor_limit,
             neighborhood.preview_depth_limit,
         );
+        if let Some(keep) = &options.search_filter {
+            entries.retain(|entry| keep.contains(&entry.node_index));
+        }
+        return entries;
     }
 
-    let (filtered_nodes, base_depth) = visible_scope(nodes, topology, options.focused_root);
+    let (mut filtered_nodes, base_depth) = visible_scope(nodes, topology, options.focused_root);
+
+    // a filtered search shows every match, so compact mode would only hide them
+    if let Some(keep) = &options.search_filter {
+        filtered_nodes.retain(|(node_index, _)| keep.contains(node_index));
+        return full_mode_entries(&filtered_nodes, base_depth);
+    }
 
     if options.full_mode {
         full_mode_entries(&filtered_nodes, base_depth)
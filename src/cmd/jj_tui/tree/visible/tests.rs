@@ -34,6 +34,7 @@ fn neighborhood_shows_all_revs_down_on_linear_path() {
                 ancestor_limit: 4,
                 preview_depth_limit: 2,
             }),
+            search_filter: None,
         },
     );
 
@@ -80,6 +81,7 @@ fn neighborhood_previews_sibling_paths_with_hidden_counts() {
                 ancestor_limit: 4,
                 preview_depth_limit: 2,
             }),
+            search_filter: None,
         },
     );
 
@@ -118,6 +120,7 @@ fn neighborhood_stops_mainline_at_first_fork_and_previews_all_children() {
                 ancestor_limit: 4,
                 preview_depth_limit: 2,
             }),
+            search_filter: None,
         },
     );
 
@@ -3,9 +3,15 @@ mod clipboard;
 mod common;
 mod confirm;
 mod conflicts;
+mod goto;
 mod help;
+mod operation_log;
 mod prefix;
+mod revset;
+mod split_hunk;
+mod stack_sync;
 mod toast;
+mod views;
 
 use super::super::app::App;
 use super::super::keybindings;
@@ -14,10 +20,16 @@ use bookmarks::{render_bookmark_picker, render_bookmark_select, render_push_sele
 use clipboard::render_clipboard_branch_select;
 use confirm::render_confirmation;
 use conflicts::render_conflicts_panel;
+use goto::render_goto_prompt;
 use help::render_help;
+use operation_log::render_operation_log;
 use prefix::render_prefix_key_popup;
 use ratatui::Frame;
+use revset::render_revset_prompt;
+use split_hunk::render_split_hunk;
+use stack_sync::render_stack_sync;
 use toast::render_toast;
+use views::render_view_picker;
 
 pub(super) fn render_overlays(frame: &mut Frame, app: &App) {
     if let ModeState::Help(ref help_state) = app.mode {
@@ -48,6 +60,30 @@ pub(super) fn render_overlays(frame: &mut Frame, app: &App) {
         render_conflicts_panel(frame, state);
     }
 
+    if let ModeState::OperationLog(ref state) = app.mode {
+        render_operation_log(frame, state);
+    }
+
+    if let ModeState::SplitHunk(ref state) = app.mode {
+        render_split_hunk(frame, state);
+    }
+
+    if let ModeState::StackSync(ref state) = app.mode {
+        render_stack_sync(frame, state);
+    }
+
+    if let ModeState::RevsetPrompt(ref state) = app.mode {
+        render_revset_prompt(frame, state);
+    }
+
+    if let ModeState::ViewPicker(ref state) = app.mode {
+        render_view_picker(frame, state);
+    }
+
+    if let ModeState::GotoPrompt(ref state) = app.mode {
+        render_goto_prompt(frame, state);
+    }
+
     if let Some(pending) = app.pending_key {
         render_prefix_key_popup(frame, keybindings::mode_id_from_state(&app.mode), pending);
     }
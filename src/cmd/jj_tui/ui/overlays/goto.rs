@@ -0,0 +1,53 @@
+use super::common::{centered_popup_area, empty_line, footer_line, key_hint, render_popup_shell};
+use crate::cmd::jj_tui::keybindings::ModeId;
+use crate::cmd::jj_tui::state::GotoPromptState;
+use crate::cmd::jj_tui::theme;
+use ratatui::{
+    Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+pub(super) fn render_goto_prompt(frame: &mut Frame, state: &GotoPromptState) {
+    let area = frame.area();
+    let error_lines = if state.error.is_some() { 2 } else { 0 };
+    let popup_height = (error_lines + 5) as u16;
+    let popup_width = 60u16.min(area.width.saturating_sub(4));
+    let popup_area = centered_popup_area(area, popup_width, popup_height.min(area.height));
+    let inner = render_popup_shell(
+        frame,
+        popup_area,
+        " Go to change ",
+        Color::Cyan,
+        theme::POPUP_BG,
+    );
+
+    let (input, input_style) = if state.input.is_empty() {
+        ("change id prefix", Color::DarkGray)
+    } else {
+        (state.input.as_str(), Color::White)
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Change: ", Style::default().fg(Color::Green)),
+        Span::styled(input.to_string(), Style::default().fg(input_style)),
+        Span::styled("█", Style::default().fg(Color::Cyan)),
+    ])];
+
+    if let Some(error) = &state.error {
+        lines.push(empty_line());
+        lines.push(Line::from(Span::styled(
+            error.lines().next().unwrap_or_default().to_string(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    lines.push(empty_line());
+    let confirm_key = key_hint(ModeId::GotoPrompt, "confirm", false);
+    let cancel_key = key_hint(ModeId::GotoPrompt, "cancel", false);
+    lines.push(footer_line(format!(
+        "{confirm_key}: go | {cancel_key}: cancel"
+    )));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
@@ -0,0 +1,90 @@
+use super::common::{centered_popup_area, key_hint, render_popup_shell};
+use crate::cmd::jj_tui::keybindings;
+use crate::cmd::jj_tui::state::OperationLogState;
+use crate::cmd::jj_tui::theme;
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const VISIBLE_ENTRIES: usize = 15;
+
+pub(super) fn render_operation_log(frame: &mut Frame, state: &OperationLogState) {
+    let area = frame.area();
+    let list_height = state.entries.len().clamp(1, VISIBLE_ENTRIES);
+    let popup_height = (5 + list_height) as u16;
+    let popup_width = 100u16.min(area.width.saturating_sub(4));
+    let popup_area = centered_popup_area(area, popup_width, popup_height);
+    let inner = render_popup_shell(
+        frame,
+        popup_area,
+        " Operation Log ",
+        Color::Cyan,
+        theme::POPUP_BG,
+    );
+
+    frame.render_widget(Paragraph::new(operation_log_lines(state)), inner);
+}
+
+fn operation_log_lines(state: &OperationLogState) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if state.entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  Loading operations...",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    // keep the selected entry visible by scrolling the window with it
+    let start = state
+        .selected_index
+        .saturating_sub(VISIBLE_ENTRIES.saturating_sub(1));
+    for (idx, entry) in state
+        .entries
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(VISIBLE_ENTRIES)
+    {
+        let is_selected = idx == state.selected_index;
+        let marker = if is_selected { "> " } else { "  " };
+        let current = if entry.is_current { "@ " } else { "  " };
+        let short_id: String = entry.op_id.chars().take(12).collect();
+        let description = if entry.description.is_empty() {
+            "(no description)".to_string()
+        } else {
+            entry.description.clone()
+        };
+        let text_style = if is_selected {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(format!("{marker}{current}"), text_style),
+            Span::styled(format!("{short_id}  "), Style::default().fg(Color::Blue)),
+            Span::styled(
+                format!("{}  ", entry.timestamp),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(description, text_style),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    let down_key = key_hint(keybindings::ModeId::OperationLog, "down", false);
+    let up_key = key_hint(keybindings::ModeId::OperationLog, "up", false);
+    let restore_key = key_hint(keybindings::ModeId::OperationLog, "restore", false);
+    let exit_keys = key_hint(keybindings::ModeId::OperationLog, "exit", true);
+    lines.push(Line::from(Span::styled(
+        format!("{down_key}/{up_key}: navigate | {restore_key}: restore | {exit_keys}: exit"),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    lines
+}
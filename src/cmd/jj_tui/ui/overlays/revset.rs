@@ -0,0 +1,70 @@
+use super::common::{centered_popup_area, empty_line, footer_line, key_hint, render_popup_shell};
+use crate::cmd::jj_tui::keybindings::ModeId;
+use crate::cmd::jj_tui::state::RevsetPromptState;
+use crate::cmd::jj_tui::theme;
+use ratatui::{
+    Frame,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+};
+
+const ERROR_LINES: usize = 4;
+
+pub(super) fn render_revset_prompt(frame: &mut Frame, state: &RevsetPromptState) {
+    let area = frame.area();
+    let error_lines = state
+        .error
+        .as_deref()
+        .map(|error| error.lines().count().clamp(1, ERROR_LINES) + 1)
+        .unwrap_or(0);
+    let popup_height = (error_lines + 5) as u16;
+    let popup_width = 80u16.min(area.width.saturating_sub(4));
+    let popup_area = centered_popup_area(area, popup_width, popup_height.min(area.height));
+    let inner = render_popup_shell(
+        frame,
+        popup_area,
+        title(state),
+        Color::Cyan,
+        theme::POPUP_BG,
+    );
+
+    let (input, input_style) = if state.input.is_empty() {
+        ("empty shows the default revset", Color::DarkGray)
+    } else {
+        (state.input.as_str(), Color::White)
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Revset: ", Style::default().fg(Color::Green)),
+        Span::styled(input.to_string(), Style::default().fg(input_style)),
+        Span::styled("█", Style::default().fg(Color::Cyan)),
+    ])];
+
+    if let Some(error) = &state.error {
+        lines.push(empty_line());
+        lines.extend(error.lines().take(ERROR_LINES).map(|line| {
+            Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(Color::Red),
+            ))
+        }));
+    }
+
+    lines.push(empty_line());
+    let up_key = key_hint(ModeId::RevsetPrompt, "up", false);
+    let down_key = key_hint(ModeId::RevsetPrompt, "down", false);
+    let confirm_key = key_hint(ModeId::RevsetPrompt, "confirm", false);
+    let cancel_key = key_hint(ModeId::RevsetPrompt, "cancel", false);
+    lines.push(footer_line(format!(
+        "{up_key}/{down_key}: history | {confirm_key}: show | {cancel_key}: cancel"
+    )));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+fn title(state: &RevsetPromptState) -> &'static str {
+    match state.history_index {
+        Some(_) => " Revset (history) ",
+        None => " Revset ",
+    }
+}
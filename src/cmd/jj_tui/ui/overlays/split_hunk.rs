@@ -0,0 +1,214 @@
+use super::common::{
+    centered_popup_area, empty_line, footer_line, key_hint, render_popup_shell, short_rev,
+};
+use crate::cmd::jj_tui::keybindings::ModeId;
+use crate::cmd::jj_tui::state::{SplitHunkRow, SplitHunkState};
+use crate::cmd::jj_tui::theme;
+use jju_jj::split_hunk::DiffLineKind;
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const VISIBLE_ROWS: usize = 12;
+const PREVIEW_LINES: usize = 8;
+
+pub(super) fn render_split_hunk(frame: &mut Frame, state: &SplitHunkState) {
+    let area = frame.area();
+    let row_count = state.rows().len().clamp(1, VISIBLE_ROWS);
+    let popup_height = (row_count + PREVIEW_LINES + 6) as u16;
+    let popup_width = 100u16.min(area.width.saturating_sub(4));
+    let popup_area = centered_popup_area(area, popup_width, popup_height.min(area.height));
+    let title = format!(
+        " Split {} ({}/{} hunks) ",
+        short_rev(&state.rev),
+        state.selected.len(),
+        state.hunk_count()
+    );
+    let inner = render_popup_shell(frame, popup_area, &title, Color::Cyan, theme::POPUP_BG);
+
+    let mut lines = checklist_lines(state);
+    lines.push(empty_line());
+    lines.extend(preview_lines(state));
+    lines.push(empty_line());
+    lines.push(footer(state));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn checklist_lines(state: &SplitHunkState) -> Vec<Line<'static>> {
+    let rows = state.rows();
+    if rows.is_empty() {
+        return vec![Line::from(Span::styled(
+            "  Loading diff...",
+            Style::default().fg(Color::DarkGray),
+        ))];
+    }
+
+    let start = state.cursor.saturating_sub(VISIBLE_ROWS.saturating_sub(1));
+    rows.iter()
+        .enumerate()
+        .skip(start)
+        .take(VISIBLE_ROWS)
+        .map(|(idx, row)| row_line(state, *row, idx == state.cursor))
+        .collect()
+}
+
+fn row_line(state: &SplitHunkState, row: SplitHunkRow, is_selected: bool) -> Line<'static> {
+    let marker = if is_selected { "> " } else { "  " };
+    let highlight = |style: Style| {
+        if is_selected {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        }
+    };
+
+    match row {
+        SplitHunkRow::File(file_index) => {
+            let (selected, total) = state.file_selection(file_index);
+            let checkbox = match selected {
+                0 => "[ ]",
+                n if n == total => "[x]",
+                _ => "[~]",
+            };
+            let file = &state.diff.files()[file_index];
+            let mut spans = vec![
+                Span::styled(
+                    format!("{marker}{checkbox} "),
+                    highlight(Style::default().fg(Color::Yellow)),
+                ),
+                Span::styled(
+                    file.path().to_string(),
+                    highlight(Style::default().fg(Color::White)),
+                ),
+                Span::styled(
+                    format!(" ({selected}/{total})"),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if let Some(summary) = file.summary() {
+                spans.push(Span::styled(
+                    format!(" {summary}"),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            Line::from(spans)
+        }
+        SplitHunkRow::Hunk(hunk) => {
+            let checkbox = if state.is_partial(&hunk) {
+                "[~]"
+            } else if state.selected.contains(&hunk) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let diff_hunk = &state.diff.files()[hunk.file_index].hunks()[hunk.hunk_index];
+            let (added, removed) = diff_hunk.lines().iter().fold((0, 0), |(a, r), line| {
+                (
+                    a + usize::from(line.kind.is_added()),
+                    r + usize::from(line.kind.is_removed()),
+                )
+            });
+            Line::from(vec![
+                Span::styled(
+                    format!("{marker}    {checkbox} "),
+                    highlight(Style::default().fg(Color::Yellow)),
+                ),
+                Span::styled(
+                    diff_hunk.header(),
+                    highlight(Style::default().fg(Color::Cyan)),
+                ),
+                Span::styled(format!(" +{added}"), Style::default().fg(Color::Green)),
+                Span::styled(format!(" -{removed}"), Style::default().fg(Color::Red)),
+            ])
+        }
+        SplitHunkRow::Line(hunk, line_index) => {
+            let checkbox = if state.is_line_selected(&hunk, line_index) {
+                "[x]"
+            } else {
+                "[ ]"
+            };
+            let line =
+                &state.diff.files()[hunk.file_index].hunks()[hunk.hunk_index].lines()[line_index];
+            let (prefix, color) = line_style(line.kind);
+            Line::from(vec![
+                Span::styled(
+                    format!("{marker}        {checkbox} "),
+                    highlight(Style::default().fg(Color::Yellow)),
+                ),
+                Span::styled(
+                    format!("{prefix}{}", line.content),
+                    highlight(Style::default().fg(color)),
+                ),
+            ])
+        }
+    }
+}
+
+fn line_style(kind: DiffLineKind) -> (&'static str, Color) {
+    match kind {
+        DiffLineKind::Added => ("+", Color::Green),
+        DiffLineKind::Removed => ("-", Color::Red),
+        DiffLineKind::Context => (" ", Color::DarkGray),
+    }
+}
+
+fn preview_lines(state: &SplitHunkState) -> Vec<Line<'static>> {
+    let hunk = match state.current_row() {
+        Some(SplitHunkRow::Hunk(hunk) | SplitHunkRow::Line(hunk, _)) => hunk,
+        Some(SplitHunkRow::File(file_index)) => match state.file_hunks(file_index).next() {
+            Some(hunk) => hunk,
+            None => return Vec::new(),
+        },
+        None => return Vec::new(),
+    };
+    let diff_hunk = &state.diff.files()[hunk.file_index].hunks()[hunk.hunk_index];
+
+    let mut lines: Vec<Line<'static>> = diff_hunk
+        .lines()
+        .iter()
+        .take(PREVIEW_LINES)
+        .map(|line| {
+            let (prefix, color) = line_style(line.kind);
+            Line::from(Span::styled(
+                format!("  {prefix}{}", line.content),
+                Style::default().fg(color),
+            ))
+        })
+        .collect();
+
+    let hidden = diff_hunk.lines().len().saturating_sub(PREVIEW_LINES);
+    if hidden > 0 {
+        lines.push(footer_line(format!("  ... {hidden} more lines")));
+    }
+    lines
+}
+
+fn footer(state: &SplitHunkState) -> Line<'static> {
+    if let Some(message) = &state.message {
+        let confirm_key = key_hint(ModeId::SplitHunkMessage, "confirm", false);
+        let cancel_key = key_hint(ModeId::SplitHunkMessage, "cancel", false);
+        return Line::from(vec![
+            Span::styled("Message: ", Style::default().fg(Color::Green)),
+            Span::styled(message.clone(), Style::default().fg(Color::White)),
+            Span::styled("█", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("  {confirm_key}: split | {cancel_key}: back"),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+    }
+
+    let toggle_key = key_hint(ModeId::SplitHunk, "toggle", false);
+    let lines_key = key_hint(ModeId::SplitHunk, "lines", false);
+    let all_key = key_hint(ModeId::SplitHunk, "all", false);
+    let none_key = key_hint(ModeId::SplitHunk, "none", false);
+    let confirm_key = key_hint(ModeId::SplitHunk, "confirm", false);
+    let cancel_keys = key_hint(ModeId::SplitHunk, "cancel", true);
+    footer_line(format!(
+        "{toggle_key}: toggle | {lines_key}: lines | {all_key}: all | {none_key}: none | {confirm_key}: message | {cancel_keys}: cancel"
+    ))
+}
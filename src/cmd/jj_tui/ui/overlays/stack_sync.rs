@@ -0,0 +1,316 @@
+use super::common::{centered_popup_area, empty_line, footer_line, key_hint, render_popup_shell};
+use crate::cmd::jj_tui::keybindings::ModeId;
+use crate::cmd::jj_tui::state::{
+    StackSyncPhase, StackSyncState, StackSyncStep, StackSyncStepKind, StackSyncStepStatus,
+};
+use crate::cmd::jj_tui::theme;
+use jju_core::stack_sync::StackSyncPlan;
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const VISIBLE_ROWS: usize = 12;
+const PREVIEW_LINES: usize = 8;
+
+pub(super) fn render_stack_sync(frame: &mut Frame, state: &StackSyncState) {
+    let area = frame.area();
+    let row_count = state.row_count().clamp(1, VISIBLE_ROWS);
+    let popup_height = (row_count + PREVIEW_LINES + 6) as u16;
+    let popup_width = 100u16.min(area.width.saturating_sub(4));
+    let popup_area = centered_popup_area(area, popup_width, popup_height.min(area.height));
+    let inner = render_popup_shell(
+        frame,
+        popup_area,
+        &title(state),
+        Color::Cyan,
+        theme::POPUP_BG,
+    );
+
+    let lines = match (&state.plan, state.shows_progress()) {
+        (None, _) => vec![Line::from(Span::styled(
+            "  Discovering stacks...",
+            Style::default().fg(Color::DarkGray),
+        ))],
+        (Some(plan), false) => plan_lines(state, plan),
+        (Some(plan), true) => progress_lines(state, plan),
+    };
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn title(state: &StackSyncState) -> String {
+    let Some(plan) = &state.plan else {
+        return " Stack sync ".to_string();
+    };
+
+    match state.phase {
+        StackSyncPhase::Loading | StackSyncPhase::Choosing => format!(
+            " Stack sync onto {} ({}/{} stacks) ",
+            plan.onto.label,
+            state.selected.len() + state.abandon.len(),
+            plan.roots.len()
+        ),
+        StackSyncPhase::Running if state.cancelled => {
+            format!(" Cancelling sync onto {}... ", plan.onto.label)
+        }
+        StackSyncPhase::Running => format!(" Syncing onto {}... ", plan.onto.label),
+        StackSyncPhase::Finished => format!(" Stack sync onto {} finished ", plan.onto.label),
+    }
+}
+
+fn plan_lines(state: &StackSyncState, plan: &StackSyncPlan) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if plan.roots.is_empty() {
+        lines.push(footer_line(format!(
+            "  Nothing to rebase, syncing will fetch and update {}",
+            plan.trunk
+        )));
+    }
+
+    let start = state.cursor.saturating_sub(VISIBLE_ROWS.saturating_sub(1));
+    for (index, root) in plan.roots.iter().enumerate().skip(start).take(VISIBLE_ROWS) {
+        let is_current = index == state.cursor;
+        let marker = if is_current { "> " } else { "  " };
+        let checkbox = if state.abandon.contains(&index) {
+            "[x] abandon"
+        } else if state.selected.contains(&index) {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let commits = root.commits.len();
+        let mut spans = vec![
+            Span::styled(
+                format!("{marker}{checkbox} "),
+                highlight(Style::default().fg(Color::Yellow), is_current),
+            ),
+            Span::styled(
+                format!("{}  ", root.change_id),
+                highlight(Style::default().fg(Color::Magenta), is_current),
+            ),
+            Span::styled(
+                description(&root.description),
+                highlight(Style::default().fg(Color::White), is_current),
+            ),
+            Span::styled(
+                format!(" ({commits} commit{})", if commits == 1 { "" } else { "s" }),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        if let Some(landed) = &root.landed {
+            spans.push(Span::styled(
+                format!("  landed as {landed}"),
+                Style::default().fg(Color::Green),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(empty_line());
+    lines.extend(commit_preview(state, plan));
+    lines.push(empty_line());
+    lines.push(Line::from(vec![
+        Span::styled(
+            if state.push { "[x] " } else { "[ ] " },
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            format!(
+                "push the first bookmark of this stack to {} afterwards",
+                plan.remotes.push
+            ),
+            Style::default().fg(Color::White),
+        ),
+    ]));
+
+    let toggle_key = key_hint(ModeId::StackSync, "toggle", false);
+    let all_key = key_hint(ModeId::StackSync, "all", false);
+    let none_key = key_hint(ModeId::StackSync, "none", false);
+    let push_key = key_hint(ModeId::StackSync, "push", false);
+    let confirm_key = key_hint(ModeId::StackSync, "confirm", false);
+    let cancel_keys = key_hint(ModeId::StackSync, "cancel", true);
+    lines.push(footer_line(format!(
+        "{toggle_key}: toggle | {all_key}: all | {none_key}: none | {push_key}: push | {confirm_key}: sync | {cancel_keys}: cancel"
+    )));
+    lines
+}
+
+/// The commits that move with the root under the cursor
+fn commit_preview(state: &StackSyncState, plan: &StackSyncPlan) -> Vec<Line<'static>> {
+    let Some(root) = plan.roots.get(state.cursor) else {
+        return Vec::new();
+    };
+
+    let mut lines: Vec<Line<'static>> = root
+        .commits
+        .iter()
+        .take(PREVIEW_LINES)
+        .map(|commit| {
+            Line::from(vec![
+                Span::styled(
+                    format!("    {}  ", commit.change_id),
+                    Style::default().fg(Color::Magenta),
+                ),
+                Span::styled(
+                    description(&commit.description),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        })
+        .collect();
+
+    let hidden = root.commits.len().saturating_sub(PREVIEW_LINES);
+    if hidden > 0 {
+        lines.push(footer_line(format!("    ... {hidden} more commits")));
+    }
+    lines
+}
+
+fn progress_lines(state: &StackSyncState, plan: &StackSyncPlan) -> Vec<Line<'static>> {
+    let start = state.cursor.saturating_sub(VISIBLE_ROWS.saturating_sub(1));
+    let mut lines: Vec<Line<'static>> = state
+        .steps
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(VISIBLE_ROWS)
+        .map(|(index, step)| step_line(step, plan, index == state.cursor))
+        .collect();
+
+    lines.push(empty_line());
+    lines.extend(detail_lines(state));
+    lines.push(empty_line());
+
+    let down_key = key_hint(ModeId::StackSyncProgress, "down", false);
+    let up_key = key_hint(ModeId::StackSyncProgress, "up", false);
+    let details_key = key_hint(ModeId::StackSyncProgress, "details", false);
+    let close_keys = key_hint(ModeId::StackSyncProgress, "close", true);
+    let close = if state.is_running() && !state.cancelled {
+        format!(" | {close_keys}: cancel")
+    } else {
+        format!(" | {close_keys}: close")
+    };
+    lines.push(footer_line(format!(
+        "{down_key}/{up_key}: navigate | {details_key}: details{close}"
+    )));
+    lines
+}
+
+fn step_line(step: &StackSyncStep, plan: &StackSyncPlan, is_current: bool) -> Line<'static> {
+    let marker = if is_current { "> " } else { "  " };
+    let (icon, color) = match step.status {
+        StackSyncStepStatus::Pending => ("·", Color::DarkGray),
+        StackSyncStepStatus::Running => ("…", Color::Yellow),
+        StackSyncStepStatus::Done(_) => ("✓", Color::Green),
+        StackSyncStepStatus::Warning(_) => ("!", Color::Yellow),
+        StackSyncStepStatus::Failed(_) => ("✗", Color::Red),
+        StackSyncStepStatus::Skipped => ("-", Color::DarkGray),
+    };
+    let label_color = match step.status {
+        StackSyncStepStatus::Pending | StackSyncStepStatus::Skipped => Color::DarkGray,
+        _ => Color::White,
+    };
+
+    let mut spans = vec![
+        Span::styled(
+            format!("{marker}{icon} "),
+            highlight(Style::default().fg(color), is_current),
+        ),
+        Span::styled(
+            step_label(&step.kind, plan),
+            highlight(Style::default().fg(label_color), is_current),
+        ),
+    ];
+    let note = match &step.status {
+        StackSyncStepStatus::Done(Some(note)) | StackSyncStepStatus::Warning(note) => {
+            Some(note.clone())
+        }
+        StackSyncStepStatus::Failed(stderr) => {
+            Some(stderr.lines().next().unwrap_or_default().to_string())
+        }
+        StackSyncStepStatus::Skipped => Some("skipped".to_string()),
+        _ => None,
+    };
+    if let Some(note) = note {
+        spans.push(Span::styled(
+            format!("  {note}"),
+            Style::default().fg(color),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn step_label(kind: &StackSyncStepKind, plan: &StackSyncPlan) -> String {
+    match kind {
+        StackSyncStepKind::Fetch => format!("Fetch {}", plan.remotes.to_fetch().join(", ")),
+        StackSyncStepKind::SyncTrunk => {
+            format!(
+                "Sync {} with {}@{}",
+                plan.trunk, plan.trunk, plan.remotes.trunk
+            )
+        }
+        StackSyncStepKind::Abandon { root } => {
+            format!("Abandon landed {root} {}", root_description(plan, root))
+        }
+        StackSyncStepKind::Rebase { root } => {
+            format!("Rebase {root} {}", root_description(plan, root))
+        }
+        StackSyncStepKind::Cleanup => "Clean up merged bookmarks".to_string(),
+        StackSyncStepKind::Push => format!("Push first bookmark to {}", plan.remotes.push),
+    }
+}
+
+fn root_description(plan: &StackSyncPlan, root: &str) -> String {
+    plan.roots
+        .iter()
+        .find(|plan_root| plan_root.change_id == root)
+        .map(|plan_root| description(&plan_root.description))
+        .unwrap_or_default()
+}
+
+/// The stderr of the failed step under the cursor, or a hint to show it
+fn detail_lines(state: &StackSyncState) -> Vec<Line<'static>> {
+    let Some(stderr) = state.current_error() else {
+        return Vec::new();
+    };
+
+    if !state.show_details {
+        let details_key = key_hint(ModeId::StackSyncProgress, "details", false);
+        return vec![footer_line(format!("  {details_key}: show stderr"))];
+    }
+
+    let mut lines: Vec<Line<'static>> = stderr
+        .lines()
+        .take(PREVIEW_LINES)
+        .map(|line| {
+            Line::from(Span::styled(
+                format!("  {line}"),
+                Style::default().fg(Color::Red),
+            ))
+        })
+        .collect();
+    let hidden = stderr.lines().count().saturating_sub(PREVIEW_LINES);
+    if hidden > 0 {
+        lines.push(footer_line(format!("  ... {hidden} more lines")));
+    }
+    lines
+}
+
+fn description(description: &str) -> String {
+    if description.is_empty() {
+        "(no description)".to_string()
+    } else {
+        description.to_string()
+    }
+}
+
+fn highlight(style: Style, is_current: bool) -> Style {
+    if is_current {
+        style.add_modifier(Modifier::BOLD)
+    } else {
+        style
+    }
+}
@@ -0,0 +1,76 @@
+use super::common::{centered_popup_area, empty_line, footer_line, key_hint, render_popup_shell};
+use crate::cmd::jj_tui::keybindings::ModeId;
+use crate::cmd::jj_tui::state::ViewPickerState;
+use crate::cmd::jj_tui::theme;
+use crate::cmd::jj_tui::views::VIEW_SLOTS;
+use ratatui::{
+    Frame,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+const VISIBLE_ROWS: usize = 12;
+
+pub(super) fn render_view_picker(frame: &mut Frame, state: &ViewPickerState) {
+    let area = frame.area();
+    let mut row_count = state.row_count().min(VISIBLE_ROWS);
+    if state.views.is_empty() {
+        row_count += 1;
+    }
+    let popup_height = (row_count + 4) as u16;
+    let popup_width = 80u16.min(area.width.saturating_sub(4));
+    let popup_area = centered_popup_area(area, popup_width, popup_height.min(area.height));
+    let inner = render_popup_shell(frame, popup_area, " Views ", Color::Cyan, theme::POPUP_BG);
+
+    let start = state.cursor.saturating_sub(VISIBLE_ROWS.saturating_sub(1));
+    let mut lines: Vec<Line<'static>> = (0..state.row_count())
+        .skip(start)
+        .take(VISIBLE_ROWS)
+        .map(|row| view_line(state, row))
+        .collect();
+    if state.views.is_empty() {
+        lines.push(footer_line(
+            "  No views configured, add them to jju/views.toml".to_string(),
+        ));
+    }
+
+    lines.push(empty_line());
+    let down_key = key_hint(ModeId::ViewPicker, "down", false);
+    let up_key = key_hint(ModeId::ViewPicker, "up", false);
+    let confirm_key = key_hint(ModeId::ViewPicker, "confirm", false);
+    let cancel_keys = key_hint(ModeId::ViewPicker, "cancel", true);
+    lines.push(footer_line(format!(
+        "{down_key}/{up_key}: navigate | {confirm_key}: show | {cancel_keys}: cancel"
+    )));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn view_line(state: &ViewPickerState, row: usize) -> Line<'static> {
+    let is_current = row == state.cursor;
+    let marker = if is_current { "> " } else { "  " };
+    // rows past the ninth view have no direct key
+    let slot = if row <= VIEW_SLOTS {
+        row.to_string()
+    } else {
+        " ".to_string()
+    };
+    let (name, revset) = match row.checked_sub(1).and_then(|index| state.views.get(index)) {
+        Some(view) => (view.name.clone(), view.revset.clone()),
+        None => ("default".to_string(), "the default revset".to_string()),
+    };
+
+    let mut name_style = Style::default().fg(Color::White);
+    if is_current {
+        name_style = name_style.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    }
+    Line::from(vec![
+        Span::styled(
+            format!("{marker}{slot}  "),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(format!("{name:<16}"), name_style),
+        Span::styled(revset, Style::default().fg(Color::DarkGray)),
+    ])
+}
@@ -2,6 +2,7 @@ mod current;
 mod indicators;
 
 use super::super::app::App;
+use super::super::state::{ModeState, SearchPromptState};
 use super::super::theme;
 use ratatui::{
     Frame,
@@ -12,17 +13,10 @@ use ratatui::{
 use unicode_width::UnicodeWidthStr;
 
 pub(super) fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
-    let left = format!(
-        " {}{}{}{}{}{}{}{}",
-        indicators::mode_indicator(app),
-        indicators::full_indicator(app),
-        indicators::neighborhood_indicator(app),
-        indicators::split_indicator(app),
-        indicators::focus_indicator(app),
-        indicators::pending_indicator(app),
-        indicators::selection_indicator(app),
-        current::current_info(app),
-    );
+    let left = match &app.mode {
+        ModeState::Search(state) => search_prompt(app, state),
+        _ => indicators_line(app),
+    };
     let hints = indicators::hints(app);
     let right = format!("{hints} ");
 
@@ -42,3 +36,33 @@ pub(super) fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
     frame.render_widget(bar, area);
 }
+
+fn indicators_line(app: &App) -> String {
+    format!(
+        " {}{}{}{}{}{}{}{}{}{}{}{}{}",
+        indicators::mode_indicator(app),
+        indicators::effect_indicator(app),
+        indicators::full_indicator(app),
+        indicators::revset_indicator(app),
+        indicators::search_indicator(app),
+        indicators::neighborhood_indicator(app),
+        indicators::split_indicator(app),
+        indicators::focus_indicator(app),
+        indicators::pending_indicator(app),
+        indicators::selection_indicator(app),
+        indicators::undo_indicator(app),
+        indicators::updated_indicator(app),
+        current::current_info(app),
+    )
+}
+
+fn search_prompt(app: &App, state: &SearchPromptState) -> String {
+    let matches = app.tree.search_matches().len();
+    let count = if state.input.is_empty() {
+        String::new()
+    } else {
+        format!("  {matches} match{}", if matches == 1 { "" } else { "es" })
+    };
+    let filter = if state.filter { " [FILTER]" } else { "" };
+    format!(" /{}█{count}{filter}", state.input)
+}
@@ -25,6 +25,34 @@ pub(super) fn mode_indicator(app: &App) -> &'static str {
         ModeState::PushSelect(_) => "PUSH SELECT",
         ModeState::Squashing(_) => "SQUASH",
         ModeState::Conflicts(_) => "CONFLICTS",
+        ModeState::OperationLog(_) => "OP LOG",
+        ModeState::SplitHunk(_) => "SPLIT HUNK",
+        ModeState::StackSync(_) => "STACK SYNC",
+        ModeState::RevsetPrompt(_) => "REVSET",
+        ModeState::ViewPicker(_) => "VIEWS",
+        ModeState::Search(_) => "SEARCH",
+        ModeState::GotoPrompt(_) => "GOTO",
+    }
+}
+
+pub(super) fn effect_indicator(app: &App) -> String {
+    let Some(task) = &app.effect_task else {
+        return String::new();
+    };
+
+    let queued = match app.queued_effects.len() {
+        0 => String::new(),
+        count => format!(" +{count} queued"),
+    };
+    if task.is_cancelled() {
+        format!(" [{} cancelling {}]", task.spinner(), task.label)
+    } else {
+        format!(
+            " [{} {} {}s{queued} · Esc cancel]",
+            task.spinner(),
+            task.label,
+            task.elapsed().as_secs()
+        )
     }
 }
 
@@ -36,6 +64,33 @@ pub(super) fn full_indicator(app: &App) -> &'static str {
     }
 }
 
+pub(super) fn revset_indicator(app: &App) -> String {
+    if let Some(name) = &app.tree.view.view_name {
+        return format!(" [VIEW:{name}]");
+    }
+
+    app.tree
+        .view
+        .revset
+        .as_deref()
+        .map(|revset| format!(" [REVSET:{revset}]"))
+        .unwrap_or_default()
+}
+
+pub(super) fn search_indicator(app: &App) -> String {
+    let Some(search) = &app.tree.view.search else {
+        return String::new();
+    };
+
+    let matches = app.tree.search_matches().len();
+    let filter = if search.filter { " filtered" } else { "" };
+    format!(
+        " [/{} {matches} match{}{filter}]",
+        search.query,
+        if matches == 1 { "" } else { "es" }
+    )
+}
+
 pub(super) fn neighborhood_indicator(app: &App) -> String {
     app.tree
         .neighborhood_state()
@@ -79,6 +134,21 @@ pub(super) fn selection_indicator(app: &App) -> String {
     }
 }
 
+pub(super) fn undo_indicator(app: &App) -> String {
+    app.undo_history
+        .next_undo()
+        .map(|entry| format!(" [UNDO:{}]", entry.label))
+        .unwrap_or_default()
+}
+
+pub(super) fn updated_indicator(app: &App) -> &'static str {
+    if app.repo_recently_updated() {
+        " [UPDATED]"
+    } else {
+        ""
+    }
+}
+
 pub(super) fn hints(app: &App) -> String {
     let rebase_allow_branches = match &app.mode {
         ModeState::Rebasing(state) => Some(state.allow_branches),
@@ -90,6 +160,7 @@ pub(super) fn hints(app: &App) -> String {
         has_selection: !app.tree.view.selected.is_empty(),
         has_focus: app.tree.is_focused(),
         neighborhood_active: app.tree.is_neighborhood_mode(),
+        search_active: app.tree.view.search.is_some(),
         current_has_bookmark: app.current_has_bookmark(),
         rebase_allow_branches,
     })
@@ -43,12 +43,17 @@ fn test_split_view_renders_tree_and_diff_when_viewing_diff() {
         split_view: true,
         diff_stats_cache: std::collections::HashMap::new(),
         status_message: None,
-        last_op: None,
+        undo_history: Default::default(),
         pending_key: None,
         syntax_set: SyntaxSet::load_defaults_newlines(),
         theme_set: ThemeSet::load_defaults(),
         repo_path: std::env::current_dir().unwrap_or_default(),
         row_data_loader: Default::default(),
+        stack_sync_task: None,
+        views: Vec::new(),
+        repo_watcher: None,
+        effect_task: None,
+        queued_effects: Default::default(),
     };
 
     let backend = TestBackend::new(80, 20);
@@ -85,12 +90,17 @@ fn test_pane_plan_no_secondary_viewing_diff() {
         split_view: false,
         diff_stats_cache: std::collections::HashMap::new(),
         status_message: None,
-        last_op: None,
+        undo_history: Default::default(),
         pending_key: None,
         syntax_set: SyntaxSet::load_defaults_newlines(),
         theme_set: ThemeSet::load_defaults(),
         repo_path: std::env::current_dir().unwrap_or_default(),
         row_data_loader: Default::default(),
+        stack_sync_task: None,
+        views: Vec::new(),
+        repo_watcher: None,
+        effect_task: None,
+        queued_effects: Default::default(),
     };
 
     let plan = pane_plan(&app, false);
@@ -113,12 +123,17 @@ fn test_pane_plan_with_secondary_viewing_diff() {
         split_view: true,
         diff_stats_cache: std::collections::HashMap::new(),
         status_message: None,
-        last_op: None,
+        undo_history: Default::default(),
         pending_key: None,
         syntax_set: SyntaxSet::load_defaults_newlines(),
         theme_set: ThemeSet::load_defaults(),
         repo_path: std::env::current_dir().unwrap_or_default(),
         row_data_loader: Default::default(),
+        stack_sync_task: None,
+        views: Vec::new(),
+        repo_watcher: None,
+        effect_task: None,
+        queued_effects: Default::default(),
     };
 
     let plan = pane_plan(&app, true);
@@ -136,12 +151,17 @@ fn test_pane_plan_with_secondary_normal_mode() {
         split_view: true,
         diff_stats_cache: std::collections::HashMap::new(),
         status_message: None,
-        last_op: None,
+        undo_history: Default::default(),
         pending_key: None,
         syntax_set: SyntaxSet::load_defaults_newlines(),
         theme_set: ThemeSet::load_defaults(),
         repo_path: std::env::current_dir().unwrap_or_default(),
         row_data_loader: Default::default(),
+        stack_sync_task: None,
+        views: Vec::new(),
+        repo_watcher: None,
+        effect_task: None,
+        queued_effects: Default::default(),
     };
 
     let plan = pane_plan(&app, true);
@@ -1,38 +1,38 @@
 use super::bookmarks::format_bookmarks_truncated;
 use crate::cmd::jj_tui::preview::NodeRole;
 use crate::cmd::jj_tui::theme;
+use crate::cmd::jj_tui::tree::match_ranges;
 use crate::cmd::jj_tui::vm::{InlineRowBadge, Marker, TreeRowVm};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
+use std::ops::Range;
 
 pub(super) fn render_row(vm: &TreeRowVm) -> Line<'static> {
     let is_source = matches!(vm.role, NodeRole::Source | NodeRole::Moving);
+    let query = vm.search_query.as_deref();
     let mut spans = indicator_spans(vm);
 
-    spans.extend([
-        prefix_fragment(vm),
-        Span::styled(
-            vm.change_id_prefix.clone(),
-            Style::default().fg(prefix_color(is_source)),
-        ),
-        suffix_fragment(vm),
-        Span::raw(")"),
-    ]);
+    spans.push(prefix_fragment(vm));
+    spans.extend(change_id_spans(vm, is_source));
+    spans.push(Span::raw(")"));
 
     if !vm.bookmarks.is_empty() {
         spans.push(Span::raw(" "));
-        spans.push(Span::styled(
-            format_bookmarks_truncated(&vm.bookmarks, 30),
+        spans.extend(search_spans(
+            &format_bookmarks_truncated(&vm.bookmarks, 30),
             Style::default().fg(bookmark_color(is_source)),
+            query,
         ));
     }
 
     spans.extend(inline_badge_spans(vm));
-    spans.push(Span::styled(
-        format!("  {}", vm.description),
+    spans.push(Span::raw("  "));
+    spans.extend(search_spans(
+        &vm.description,
         Style::default().fg(Color::Reset),
+        query,
     ));
 
     if vm.is_neighborhood_preview && vm.neighborhood_hidden_count > 0 {
@@ -81,11 +81,65 @@ fn prefix_fragment(vm: &TreeRowVm) -> Span<'static> {
     ))
 }
 
-fn suffix_fragment(vm: &TreeRowVm) -> Span<'static> {
-    Span::styled(
-        vm.change_id_suffix.clone(),
+/// The unique prefix and dimmed suffix of the change id, with search matches
+/// found across the whole id
+fn change_id_spans(vm: &TreeRowVm, is_source: bool) -> Vec<Span<'static>> {
+    let change_id = format!("{}{}", vm.change_id_prefix, vm.change_id_suffix);
+    let ranges = vm
+        .search_query
+        .as_deref()
+        .map(|query| match_ranges(&change_id, query))
+        .unwrap_or_default();
+    let prefix_len = vm.change_id_prefix.len();
+
+    let mut spans = highlighted_spans(
+        &vm.change_id_prefix,
+        Style::default().fg(prefix_color(is_source)),
+        &clip_ranges(&ranges, 0..prefix_len),
+    );
+    spans.extend(highlighted_spans(
+        &vm.change_id_suffix,
         Style::default().add_modifier(Modifier::DIM),
-    )
+        &clip_ranges(&ranges, prefix_len..change_id.len()),
+    ));
+    spans
+}
+
+fn search_spans(text: &str, style: Style, query: Option<&str>) -> Vec<Span<'static>> {
+    let ranges = query
+        .map(|query| match_ranges(text, query))
+        .unwrap_or_default();
+    highlighted_spans(text, style, &ranges)
+}
+
+/// Split `text` into spans, marking `ranges` as search matches
+fn highlighted_spans(text: &str, style: Style, ranges: &[Range<usize>]) -> Vec<Span<'static>> {
+    let match_style = Style::default().fg(Color::Black).bg(theme::SEARCH_MATCH_BG);
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for range in ranges {
+        if range.start > start {
+            spans.push(Span::styled(text[start..range.start].to_string(), style));
+        }
+        spans.push(Span::styled(text[range.clone()].to_string(), match_style));
+        start = range.end;
+    }
+    if start < text.len() || spans.is_empty() {
+        spans.push(Span::styled(text[start..].to_string(), style));
+    }
+    spans
+}
+
+/// The parts of `ranges` inside `part`, relative to its start
+fn clip_ranges(ranges: &[Range<usize>], part: Range<usize>) -> Vec<Range<usize>> {
+    ranges
+        .iter()
+        .filter_map(|range| {
+            let start = range.start.max(part.start);
+            let end = range.end.min(part.end);
+            (start < end).then(|| start - part.start..end - part.start)
+        })
+        .collect()
 }
 
 fn indicator_spans(vm: &TreeRowVm) -> Vec<Span<'static>> {
@@ -169,6 +223,7 @@ fn apply_row_style(vm: &TreeRowVm, is_source: bool, line: Line<'static>) -> Line
 mod tests {
     use super::render_row;
     use crate::cmd::jj_tui::state::DiffStats;
+    use crate::cmd::jj_tui::theme;
     use crate::cmd::jj_tui::vm::{InlineRowBadge, TreeRowVm};
 
     fn row_text(vm: &TreeRowVm) -> String {
@@ -202,6 +257,7 @@ mod tests {
             details: None,
             height: 1,
             has_separator_before: false,
+            search_query: None,
         }
     }
 
@@ -243,4 +299,23 @@ mod tests {
         assert!(row.contains("+0 -0"));
         assert!(!row.contains("∅"));
     }
+
+    #[test]
+    fn highlights_search_matches_across_change_id_prefix_and_suffix() {
+        let mut vm = make_vm();
+        vm.change_id_suffix = "efgh".to_string();
+        vm.description = "add Docs".to_string();
+        vm.search_query = Some("d".to_string());
+
+        let line = render_row(&vm);
+        let highlighted = line
+            .spans
+            .iter()
+            .filter(|span| span.style.bg == Some(theme::SEARCH_MATCH_BG))
+            .map(|span| span.content.as_ref())
+            .collect::<Vec<_>>();
+
+        assert_eq!(highlighted, vec!["d", "d", "d", "D"]);
+        assert!(row_text(&vm).contains("(abcdefgh)  add Docs"));
+    }
 }
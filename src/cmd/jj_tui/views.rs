@@ -0,0 +1,144 @@
+//! Named views from `views.toml`, each a revset with its own display defaults
+
+use eyre::{Result, bail, eyre};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Views reachable with the `1`-`9` keys, in config order
+pub(crate) const VIEW_SLOTS: usize = 9;
+
+/// A saved revset the tree can switch to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedView {
+    pub name: String,
+    pub revset: String,
+    /// Whether to show every commit, left as is when unset
+    pub full_mode: Option<bool>,
+    /// Whether to open the neighborhood, left as is when unset
+    pub neighborhood: Option<bool>,
+}
+
+/// The configured views, with a warning when the config couldn't be used
+pub(crate) fn load() -> (Vec<NamedView>, Option<String>) {
+    let Some(path) = config_path() else {
+        return (Vec::new(), None);
+    };
+
+    match load_views(&path) {
+        Ok(views) => (views, None),
+        Err(error) => (Vec::new(), Some(error.to_string())),
+    }
+}
+
+fn load_views(path: &Path) -> Result<Vec<NamedView>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| eyre!("failed to read {}: {error}", path.display()))?;
+    parse_views(&text)
+}
+
+fn parse_views(text: &str) -> Result<Vec<NamedView>> {
+    let file: ViewsFile =
+        toml::from_str(text).map_err(|error| eyre!("failed to parse views TOML: {error}"))?;
+
+    let mut views: Vec<NamedView> = Vec::with_capacity(file.view.len());
+    for view in file.view {
+        if view.name.trim().is_empty() {
+            bail!("view for `{}` must have a name", view.revset);
+        }
+        if view.revset.trim().is_empty() {
+            bail!("view `{}` must define a revset", view.name);
+        }
+        if views.iter().any(|existing| existing.name == view.name) {
+            bail!("view `{}` is defined more than once", view.name);
+        }
+
+        views.push(NamedView {
+            name: view.name,
+            revset: view.revset,
+            full_mode: view.full,
+            neighborhood: view.neighborhood,
+        });
+    }
+
+    Ok(views)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ViewsFile {
+    #[serde(default)]
+    view: Vec<ViewToml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ViewToml {
+    name: String,
+    revset: String,
+    full: Option<bool>,
+    neighborhood: Option<bool>,
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(config_home) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(config_home).join("jju/views.toml"));
+    }
+
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .map(|home| home.join(".config/jju/views.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_views;
+
+    #[test]
+    fn parse_views_keeps_config_order_and_optional_defaults() {
+        let views = parse_views(
+            r#"
+            [[view]]
+            name = "mine"
+            revset = "mine() & mutable()"
+            full = false
+
+            [[view]]
+            name = "all-heads"
+            revset = "heads(all())"
+            neighborhood = true
+            "#,
+        )
+        .unwrap();
+
+        let names = views
+            .iter()
+            .map(|view| view.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["mine", "all-heads"]);
+        assert_eq!(views[0].full_mode, Some(false));
+        assert_eq!(views[0].neighborhood, None);
+        assert_eq!(views[1].neighborhood, Some(true));
+    }
+
+    #[test]
+    fn parse_views_rejects_duplicate_names() {
+        let error = parse_views(
+            r#"
+            [[view]]
+            name = "mine"
+            revset = "mine()"
+
+            [[view]]
+            name = "mine"
+            revset = "@"
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(error.to_string().contains("more than once"));
+    }
+}
@@ -47,6 +47,7 @@ impl OperationViewBuilder<'_> {
                     .inline_diff_stats(inline_diff_stats)
                     .details(details)
                     .separator_before(entry.has_separator_before)
+                    .search_query(self.app.tree.search_query())
                     .build()
             })
             .collect()
@@ -25,6 +25,8 @@ pub struct TreeRowVm {
     pub details: Option<RowDetails>,
     pub height: usize,
     pub has_separator_before: bool,
+    /// Active search, highlighted wherever it appears in the row
+    pub search_query: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -55,6 +57,7 @@ pub(super) struct RowVmBuilder<'a> {
     inline_diff_stats: Option<DiffStats>,
     details: Option<RowDetails>,
     has_separator_before: bool,
+    search_query: Option<String>,
 }
 
 impl<'a> RowVmBuilder<'a> {
@@ -73,6 +76,7 @@ impl<'a> RowVmBuilder<'a> {
             inline_diff_stats: None,
             details: None,
             has_separator_before: false,
+            search_query: None,
         }
     }
 
@@ -127,6 +131,11 @@ impl<'a> RowVmBuilder<'a> {
         self
     }
 
+    pub(super) fn search_query(mut self, search_query: Option<&str>) -> Self {
+        self.search_query = search_query.map(ToOwned::to_owned);
+        self
+    }
+
     pub(super) fn build(self) -> TreeRowVm {
         let (prefix, suffix) = self
             .node
@@ -167,6 +176,7 @@ impl<'a> RowVmBuilder<'a> {
             height: row_height(self.details.as_ref()),
             details: self.details,
             has_separator_before: self.has_separator_before,
+            search_query: self.search_query,
         }
     }
 }
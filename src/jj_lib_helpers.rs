@@ -1 +1 @@
-pub use jju_jj::repo::{CommitDetails, JjRepo};
+pub use jju_jj::repo::{CommitDetails, JjRepo, OpHeadsWatch, OperationLogEntry};